@@ -1,6 +1,8 @@
 mod parser;
+mod systems;
 
 pub use parser::*;
+pub use systems::*;
 
 #[cfg(doctest)]
 mod test_readme {
@@ -1,23 +1,250 @@
+use std::f32::consts::{PI, TAU};
+
 use nom::{
-    branch::alt, bytes::complete::tag, combinator::map, number::complete::float, sequence::tuple,
-    IResult,
+    branch::alt,
+    character::complete::multispace0 as multispace,
+    combinator::{map, opt},
+    sequence::{delimited, pair},
 };
 
+use super::common::{number_parser, number_parser_f64, token};
+use super::error::{parse_all, IResult, ParseError};
+
+/// Unit of an [`Angle`], see [mdn](https://developer.mozilla.org/en-US/docs/Web/CSS/angle)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum AngleUnit {
+    /// Degrees, 360 per turn
+    Deg,
+    /// Radians, 2π per turn, also used for numbers without unit
+    #[default]
+    Rad,
+    /// Gradians, 400 per turn
+    Grad,
+    /// Turns
+    Turn,
+}
+
+impl AngleUnit {
+    /// Returns the suffix of the unit, like `deg`
+    pub fn suffix(self) -> &'static str {
+        match self {
+            AngleUnit::Deg => "deg",
+            AngleUnit::Rad => "rad",
+            AngleUnit::Grad => "grad",
+            AngleUnit::Turn => "turn",
+        }
+    }
+
+    /// Converts a value in this unit into radians
+    pub fn to_radians(self, value: f32) -> f32 {
+        match self {
+            AngleUnit::Deg => value.to_radians(),
+            AngleUnit::Rad => value,
+            AngleUnit::Grad => value * (PI / 200.0),
+            AngleUnit::Turn => value * TAU,
+        }
+    }
+
+    /// Converts a value in this unit into radians with double precision
+    pub fn to_radians_f64(self, value: f64) -> f64 {
+        match self {
+            AngleUnit::Deg => value.to_radians(),
+            AngleUnit::Rad => value,
+            AngleUnit::Grad => value * (std::f64::consts::PI / 200.0),
+            AngleUnit::Turn => value * std::f64::consts::TAU,
+        }
+    }
+
+    /// Converts radians into a value in this unit
+    pub fn from_radians(self, radians: f32) -> f32 {
+        match self {
+            AngleUnit::Deg => radians.to_degrees(),
+            AngleUnit::Rad => radians,
+            AngleUnit::Grad => radians * (200.0 / PI),
+            AngleUnit::Turn => radians / TAU,
+        }
+    }
+
+    /// Returns the value of a full turn in this unit
+    fn full_turn(self) -> f32 {
+        match self {
+            AngleUnit::Deg => 360.0,
+            AngleUnit::Rad => TAU,
+            AngleUnit::Grad => 400.0,
+            AngleUnit::Turn => 1.0,
+        }
+    }
+}
+
+/// How angles are normalized into a range of a full turn
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum AngleNormalization {
+    /// Angles are kept as they are
+    #[default]
+    Keep,
+    /// Angles are wrapped into `[0, 2π)`
+    Positive,
+    /// Angles are wrapped into `(−π, π]`
+    Signed,
+}
+
+impl AngleNormalization {
+    /// Normalizes an angle in radians
+    pub fn apply(self, radians: f32) -> f32 {
+        self.wrap(radians, TAU)
+    }
+
+    /// Normalizes an angle in radians with double precision
+    pub fn apply_f64(self, radians: f64) -> f64 {
+        self.wrap(radians, std::f64::consts::TAU)
+    }
+
+    /// Normalizes a value given the value of a full turn in its unit
+    fn wrap<T: WrapFloat>(self, value: T, full_turn: T) -> T {
+        match self {
+            AngleNormalization::Keep => value,
+            // rem_euclid may round up to the full turn for tiny negative values
+            AngleNormalization::Positive => match value.rem_euclid(full_turn) {
+                wrapped if wrapped >= full_turn => T::ZERO,
+                wrapped => wrapped,
+            },
+            AngleNormalization::Signed => {
+                match AngleNormalization::Positive.wrap(value, full_turn) {
+                    wrapped if wrapped > full_turn.half() => wrapped - full_turn,
+                    wrapped => wrapped,
+                }
+            }
+        }
+    }
+}
+
+/// The float types that [`AngleNormalization`] wraps values of
+trait WrapFloat: Copy + PartialOrd + std::ops::Sub<Output = Self> {
+    const ZERO: Self;
+    fn rem_euclid(self, rhs: Self) -> Self;
+    fn half(self) -> Self;
+}
+
+impl WrapFloat for f32 {
+    const ZERO: Self = 0.0;
+    fn rem_euclid(self, rhs: Self) -> Self {
+        f32::rem_euclid(self, rhs)
+    }
+    fn half(self) -> Self {
+        self / 2.0
+    }
+}
+
+impl WrapFloat for f64 {
+    const ZERO: Self = 0.0;
+    fn rem_euclid(self, rhs: Self) -> Self {
+        f64::rem_euclid(self, rhs)
+    }
+    fn half(self) -> Self {
+        self / 2.0
+    }
+}
+
+/// An angle that remembers the unit it was written in, like `0.25turn`
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Angle {
+    pub value: f32,
+    pub unit: AngleUnit,
+}
+
+impl Angle {
+    pub fn new(value: f32, unit: AngleUnit) -> Self {
+        Self { value, unit }
+    }
+
+    /// Creates an angle in radians
+    pub fn from_radians(radians: f32) -> Self {
+        Self::new(radians, AngleUnit::Rad)
+    }
+
+    /// Returns the angle in radians
+    pub fn radians(&self) -> f32 {
+        self.unit.to_radians(self.value)
+    }
+
+    /// Returns the same angle in another unit
+    pub fn to_unit(&self, unit: AngleUnit) -> Self {
+        if unit == self.unit {
+            *self
+        } else {
+            Self::new(unit.from_radians(self.radians()), unit)
+        }
+    }
+
+    /// Returns the angle normalized in its own unit, so `450deg` becomes `90deg`
+    pub fn normalized(&self, normalization: AngleNormalization) -> Self {
+        Self::new(
+            normalization.wrap(self.value, self.unit.full_turn()),
+            self.unit,
+        )
+    }
+}
+
+/// Parses the unit of an angle, numbers without unit are radians
+fn angle_unit_parser(input: &str) -> IResult<&str, AngleUnit> {
+    map(
+        opt(alt((
+            map(token("deg"), |_| AngleUnit::Deg),
+            map(token("grad"), |_| AngleUnit::Grad),
+            map(token("rad"), |_| AngleUnit::Rad),
+            map(token("turn"), |_| AngleUnit::Turn),
+        ))),
+        Option::unwrap_or_default,
+    )(input)
+}
+
+/// Parser for a angle value string into an [`Angle`] that keeps its unit.
+///
+/// Supports the same formats as [`angle_parser`].
+pub fn typed_angle_parser(input: &str) -> IResult<&str, Angle> {
+    delimited(
+        multispace,
+        map(pair(number_parser, angle_unit_parser), |(value, unit)| {
+            Angle::new(value, unit)
+        }),
+        multispace,
+    )(input)
+}
+
 /// Parser for a angle value string.
 ///
 /// Supported Formats:
 /// * 60deg / 10.234deg / -45deg (interpreted as degrees, converted to radians)
 /// * 3.1415rad (is interpreted as radians)
 /// * 3.1415 (is interpreted as radians)
+/// * 100grad (interpreted as gradians, converted to radians)
+/// * 0.25turn (interpreted as turns, converted to radians)
 ///
-/// TODO: support grad, turn suffixes, f64 version?
-/// https://developer.mozilla.org/en-US/docs/Web/CSS/angle
+/// See [mdn](https://developer.mozilla.org/en-US/docs/Web/CSS/angle) for more information.
 pub fn angle_parser(input: &str) -> IResult<&str, f32> {
-    alt((
-        map(tuple((float, tag("deg"))), |(val, _)| val.to_radians()),
-        map(tuple((float, tag("rad"))), |(val, _)| val),
-        map(float, |val| val),
-    ))(input.trim())
+    map(typed_angle_parser, |angle| angle.radians())(input)
+}
+
+/// Parser for a angle value string, like [`angle_parser`] but with double precision
+pub fn angle_parser_f64(input: &str) -> IResult<&str, f64> {
+    delimited(
+        multispace,
+        map(
+            pair(number_parser_f64, angle_unit_parser),
+            |(value, unit)| unit.to_radians_f64(value),
+        ),
+        multispace,
+    )(input)
+}
+
+/// Parser for a angle value string, like [`angle_parser`] but normalized
+///
+/// For example `270deg` -> `-π/2` with [`AngleNormalization::Signed`].
+pub fn angle_normalized_parser(
+    input: &str,
+    normalization: AngleNormalization,
+) -> IResult<&str, f32> {
+    map(angle_parser, |radians| normalization.apply(radians))(input)
 }
 
 /// Wrapper for [`angle_parser`] that returns an optional f32
@@ -25,6 +252,77 @@ pub fn angle_string_parser(input: &str) -> Option<f32> {
     angle_parser(input).map(|(_, value)| value).ok()
 }
 
+/// Wrapper for [`angle_parser`] that returns a [`ParseError`] on failure
+///
+/// Unlike [`angle_string_parser`] the whole input must be consumed.
+pub fn angle_result_parser(input: &str) -> Result<f32, ParseError> {
+    parse_all(input, angle_parser)
+}
+
+/// Wrapper for [`angle_parser_f64`] that returns an optional f64
+pub fn angle_string_parser_f64(input: &str) -> Option<f64> {
+    angle_parser_f64(input).map(|(_, value)| value).ok()
+}
+
+/// Wrapper for [`angle_parser_f64`] that returns a [`ParseError`] on failure
+///
+/// Unlike [`angle_string_parser_f64`] the whole input must be consumed.
+pub fn angle_result_parser_f64(input: &str) -> Result<f64, ParseError> {
+    parse_all(input, angle_parser_f64)
+}
+
+/// Wrapper for [`angle_normalized_parser`] that returns an optional f32
+pub fn angle_normalized_string_parser(
+    input: &str,
+    normalization: AngleNormalization,
+) -> Option<f32> {
+    angle_normalized_parser(input, normalization)
+        .map(|(_, value)| value)
+        .ok()
+}
+
+/// Wrapper for [`angle_normalized_parser`] that returns a [`ParseError`] on failure
+///
+/// Unlike [`angle_normalized_string_parser`] the whole input must be consumed.
+pub fn angle_normalized_result_parser(
+    input: &str,
+    normalization: AngleNormalization,
+) -> Result<f32, ParseError> {
+    parse_all(input, |input| angle_normalized_parser(input, normalization))
+}
+
+/// Wrapper for [`typed_angle_parser`] that returns an optional [`Angle`]
+pub fn typed_angle_string_parser(input: &str) -> Option<Angle> {
+    typed_angle_parser(input).map(|(_, value)| value).ok()
+}
+
+/// Wrapper for [`typed_angle_parser`] that returns a [`ParseError`] on failure
+///
+/// Unlike [`typed_angle_string_parser`] the whole input must be consumed.
+pub fn typed_angle_result_parser(input: &str) -> Result<Angle, ParseError> {
+    parse_all(input, typed_angle_parser)
+}
+
+/// Formats an [`Angle`] in its own unit, like `0.25turn`
+pub fn typed_angle_formatter(angle: Angle) -> String {
+    format!("{}{}", angle.value, angle.unit.suffix())
+}
+
+/// Formats an angle in radians as a string, the counterpart of [`angle_parser`]
+///
+/// Uses degrees with the least decimals that parse back to the same angle,
+/// like `90deg`, or radians if there are none, like `1.2345rad`.
+pub fn angle_formatter(radians: f32) -> String {
+    let degrees = radians.to_degrees();
+    (0..=4)
+        .map(|precision| format!("{:.*}", precision, degrees))
+        .find(|degrees| degrees.parse::<f32>().map(f32::to_radians) == Ok(radians))
+        .map_or_else(
+            || format!("{}rad", radians),
+            |degrees| format!("{}deg", degrees),
+        )
+}
+
 /// Wrapper for [`angle_parser`] that implements a serde deserializer
 #[cfg(feature = "serde")]
 pub fn angle_serde_parser<'de, D>(deserializer: D) -> Result<f32, D::Error>
@@ -33,7 +331,38 @@ where
 {
     use serde::de::Error;
     let s: &str = serde::Deserialize::deserialize(deserializer)?;
-    angle_string_parser(s).ok_or(D::Error::custom("invalid angle string"))
+    angle_result_parser(s)
+        .map_err(|error| D::Error::custom(format!("invalid angle string: {}", error)))
+}
+
+/// Wrapper for [`angle_formatter`] that implements a serde serializer
+#[cfg(feature = "serde")]
+pub fn angle_serde_serializer<S>(angle: &f32, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.serialize_str(&angle_formatter(*angle))
+}
+
+/// Wrapper for [`typed_angle_parser`] that implements a serde deserializer
+#[cfg(feature = "serde")]
+pub fn typed_angle_serde_parser<'de, D>(deserializer: D) -> Result<Angle, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::Error;
+    let s: &str = serde::Deserialize::deserialize(deserializer)?;
+    typed_angle_result_parser(s)
+        .map_err(|error| D::Error::custom(format!("invalid angle string: {}", error)))
+}
+
+/// Wrapper for [`typed_angle_formatter`] that implements a serde serializer
+#[cfg(feature = "serde")]
+pub fn typed_angle_serde_serializer<S>(angle: &Angle, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.serialize_str(&typed_angle_formatter(*angle))
 }
 
 #[cfg(test)]
@@ -58,16 +387,114 @@ mod tests {
         assert_eq!(angle_parser(&format!("{}rad", string)), Ok(("", expected)));
         assert_eq!(angle_parser(string), Ok(("", expected)));
     }
+
+    #[test_case(std::f32::consts::FRAC_PI_2, "90deg" ; "right angle")]
+    #[test_case(-std::f32::consts::PI, "-180deg" ; "negative")]
+    #[test_case(0.0, "0deg" ; "zero")]
+    #[test_case(22.5_f32.to_radians(), "22.5deg" ; "fraction of degrees")]
+    #[test_case(1.234_567_9, "1.2345679rad" ; "radians")]
+    fn test_angle_formatter(radians: f32, expected: &str) {
+        assert_eq!(angle_formatter(radians), expected);
+        assert_eq!(angle_result_parser(expected), Ok(radians));
+    }
+
+    #[test]
+    fn test_angle_result_parser() {
+        assert_eq!(angle_result_parser(" 1.5rad "), Ok(1.5));
+        let error = angle_result_parser("90 deg").unwrap_err();
+        assert_eq!(error.offset, 3);
+        assert_eq!(error.fragment, "deg");
+        assert_eq!(
+            angle_result_parser("deg").unwrap_err().expected,
+            vec!["<number>"]
+        );
+    }
+
+    #[test_case("200grad", std::f32::consts::PI ; "gradians")]
+    #[test_case("0.25turn", std::f32::consts::FRAC_PI_2 ; "turns")]
+    #[test_case("-1turn", -std::f32::consts::TAU ; "negative turns")]
+    fn test_angle_parser_units(string: &str, expected: f32) {
+        let (_, radians) = angle_parser(string).unwrap();
+        assert!(
+            (radians - expected).abs() < 1e-6,
+            "{} != {}",
+            radians,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_angle_parser_f64() {
+        assert_eq!(angle_result_parser_f64("180deg"), Ok(std::f64::consts::PI));
+        assert_eq!(
+            angle_string_parser_f64("0.5turn"),
+            Some(std::f64::consts::PI)
+        );
+        assert_eq!(angle_result_parser_f64(" 1.5 "), Ok(1.5));
+        assert!(angle_result_parser_f64("1.5 rad").is_err());
+    }
+
+    #[test_case("450deg", AngleNormalization::Keep, 450.0 ; "keep")]
+    #[test_case("450deg", AngleNormalization::Positive, 90.0 ; "positive")]
+    #[test_case("-90deg", AngleNormalization::Positive, 270.0 ; "positive from negative")]
+    #[test_case("270deg", AngleNormalization::Signed, -90.0 ; "signed")]
+    #[test_case("-180deg", AngleNormalization::Signed, 180.0 ; "signed excludes minus pi")]
+    #[test_case("-720deg", AngleNormalization::Signed, 0.0 ; "signed full turns")]
+    fn test_angle_normalization(string: &str, normalization: AngleNormalization, degrees: f32) {
+        let radians = angle_normalized_result_parser(string, normalization).unwrap();
+        assert!((radians - degrees.to_radians()).abs() < 1e-5);
+        let radians_f64 = normalization.apply_f64(angle_result_parser_f64(string).unwrap());
+        assert!((radians_f64 - f64::from(degrees).to_radians()).abs() < 1e-12);
+        let angle = typed_angle_result_parser(string)
+            .unwrap()
+            .normalized(normalization);
+        assert_eq!(angle, Angle::new(degrees, AngleUnit::Deg));
+    }
+
+    #[test]
+    fn test_angle_normalization_bounds() {
+        assert_eq!(AngleNormalization::Positive.apply(-1e-9), 0.0);
+        assert_eq!(AngleNormalization::Positive.apply(TAU), 0.0);
+        assert_eq!(AngleNormalization::Signed.apply(-PI), PI);
+    }
+
+    #[test_case("0.25turn", Angle::new(0.25, AngleUnit::Turn) ; "turns")]
+    #[test_case("100grad", Angle::new(100.0, AngleUnit::Grad) ; "gradians")]
+    #[test_case("-45deg", Angle::new(-45.0, AngleUnit::Deg) ; "degrees")]
+    #[test_case("1.5rad", Angle::new(1.5, AngleUnit::Rad) ; "radians")]
+    fn test_typed_angle_formatter(string: &str, expected: Angle) {
+        assert_eq!(typed_angle_result_parser(string), Ok(expected));
+        assert_eq!(typed_angle_formatter(expected), string);
+    }
+
+    #[test]
+    fn test_typed_angle() {
+        assert_eq!(
+            typed_angle_string_parser("1.5"),
+            Some(Angle::from_radians(1.5))
+        );
+        let angle = Angle::new(0.25, AngleUnit::Turn);
+        assert_eq!(angle.radians(), std::f32::consts::FRAC_PI_2);
+        assert_eq!(
+            angle.to_unit(AngleUnit::Deg),
+            Angle::new(90.0, AngleUnit::Deg)
+        );
+        assert_eq!(angle.to_unit(AngleUnit::Turn), angle);
+    }
 }
 
 #[cfg(all(test, feature = "serde"))]
 mod tests_serde {
-    use serde::Deserialize;
+    use super::{Angle, AngleUnit};
+    use serde::{Deserialize, Serialize};
     use std::f32::consts::PI;
 
-    #[derive(Deserialize)]
+    #[derive(Deserialize, Serialize)]
     pub struct Foo {
-        #[serde(deserialize_with = "super::angle_serde_parser")]
+        #[serde(
+            deserialize_with = "super::angle_serde_parser",
+            serialize_with = "super::angle_serde_serializer"
+        )]
         pub angle: f32,
     }
 
@@ -76,4 +503,32 @@ mod tests_serde {
         let foo: Foo = serde_json::from_str(r#"{"angle": "180deg"}"#).unwrap();
         assert_eq!(foo.angle, PI);
     }
+
+    #[test]
+    fn test_angle_serde_serializer() {
+        let foo = Foo { angle: PI };
+        assert_eq!(
+            serde_json::to_string(&foo).unwrap(),
+            r#"{"angle":"180deg"}"#
+        );
+    }
+
+    #[derive(Deserialize, Serialize)]
+    pub struct Bar {
+        #[serde(
+            deserialize_with = "super::typed_angle_serde_parser",
+            serialize_with = "super::typed_angle_serde_serializer"
+        )]
+        pub angle: Angle,
+    }
+
+    #[test]
+    fn test_typed_angle_serde() {
+        let bar: Bar = serde_json::from_str(r#"{"angle": "0.25turn"}"#).unwrap();
+        assert_eq!(bar.angle, Angle::new(0.25, AngleUnit::Turn));
+        assert_eq!(
+            serde_json::to_string(&bar).unwrap(),
+            r#"{"angle":"0.25turn"}"#
+        );
+    }
 }
@@ -0,0 +1,486 @@
+use std::time::Duration;
+
+use nom::{
+    branch::alt,
+    character::complete::multispace0 as multispace,
+    combinator::{eof, map, peek, verify},
+    multi::separated_list1,
+    sequence::{delimited, preceded, terminated, tuple},
+};
+
+use super::common::{comma_parser, identifier_parser, number_parser, token};
+use super::duration::delay_parser;
+use super::error::{parse_all, IResult, InputError, ParseError};
+use super::{duration_parser, easing_parser, easing_result_parser, Easing};
+
+/// How often an animation is played, like the value of `animation-iteration-count`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IterationCount {
+    /// A number of iterations, can be fractional
+    Count(f32),
+    /// `infinite`
+    Infinite,
+}
+
+impl Default for IterationCount {
+    fn default() -> Self {
+        IterationCount::Count(1.0)
+    }
+}
+
+/// The direction of the iterations, like the value of `animation-direction`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AnimationDirection {
+    #[default]
+    Normal,
+    Reverse,
+    /// Every other iteration is reversed, starting with the second
+    Alternate,
+    /// Every other iteration is reversed, starting with the first
+    AlternateReverse,
+}
+
+/// Whether the animation applies before it starts and after it ends, like the value of `animation-fill-mode`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FillMode {
+    #[default]
+    None,
+    /// The last keyframe applies after the animation ended
+    Forwards,
+    /// The first keyframe applies during the delay
+    Backwards,
+    Both,
+}
+
+/// An animation of a node, like an entry of the css `animation` shorthand
+#[derive(Debug, Clone, PartialEq)]
+pub struct Animation {
+    /// The name of the [`super::Keyframes`] to play
+    pub name: String,
+    pub duration: Duration,
+    /// The easing function applied to each interval between keyframes
+    pub easing: Easing,
+    /// The delay in seconds, a negative delay starts the animation partway through
+    pub delay: f32,
+    pub iteration_count: IterationCount,
+    pub direction: AnimationDirection,
+    pub fill_mode: FillMode,
+    /// `paused` or `running`
+    pub paused: bool,
+}
+
+impl Animation {
+    /// Creates an animation of the keyframes with the css defaults
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            duration: Duration::ZERO,
+            easing: Easing::default(),
+            delay: 0.0,
+            iteration_count: IterationCount::default(),
+            direction: AnimationDirection::default(),
+            fill_mode: FillMode::default(),
+            paused: false,
+        }
+    }
+
+    /// Returns the progress within the keyframes after the time since the animation started
+    ///
+    /// The progress is between `0.0` and `1.0` and follows the direction of the current
+    /// iteration, `None` if the animation doesn't apply, like during the delay or
+    /// after it ended without fill mode.
+    pub fn progress(&self, elapsed: Duration) -> Option<f32> {
+        let active = elapsed.as_secs_f32() - self.delay;
+        let duration = self.duration.as_secs_f32();
+        let count = match self.iteration_count {
+            IterationCount::Count(count) => count,
+            IterationCount::Infinite => f32::INFINITY,
+        };
+        if active < 0.0 {
+            return matches!(self.fill_mode, FillMode::Backwards | FillMode::Both)
+                .then(|| self.directed_progress(0.0, 0.0));
+        }
+        let total = if duration == 0.0 {
+            0.0
+        } else {
+            duration * count
+        };
+        if active >= total {
+            if !matches!(self.fill_mode, FillMode::Forwards | FillMode::Both) {
+                return None;
+            }
+            if count == 0.0 {
+                return Some(self.directed_progress(0.0, 0.0));
+            }
+            if count.is_infinite() {
+                // an infinite animation without duration ends at once, its last iteration
+                // has no parity, so it plays forwards unless it is reversed, like in css
+                let reversed = self.direction == AnimationDirection::Reverse;
+                return Some(if reversed { 0.0 } else { 1.0 });
+            }
+            let iteration = count.ceil() - 1.0;
+            return Some(self.directed_progress(iteration, count - iteration));
+        }
+        let iterations = active / duration;
+        let iteration = iterations.floor();
+        Some(self.directed_progress(iteration, iterations - iteration))
+    }
+
+    /// Returns true if the animation ended after the time since it started
+    pub fn is_finished(&self, elapsed: Duration) -> bool {
+        match self.iteration_count {
+            IterationCount::Count(count) => {
+                let duration = self.duration.as_secs_f32();
+                let total = if duration == 0.0 {
+                    0.0
+                } else {
+                    duration * count
+                };
+                elapsed.as_secs_f32() >= self.delay + total
+            }
+            IterationCount::Infinite => false,
+        }
+    }
+
+    /// Applies the direction of the iteration to the progress within it
+    fn directed_progress(&self, iteration: f32, progress: f32) -> f32 {
+        let odd = iteration % 2.0 == 1.0;
+        let reversed = match self.direction {
+            AnimationDirection::Normal => false,
+            AnimationDirection::Reverse => true,
+            AnimationDirection::Alternate => odd,
+            AnimationDirection::AlternateReverse => !odd,
+        };
+        if reversed {
+            1.0 - progress
+        } else {
+            progress
+        }
+    }
+}
+
+/// A part of an animation
+enum AnimationPart {
+    Time(Duration),
+    /// A negative time, which is only valid as delay
+    Delay(f32),
+    Easing(Easing),
+    IterationCount(IterationCount),
+    Direction(AnimationDirection),
+    FillMode(FillMode),
+    Paused(bool),
+    Name(String),
+}
+
+/// Parses a keyword or the name of an animation
+///
+/// The keywords are matched against whole identifiers, so that names like
+/// `reverse-spin` or `ease-in-bounce` are not mistaken for keywords.
+fn animation_keyword_parser(input: &str) -> IResult<&str, AnimationPart> {
+    map(
+        delimited(multispace, identifier_parser, multispace),
+        |name: &str| match name {
+            "infinite" => AnimationPart::IterationCount(IterationCount::Infinite),
+            "normal" => AnimationPart::Direction(AnimationDirection::Normal),
+            "reverse" => AnimationPart::Direction(AnimationDirection::Reverse),
+            "alternate" => AnimationPart::Direction(AnimationDirection::Alternate),
+            "alternate-reverse" => AnimationPart::Direction(AnimationDirection::AlternateReverse),
+            "none" => AnimationPart::FillMode(FillMode::None),
+            "forwards" => AnimationPart::FillMode(FillMode::Forwards),
+            "backwards" => AnimationPart::FillMode(FillMode::Backwards),
+            "both" => AnimationPart::FillMode(FillMode::Both),
+            "running" => AnimationPart::Paused(false),
+            "paused" => AnimationPart::Paused(true),
+            name => match easing_result_parser(name) {
+                Ok(easing) => AnimationPart::Easing(easing),
+                Err(_) => AnimationPart::Name(name.to_string()),
+            },
+        },
+    )(input)
+}
+
+fn animation_part_parser(input: &str) -> IResult<&str, AnimationPart> {
+    alt((
+        map(duration_parser, AnimationPart::Time),
+        map(delay_parser, AnimationPart::Delay),
+        // easing functions with arguments, like `steps(4)`
+        map(
+            preceded(
+                peek(tuple((multispace, identifier_parser, token("(")))),
+                easing_parser,
+            ),
+            AnimationPart::Easing,
+        ),
+        map(
+            delimited(
+                multispace,
+                verify(number_parser, |count| *count >= 0.0),
+                multispace,
+            ),
+            |count| AnimationPart::IterationCount(IterationCount::Count(count)),
+        ),
+        animation_keyword_parser,
+    ))(input)
+}
+
+/// Parses a single animation, the parts can be given in any order
+fn animation_single_parser(input: &str) -> IResult<&str, Animation> {
+    let (mut rest, _) = multispace(input)?;
+    let mut animation = Animation::new("");
+    let (mut name, mut easing, mut count, mut direction, mut fill_mode, mut paused) =
+        (None, None, None, None, None, None);
+    let (mut duration, mut delay) = (None, None);
+    let mut parts = 0;
+    loop {
+        let (next, part) = match animation_part_parser(rest) {
+            Err(nom::Err::Error(_)) if parts > 0 => break,
+            result => result?,
+        };
+        let taken = match part {
+            AnimationPart::Time(value) if duration.is_none() => {
+                duration = Some(value);
+                false
+            }
+            AnimationPart::Time(value) => delay.replace(value.as_secs_f32()).is_some(),
+            AnimationPart::Delay(_) if duration.is_none() => {
+                return Err(nom::Err::Failure(InputError::expected(
+                    rest,
+                    "<non-negative duration>",
+                )));
+            }
+            AnimationPart::Delay(value) => delay.replace(value).is_some(),
+            AnimationPart::Easing(value) => easing.replace(value).is_some(),
+            AnimationPart::IterationCount(value) => count.replace(value).is_some(),
+            AnimationPart::Direction(value) => direction.replace(value).is_some(),
+            AnimationPart::FillMode(value) => fill_mode.replace(value).is_some(),
+            AnimationPart::Paused(value) => paused.replace(value).is_some(),
+            AnimationPart::Name(value) => name.replace(value).is_some(),
+        };
+        if taken {
+            break;
+        }
+        rest = next;
+        parts += 1;
+    }
+    let Some(name) = name else {
+        return Err(nom::Err::Error(InputError::expected(
+            rest,
+            "<animation name>",
+        )));
+    };
+    animation.name = name;
+    animation.duration = duration.unwrap_or(animation.duration);
+    animation.delay = delay.unwrap_or(animation.delay);
+    animation.easing = easing.unwrap_or(animation.easing);
+    animation.iteration_count = count.unwrap_or(animation.iteration_count);
+    animation.direction = direction.unwrap_or(animation.direction);
+    animation.fill_mode = fill_mode.unwrap_or(animation.fill_mode);
+    animation.paused = paused.unwrap_or(animation.paused);
+    Ok((rest, animation))
+}
+
+/// Parser for strings that represent a list of [`Animation`]s, like the value of `animation`.
+///
+/// The syntax follows CSS, animations are separated by commas:
+///
+/// * `pulse 1s` -> keyframes name and duration
+/// * `pulse 1s ease-in-out 200ms` -> with easing function and delay, as in [`super::transition_parser`]
+/// * `pulse 1s -500ms` -> a negative delay starts the animation partway through
+/// * `spin 2s linear infinite` -> `infinite` or a number of iterations, like `2.5`
+/// * `normal`, `reverse`, `alternate`, `alternate-reverse` -> the direction
+/// * `none`, `forwards`, `backwards`, `both` -> the fill mode
+/// * `running`, `paused` -> the play state
+/// * `none` -> no animations
+pub fn animation_parser(input: &str) -> IResult<&str, Vec<Animation>> {
+    alt((
+        map(
+            terminated(delimited(multispace, token("none"), multispace), eof),
+            |_| Vec::new(),
+        ),
+        separated_list1(comma_parser, animation_single_parser),
+    ))(input)
+}
+
+/// Wrapper for [`animation_parser`] that returns an optional list of [`Animation`]s
+pub fn animation_string_parser(input: &str) -> Option<Vec<Animation>> {
+    animation_parser(input).map(|(_, value)| value).ok()
+}
+
+/// Wrapper for [`animation_parser`] that returns a [`ParseError`] on failure
+///
+/// Unlike [`animation_string_parser`] the whole input must be consumed.
+pub fn animation_result_parser(input: &str) -> Result<Vec<Animation>, ParseError> {
+    parse_all(input, animation_parser)
+}
+
+/// Wrapper for [`animation_parser`] that implements a serde deserializer
+#[cfg(feature = "serde")]
+pub fn animation_serde_parser<'de, D>(deserializer: D) -> Result<Vec<Animation>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::Error;
+    let s: &str = serde::Deserialize::deserialize(deserializer)?;
+    animation_result_parser(s)
+        .map_err(|error| D::Error::custom(format!("invalid animation string: {}", error)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test]
+    fn test_animation_parser() {
+        let animations =
+            animation_result_parser("pulse 1s ease-in-out 200ms infinite alternate both paused")
+                .unwrap();
+        assert_eq!(
+            animations,
+            vec![Animation {
+                name: "pulse".to_string(),
+                duration: Duration::from_secs(1),
+                easing: Easing::EASE_IN_OUT,
+                delay: 0.2,
+                iteration_count: IterationCount::Infinite,
+                direction: AnimationDirection::Alternate,
+                fill_mode: FillMode::Both,
+                paused: true,
+            }]
+        );
+    }
+
+    #[test_case("spin 2s linear 3", "spin", IterationCount::Count(3.0) ; "iteration count")]
+    #[test_case("2.5 steps(4) 1s reverse-spin", "reverse-spin", IterationCount::Count(2.5) ; "name like a keyword")]
+    #[test_case("ease-in-bounce 1s", "ease-in-bounce", IterationCount::Count(1.0) ; "name like an easing")]
+    fn test_animation_parser_parts(string: &str, name: &str, count: IterationCount) {
+        let animations = animation_result_parser(string).unwrap();
+        assert_eq!(animations[0].name, name);
+        assert_eq!(animations[0].iteration_count, count);
+    }
+
+    #[test]
+    fn test_animation_parser_list() {
+        let animations = animation_result_parser("fade 1s, slide 500ms backwards").unwrap();
+        assert_eq!(animations.len(), 2);
+        assert_eq!(animations[1].fill_mode, FillMode::Backwards);
+        assert_eq!(animation_string_parser(" none "), Some(vec![]));
+        assert_eq!(
+            animation_result_parser("fade none 1s").unwrap()[0].fill_mode,
+            FillMode::None
+        );
+    }
+
+    #[test_case("1s ease" ; "without name")]
+    #[test_case("a b 1s" ; "two names")]
+    #[test_case("fade 1s 2s 3s" ; "too many times")]
+    #[test_case("fade -1" ; "negative iteration count")]
+    #[test_case("fade -1s" ; "negative duration")]
+    fn test_animation_parser_invalid(string: &str) {
+        assert!(animation_result_parser(string).is_err());
+    }
+
+    #[test_case(AnimationDirection::Normal, 2500, Some(0.5) ; "normal")]
+    #[test_case(AnimationDirection::Reverse, 2250, Some(0.75) ; "reverse")]
+    #[test_case(AnimationDirection::Alternate, 2250, Some(0.25) ; "alternate first")]
+    #[test_case(AnimationDirection::Alternate, 3250, Some(0.75) ; "alternate second")]
+    #[test_case(AnimationDirection::AlternateReverse, 2250, Some(0.75) ; "alternate reverse")]
+    #[test_case(AnimationDirection::Normal, 500, None ; "delay")]
+    #[test_case(AnimationDirection::Normal, 5000, None ; "ended")]
+    fn test_animation_progress(direction: AnimationDirection, elapsed: u64, expected: Option<f32>) {
+        let animation = Animation {
+            duration: Duration::from_secs(1),
+            delay: 2.0,
+            iteration_count: IterationCount::Count(2.0),
+            direction,
+            ..Animation::new("test")
+        };
+        let progress = animation.progress(Duration::from_millis(elapsed));
+        assert_eq!(
+            progress.map(|progress| (progress * 1e4).round() / 1e4),
+            expected
+        );
+    }
+
+    #[test_case(FillMode::Backwards, 500, Some(0.0) ; "backwards during delay")]
+    #[test_case(FillMode::Forwards, 500, None ; "forwards during delay")]
+    #[test_case(FillMode::Forwards, 5000, Some(0.5) ; "forwards after fractional end")]
+    #[test_case(FillMode::Both, 5000, Some(0.5) ; "both after fractional end")]
+    fn test_animation_progress_fill_mode(fill_mode: FillMode, elapsed: u64, expected: Option<f32>) {
+        let animation = Animation {
+            duration: Duration::from_secs(1),
+            delay: 1.0,
+            iteration_count: IterationCount::Count(1.5),
+            fill_mode,
+            ..Animation::new("test")
+        };
+        assert_eq!(animation.progress(Duration::from_millis(elapsed)), expected);
+        assert_eq!(
+            animation.is_finished(Duration::from_millis(elapsed)),
+            elapsed >= 2500
+        );
+    }
+
+    #[test]
+    fn test_animation_parser_negative_delay() {
+        let animation = animation_result_parser("pulse 1s -500ms")
+            .unwrap()
+            .remove(0);
+        assert_eq!(animation.delay, -0.5);
+        assert_eq!(animation.progress(Duration::from_millis(250)), Some(0.75));
+        assert!(animation.is_finished(Duration::from_millis(500)));
+    }
+
+    #[test_case(AnimationDirection::Normal, Some(1.0) ; "normal")]
+    #[test_case(AnimationDirection::Reverse, Some(0.0) ; "reverse")]
+    #[test_case(AnimationDirection::Alternate, Some(1.0) ; "alternate")]
+    #[test_case(AnimationDirection::AlternateReverse, Some(1.0) ; "alternate reverse")]
+    fn test_animation_progress_zero_duration_infinite(
+        direction: AnimationDirection,
+        expected: Option<f32>,
+    ) {
+        let animation = Animation {
+            direction,
+            ..animation_result_parser("pulse 0s infinite forwards")
+                .unwrap()
+                .remove(0)
+        };
+        assert_eq!(animation.progress(Duration::from_millis(10)), expected);
+    }
+
+    #[test_case("pulse 1s 1e30", 1000, false ; "huge count")]
+    #[test_case("pulse 0s 1e30", 0, true ; "zero duration")]
+    #[test_case("pulse 1s 1s 0", 999, false ; "zero count during delay")]
+    #[test_case("pulse 1s 1s 0", 1000, true ; "zero count after delay")]
+    fn test_animation_is_finished(input: &str, elapsed: u64, expected: bool) {
+        let animation = animation_result_parser(input).unwrap().remove(0);
+        assert_eq!(
+            animation.is_finished(Duration::from_millis(elapsed)),
+            expected
+        );
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests_serde {
+    use super::{Animation, Easing, IterationCount};
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    pub struct Foo {
+        #[serde(deserialize_with = "super::animation_serde_parser")]
+        pub animation: Vec<Animation>,
+    }
+
+    #[test]
+    fn test_animation_serde_parser() {
+        let foo: Foo =
+            serde_json::from_str(r#"{"animation": "pulse 300ms steps(4, end) 2"}"#).unwrap();
+        assert_eq!(
+            foo.animation[0].easing,
+            Easing::Steps(4, Default::default())
+        );
+        assert_eq!(foo.animation[0].iteration_count, IterationCount::Count(2.0));
+        assert!(serde_json::from_str::<Foo>(r#"{"animation": "300ms"}"#).is_err());
+    }
+}
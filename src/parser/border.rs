@@ -0,0 +1,425 @@
+use bevy::{
+    render::color::Color,
+    ui::{BorderColor, Style, UiRect, Val},
+};
+use nom::{
+    branch::alt, character::complete::multispace0 as multispace, combinator::map, multi::many_m_n,
+};
+
+use super::color_parser;
+use super::common::{identifier_parser, token};
+use super::error::{parse_all, IResult, InputError, ParseError};
+use super::val::non_negative_length_parser;
+
+/// Border styles that can be rendered, a border with style `none` or `hidden` has no width
+const BORDER_STYLES: &[&str] = &["solid", "none", "hidden"];
+
+/// Border styles of css that bevy can't render, with the description reported as error
+const UNSUPPORTED_BORDER_STYLES: &[(&str, &str)] = &[
+    ("dashed", "<unsupported border style `dashed`>"),
+    ("dotted", "<unsupported border style `dotted`>"),
+    ("double", "<unsupported border style `double`>"),
+    ("groove", "<unsupported border style `groove`>"),
+    ("ridge", "<unsupported border style `ridge`>"),
+    ("inset", "<unsupported border style `inset`>"),
+    ("outset", "<unsupported border style `outset`>"),
+];
+
+/// Width and color of a single border, like the value of `border` or `border-top`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BorderLine {
+    pub width: Val,
+    pub color: Option<Color>,
+}
+
+/// A side of a ui node, for the single side shorthands like `border-top`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BorderSide {
+    Top,
+    Right,
+    Bottom,
+    Left,
+}
+
+impl BorderSide {
+    /// Returns the edge of the rect that belongs to this side
+    pub fn edge_mut(self, rect: &mut UiRect) -> &mut Val {
+        match self {
+            BorderSide::Top => &mut rect.top,
+            BorderSide::Right => &mut rect.right,
+            BorderSide::Bottom => &mut rect.bottom,
+            BorderSide::Left => &mut rect.left,
+        }
+    }
+}
+
+impl BorderLine {
+    /// Writes the width into the border of the given side and the color, if any, into the
+    /// [`BorderColor`]
+    ///
+    /// Bevy has a single color for all sides, so the color applies to the whole border.
+    pub fn apply(&self, side: BorderSide, style: &mut Style, border_color: &mut BorderColor) {
+        *side.edge_mut(&mut style.border) = self.width;
+        if let Some(color) = self.color {
+            border_color.0 = color;
+        }
+    }
+}
+
+/// Widths and color of the borders of a ui node, like the value of `border`
+///
+/// The color is `None` if the shorthand didn't specify one.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Border {
+    pub width: UiRect,
+    pub color: Option<Color>,
+}
+
+impl Border {
+    /// Writes the widths into the style and the color, if any, into the [`BorderColor`]
+    pub fn apply(&self, style: &mut Style, border_color: &mut BorderColor) {
+        style.border = self.width;
+        if let Some(color) = self.color {
+            border_color.0 = color;
+        }
+    }
+}
+
+/// A part of a border shorthand
+enum BorderPart {
+    Width(Val),
+    Style(bool),
+    Color(Color),
+}
+
+/// Parses a border width, like `2px`, `0` or `thin`
+///
+/// Negative widths are invalid like in css, they fail without backtracking.
+fn border_width_parser(input: &str) -> IResult<&str, Val> {
+    let (input, _) = multispace(input)?;
+    let (rest, width) = alt((
+        map(token("thin"), |_| Val::Px(1.0)),
+        map(token("medium"), |_| Val::Px(3.0)),
+        map(token("thick"), |_| Val::Px(5.0)),
+        non_negative_length_parser,
+    ))(input)?;
+    multispace(rest).map(|(rest, _)| (rest, width))
+}
+
+/// Parses a border style, returns whether the border is visible
+///
+/// Styles that can't be rendered, like `dashed`, fail without backtracking and
+/// name the unsupported style in the expected token.
+fn border_style_parser(input: &str) -> IResult<&str, bool> {
+    let (rest, _) = multispace(input)?;
+    let error = |input| InputError {
+        input,
+        expected: BORDER_STYLES.to_vec(),
+    };
+    let (after, name) = identifier_parser(rest).map_err(|_| nom::Err::Error(error(rest)))?;
+    if let Some((_, expected)) = UNSUPPORTED_BORDER_STYLES
+        .iter()
+        .find(|(style, _)| *style == name)
+    {
+        return Err(nom::Err::Failure(InputError::expected(rest, expected)));
+    }
+    if !BORDER_STYLES.contains(&name) {
+        return Err(nom::Err::Error(error(rest)));
+    }
+    let (after, _) = multispace(after)?;
+    Ok((after, name == "solid"))
+}
+
+/// Parser for strings that represent a [`BorderLine`], like the value of `border-top`.
+///
+/// A width, style and color in any order, each of them is optional:
+///
+/// * `2px solid #333` -> 2px wide and dark gray
+/// * `1px red` -> 1px wide and red, the style defaults to `solid`
+/// * `thin`, `medium`, `thick` -> 1px, 3px and 5px, the width defaults to `medium`
+/// * `0 solid red` -> a zero width may omit the unit
+/// * `none`, `hidden` -> no border, the width is `0px`
+///
+/// Styles that bevy can't render, like `dashed` or `dotted`, are reported as error.
+pub fn border_line_parser(input: &str) -> IResult<&str, BorderLine> {
+    let (mut input, _) = multispace(input)?;
+    let (mut width, mut visible, mut color) = (None, None, None);
+    let mut parts = 0;
+    loop {
+        let (rest, part) = match alt((
+            map(border_width_parser, BorderPart::Width),
+            map(border_style_parser, BorderPart::Style),
+            map(color_parser, BorderPart::Color),
+        ))(input)
+        {
+            Err(nom::Err::Error(_)) if parts > 0 => break,
+            result => result?,
+        };
+        // a duplicate part ends the shorthand and stays in the remaining input
+        match part {
+            BorderPart::Width(value) if width.is_none() => width = Some(value),
+            BorderPart::Style(value) if visible.is_none() => visible = Some(value),
+            BorderPart::Color(value) if color.is_none() => color = Some(value),
+            _ => break,
+        }
+        input = rest;
+        parts += 1;
+    }
+    let width = match visible {
+        Some(false) => Val::Px(0.0),
+        _ => width.unwrap_or(Val::Px(3.0)),
+    };
+    Ok((input, BorderLine { width, color }))
+}
+
+/// Wrapper for [`border_line_parser`] that returns an optional [`BorderLine`]
+pub fn border_line_string_parser(input: &str) -> Option<BorderLine> {
+    border_line_parser(input).map(|(_, value)| value).ok()
+}
+
+/// Wrapper for [`border_line_parser`] that returns a [`ParseError`] on failure
+///
+/// Unlike [`border_line_string_parser`] the whole input must be consumed.
+pub fn border_line_result_parser(input: &str) -> Result<BorderLine, ParseError> {
+    parse_all(input, border_line_parser)
+}
+
+/// Parser for strings that represent a [`Border`], like the value of `border`.
+///
+/// Supports the same syntax as [`border_line_parser`], the width is used for all sides.
+pub fn border_parser(input: &str) -> IResult<&str, Border> {
+    map(border_line_parser, |line| Border {
+        width: UiRect::all(line.width),
+        color: line.color,
+    })(input)
+}
+
+/// Wrapper for [`border_parser`] that returns an optional [`Border`]
+pub fn border_string_parser(input: &str) -> Option<Border> {
+    border_parser(input).map(|(_, value)| value).ok()
+}
+
+/// Wrapper for [`border_parser`] that returns a [`ParseError`] on failure
+///
+/// Unlike [`border_string_parser`] the whole input must be consumed.
+pub fn border_result_parser(input: &str) -> Result<Border, ParseError> {
+    parse_all(input, border_parser)
+}
+
+/// Parser for strings that represent the border widths of a ui node, like the value of
+/// `border-width`.
+///
+/// One to four widths as supported by [`border_line_parser`], in the same order as
+/// [`super::rect_parser`]. Negative widths are reported as error.
+pub fn border_width_rect_parser(input: &str) -> IResult<&str, UiRect> {
+    map(many_m_n(1, 4, border_width_parser), |widths| {
+        match widths[..] {
+            [all] => UiRect::all(all),
+            [top_bottom, left_right] => UiRect::new(left_right, left_right, top_bottom, top_bottom),
+            [top, left_right, bottom] => UiRect::new(left_right, left_right, top, bottom),
+            [top, right, bottom, left, ..] => UiRect::new(left, right, top, bottom),
+            [] => unreachable!(),
+        }
+    })(input)
+}
+
+/// Wrapper for [`border_width_rect_parser`] that returns an optional [`UiRect`]
+pub fn border_width_rect_string_parser(input: &str) -> Option<UiRect> {
+    border_width_rect_parser(input).map(|(_, value)| value).ok()
+}
+
+/// Wrapper for [`border_width_rect_parser`] that returns a [`ParseError`] on failure
+///
+/// Unlike [`border_width_rect_string_parser`] the whole input must be consumed.
+pub fn border_width_rect_result_parser(input: &str) -> Result<UiRect, ParseError> {
+    parse_all(input, border_width_rect_parser)
+}
+
+/// Parser for strings that represent a [`BorderColor`], like the value of `border-color`.
+///
+/// One to four colors as supported by [`super::color_parser`], in the order of the css
+/// shorthands. Because [`BorderColor`] has a single color for all sides, all colors must
+/// be the same, compared in rgba so that `red` and `hsl(0 100% 50%)` are the same color.
+pub fn border_color_parser(input: &str) -> IResult<&str, BorderColor> {
+    let (mut input, first) = color_parser(input)?;
+    for _ in 1..4 {
+        match color_parser(input) {
+            Ok((rest, color)) if color.as_rgba_f32() == first.as_rgba_f32() => input = rest,
+            Ok(_) => {
+                return Err(nom::Err::Error(InputError::expected(
+                    input,
+                    "<same color for all sides>",
+                )))
+            }
+            Err(nom::Err::Error(_)) => break,
+            Err(error) => return Err(error),
+        }
+    }
+    Ok((input, BorderColor(first)))
+}
+
+/// Wrapper for [`border_color_parser`] that returns an optional [`BorderColor`]
+pub fn border_color_string_parser(input: &str) -> Option<BorderColor> {
+    border_color_parser(input).map(|(_, value)| value).ok()
+}
+
+/// Wrapper for [`border_color_parser`] that returns a [`ParseError`] on failure
+///
+/// Unlike [`border_color_string_parser`] the whole input must be consumed.
+pub fn border_color_result_parser(input: &str) -> Result<BorderColor, ParseError> {
+    parse_all(input, border_color_parser)
+}
+
+/// Wrapper for [`border_parser`] that implements a serde deserializer
+#[cfg(feature = "serde")]
+pub fn border_serde_parser<'de, D>(deserializer: D) -> Result<Border, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::Error;
+    let s: &str = serde::Deserialize::deserialize(deserializer)?;
+    border_result_parser(s)
+        .map_err(|error| D::Error::custom(format!("invalid border string: {}", error)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("2px solid #333", Val::Px(2.0), Some(Color::rgb_u8(0x33, 0x33, 0x33)) ; "width style color")]
+    #[test_case("red 1px", Val::Px(1.0), Some(Color::RED) ; "color width")]
+    #[test_case("solid 10%", Val::Percent(10.0), None ; "style width")]
+    #[test_case("thick", Val::Px(5.0), None ; "keyword width")]
+    #[test_case("solid blue", Val::Px(3.0), Some(Color::BLUE) ; "default width")]
+    #[test_case("4px none blue", Val::Px(0.0), Some(Color::BLUE) ; "no style")]
+    #[test_case(" hidden ", Val::Px(0.0), None ; "hidden style")]
+    #[test_case("0 solid red", Val::Px(0.0), Some(Color::RED) ; "zero width")]
+    #[test_case("red 0", Val::Px(0.0), Some(Color::RED) ; "color zero width")]
+    fn test_border_line_parser(string: &str, width: Val, color: Option<Color>) {
+        assert_eq!(
+            border_line_result_parser(string),
+            Ok(BorderLine { width, color })
+        );
+    }
+
+    #[test]
+    fn test_border_parser() {
+        let border = border_result_parser("2px solid #333").unwrap();
+        assert_eq!(border.width, UiRect::all(Val::Px(2.0)));
+        let mut style = Style::default();
+        let mut border_color = BorderColor(Color::NONE);
+        border.apply(&mut style, &mut border_color);
+        assert_eq!(style.border, UiRect::all(Val::Px(2.0)));
+        assert_eq!(border_color.0, Color::rgb_u8(0x33, 0x33, 0x33));
+        assert_eq!(border_string_parser("1px").unwrap().color, None);
+    }
+
+    #[test_case("2px dashed red", 4, vec!["<unsupported border style `dashed`>"] ; "dashed")]
+    #[test_case("dotted", 0, vec!["<unsupported border style `dotted`>"] ; "dotted")]
+    #[test_case("1px 2px", 4, vec!["<end of input>"] ; "duplicate width")]
+    #[test_case("1px red blue", 8, vec!["<end of input>"] ; "duplicate color")]
+    #[test_case("-2px solid red", 0, vec!["<non-negative length>"] ; "negative width")]
+    #[test_case("solid -1vw", 6, vec!["<non-negative length>"] ; "negative viewport width")]
+    #[test_case("1 solid", 1, vec!["px", "%", "vw", "vh", "vmin", "vmax"] ; "unitless width")]
+    fn test_border_line_parser_error(string: &str, offset: usize, expected: Vec<&str>) {
+        let error = border_line_result_parser(string).unwrap_err();
+        assert_eq!(error.offset, offset);
+        assert_eq!(error.expected, expected);
+    }
+
+    #[test]
+    fn test_border_line_parser_keeps_first_duplicate() {
+        assert_eq!(
+            border_line_parser("2px 3px"),
+            Ok((
+                "3px",
+                BorderLine {
+                    width: Val::Px(2.0),
+                    color: None
+                }
+            ))
+        );
+        assert_eq!(
+            border_line_string_parser("2px red blue").unwrap().color,
+            Some(Color::RED)
+        );
+    }
+
+    #[test]
+    fn test_border_line_apply() {
+        let mut style = Style::default();
+        let mut border_color = BorderColor(Color::NONE);
+        let line = border_line_result_parser("1px red").unwrap();
+        line.apply(BorderSide::Top, &mut style, &mut border_color);
+        assert_eq!(style.border.top, Val::Px(1.0));
+        assert_eq!(style.border.left, Val::Px(0.0));
+        assert_eq!(border_color.0, Color::RED);
+    }
+
+    #[test_case("2px", UiRect::all(Val::Px(2.0)) ; "one width")]
+    #[test_case("1px thick", UiRect::new(Val::Px(5.0), Val::Px(5.0), Val::Px(1.0), Val::Px(1.0)) ; "two widths")]
+    #[test_case("1px 0 2px 3px", UiRect::new(Val::Px(3.0), Val::Px(0.0), Val::Px(1.0), Val::Px(2.0)) ; "four widths")]
+    fn test_border_width_rect_parser(string: &str, expected: UiRect) {
+        assert_eq!(border_width_rect_result_parser(string), Ok(expected));
+    }
+
+    #[test]
+    fn test_border_width_rect_parser_error() {
+        let error = border_width_rect_result_parser("1px -1px").unwrap_err();
+        assert_eq!(error.offset, 4);
+        assert_eq!(error.expected, vec!["<non-negative length>"]);
+        assert!(border_width_rect_string_parser("red").is_none());
+    }
+
+    #[test_case("red", Color::RED ; "single color")]
+    #[test_case("red red", Color::RED ; "two colors")]
+    #[test_case("#f00 red rgb(255 0 0) red", Color::RED ; "four colors")]
+    #[test_case("red hsl(0 100% 50%)", Color::RED ; "different color spaces")]
+    fn test_border_color_parser(string: &str, expected: Color) {
+        assert_eq!(border_color_result_parser(string).unwrap().0, expected);
+    }
+
+    #[test]
+    fn test_border_line_parser_error_message() {
+        let error = border_line_result_parser("2px dashed red").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "unexpected \"dashed\" at offset 4, \
+             expected <unsupported border style `dashed`>"
+        );
+    }
+
+    #[test]
+    fn test_border_color_parser_error() {
+        let error = border_color_result_parser("red red blue").unwrap_err();
+        assert_eq!(error.offset, 8);
+        assert_eq!(error.expected, vec!["<same color for all sides>"]);
+        assert!(border_color_string_parser("1px").is_none());
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests_serde {
+    use super::Border;
+    use bevy::{render::color::Color, ui::UiRect, ui::Val};
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    pub struct Foo {
+        #[serde(deserialize_with = "super::border_serde_parser")]
+        pub border: Border,
+    }
+
+    #[test]
+    fn test_border_serde_parser() {
+        let foo: Foo = serde_json::from_str(r#"{"border": "1px solid red"}"#).unwrap();
+        assert_eq!(
+            foo.border,
+            Border {
+                width: UiRect::all(Val::Px(1.0)),
+                color: Some(Color::RED),
+            }
+        );
+    }
+}
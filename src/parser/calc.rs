@@ -0,0 +1,379 @@
+use bevy::{math::Vec2, ui::Val};
+use nom::{
+    branch::alt,
+    character::complete::{multispace0 as multispace, multispace1},
+    combinator::map,
+    multi::{fold_many0, separated_list1},
+    sequence::{delimited, pair, tuple},
+    Parser,
+};
+
+use super::common::{comma_parser, function_parser, number_parser, token};
+use super::error::{parse_all, IResult, InputError, ParseError};
+use super::val::val_length_parser;
+
+/// Expression of the css math functions `calc()`, `min()`, `max()` and `clamp()` for lengths
+///
+/// Percentages and viewport units can't be resolved at parse time, the expression is
+/// resolved into pixels with [`CalcExpr::resolve`] once the sizes they refer to are known.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CalcExpr {
+    /// A length, like `20px` or `100%`
+    Length(Val),
+    /// A number without unit, like the `2` in `calc(2 * 10px)`
+    Number(f32),
+    Add(Box<CalcExpr>, Box<CalcExpr>),
+    Sub(Box<CalcExpr>, Box<CalcExpr>),
+    Mul(Box<CalcExpr>, Box<CalcExpr>),
+    Div(Box<CalcExpr>, Box<CalcExpr>),
+    Min(Vec<CalcExpr>),
+    Max(Vec<CalcExpr>),
+    /// `clamp(min, value, max)`
+    Clamp(Box<CalcExpr>, Box<CalcExpr>, Box<CalcExpr>),
+}
+
+/// Sizes that the relative lengths of a [`CalcExpr`] refer to
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CalcContext {
+    /// The size percentages refer to in logical pixels, usually the width or height of the parent
+    pub percent_base: f32,
+    /// The size of the viewport in logical pixels, used by `vw`, `vh`, `vmin` and `vmax`
+    pub viewport: Vec2,
+}
+
+/// Whether an expression results in a length or a number
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CalcKind {
+    Length,
+    Number,
+}
+
+impl CalcExpr {
+    /// Resolves the expression into logical pixels
+    ///
+    /// Returns `None` if the result is not a finite number, like for a division
+    /// by zero in `calc(100% / (2 - 2))`.
+    pub fn resolve(&self, context: &CalcContext) -> Option<f32> {
+        Some(self.evaluate(context)).filter(|value| value.is_finite())
+    }
+
+    /// Resolves the expression into a [`Val::Px`], see [`CalcExpr::resolve`]
+    pub fn resolve_val(&self, context: &CalcContext) -> Option<Val> {
+        self.resolve(context).map(Val::Px)
+    }
+
+    /// Evaluates the expression into logical pixels, which may be infinite or NaN
+    pub(crate) fn evaluate(&self, context: &CalcContext) -> f32 {
+        match self {
+            CalcExpr::Length(val) => match *val {
+                Val::Px(value) => value,
+                Val::Percent(value) => value / 100.0 * context.percent_base,
+                Val::Vw(value) => value / 100.0 * context.viewport.x,
+                Val::Vh(value) => value / 100.0 * context.viewport.y,
+                Val::VMin(value) => value / 100.0 * context.viewport.min_element(),
+                Val::VMax(value) => value / 100.0 * context.viewport.max_element(),
+                Val::Auto => 0.0,
+            },
+            CalcExpr::Number(value) => *value,
+            CalcExpr::Add(a, b) => a.evaluate(context) + b.evaluate(context),
+            CalcExpr::Sub(a, b) => a.evaluate(context) - b.evaluate(context),
+            CalcExpr::Mul(a, b) => a.evaluate(context) * b.evaluate(context),
+            CalcExpr::Div(a, b) => a.evaluate(context) / b.evaluate(context),
+            CalcExpr::Min(exprs) => exprs
+                .iter()
+                .map(|expr| expr.evaluate(context))
+                .fold(f32::INFINITY, f32::min),
+            CalcExpr::Max(exprs) => exprs
+                .iter()
+                .map(|expr| expr.evaluate(context))
+                .fold(f32::NEG_INFINITY, f32::max),
+            CalcExpr::Clamp(min, value, max) => value
+                .evaluate(context)
+                .min(max.evaluate(context))
+                .max(min.evaluate(context)),
+        }
+    }
+
+    /// Returns the kind of the expression, or `None` if it mixes lengths and numbers
+    /// in a way css doesn't allow, like `10px + 2` or `10px * 10px`
+    fn kind(&self) -> Option<CalcKind> {
+        // the kind of each operand is computed once, sums are nested like
+        // `Add(Add(a, b), c)` and computing it twice would be exponential
+        let same_kind = |exprs: &[&CalcExpr]| {
+            let kinds = exprs
+                .iter()
+                .map(|expr| expr.kind())
+                .collect::<Option<Vec<_>>>()?;
+            let kind = *kinds.first()?;
+            kinds.iter().all(|other| *other == kind).then_some(kind)
+        };
+        match self {
+            CalcExpr::Length(_) => Some(CalcKind::Length),
+            CalcExpr::Number(_) => Some(CalcKind::Number),
+            CalcExpr::Add(a, b) | CalcExpr::Sub(a, b) => same_kind(&[a, b]),
+            CalcExpr::Mul(a, b) => match (a.kind()?, b.kind()?) {
+                (CalcKind::Number, kind) | (kind, CalcKind::Number) => Some(kind),
+                _ => None,
+            },
+            CalcExpr::Div(a, b) => match b.kind()? {
+                CalcKind::Number => a.kind(),
+                CalcKind::Length => None,
+            },
+            CalcExpr::Min(exprs) | CalcExpr::Max(exprs) => {
+                same_kind(&exprs.iter().collect::<Vec<_>>())
+            }
+            CalcExpr::Clamp(min, value, max) => same_kind(&[min, value, max]),
+        }
+    }
+}
+
+/// Parses a length or a number, the values of a length expression
+fn calc_length_parser(input: &str) -> IResult<&str, CalcExpr> {
+    alt((
+        map(val_length_parser, CalcExpr::Length),
+        map(number_parser, CalcExpr::Number),
+    ))(input)
+}
+
+/// Parses a single value of a math expression, like `20px`, `2` or `(100% - 20px)`
+///
+/// Values other than math functions and parentheses are parsed by the leaf parser.
+fn calc_value_parser<'a, L>(input: &'a str, leaf: L) -> IResult<&'a str, CalcExpr>
+where
+    L: Fn(&'a str) -> IResult<&'a str, CalcExpr> + Copy,
+{
+    alt((
+        delimited(
+            pair(token("("), multispace),
+            |input| calc_sum_parser(input, leaf),
+            pair(multispace, token(")")),
+        ),
+        |input| math_function_parser(input, leaf),
+        leaf,
+    ))(input)
+}
+
+/// Parses a multiplication or division, like `100% / 3`
+fn calc_product_parser<'a, L>(input: &'a str, leaf: L) -> IResult<&'a str, CalcExpr>
+where
+    L: Fn(&'a str) -> IResult<&'a str, CalcExpr> + Copy,
+{
+    let (input, first) = calc_value_parser(input, leaf)?;
+    fold_many0(
+        pair(
+            delimited(multispace, alt((token("*"), token("/"))), multispace),
+            |input| calc_value_parser(input, leaf),
+        ),
+        move || first.clone(),
+        |product, (operator, value)| match operator {
+            "*" => CalcExpr::Mul(Box::new(product), Box::new(value)),
+            _ => CalcExpr::Div(Box::new(product), Box::new(value)),
+        },
+    )(input)
+}
+
+/// Parses an addition or subtraction, like `100% - 20px`
+///
+/// As in css, `+` and `-` must be surrounded by whitespace.
+fn calc_sum_parser<'a, L>(input: &'a str, leaf: L) -> IResult<&'a str, CalcExpr>
+where
+    L: Fn(&'a str) -> IResult<&'a str, CalcExpr> + Copy,
+{
+    let (input, first) = calc_product_parser(input, leaf)?;
+    fold_many0(
+        pair(
+            delimited(multispace1, alt((token("+"), token("-"))), multispace1),
+            |input| calc_product_parser(input, leaf),
+        ),
+        move || first.clone(),
+        |sum, (operator, value)| match operator {
+            "+" => CalcExpr::Add(Box::new(sum), Box::new(value)),
+            _ => CalcExpr::Sub(Box::new(sum), Box::new(value)),
+        },
+    )(input)
+}
+
+/// Parses one of the math functions `calc()`, `min()`, `max()` or `clamp()`
+///
+/// The values of the expression are parsed by the leaf parser, like lengths and numbers
+/// for [`calc_parser`] or the channels of relative colors, the kind of the expression is
+/// not checked.
+pub(crate) fn math_function_parser<'a, L>(input: &'a str, leaf: L) -> IResult<&'a str, CalcExpr>
+where
+    L: Fn(&'a str) -> IResult<&'a str, CalcExpr> + Copy,
+{
+    let sum = move |input| calc_sum_parser(input, leaf);
+    let arguments = || separated_list1(comma_parser, sum);
+    alt((
+        function_parser("calc", sum),
+        map(function_parser("min", arguments()), CalcExpr::Min),
+        map(function_parser("max", arguments()), CalcExpr::Max),
+        map(
+            function_parser("clamp", tuple((sum, comma_parser, sum, comma_parser, sum))),
+            |(min, _, value, _, max)| {
+                CalcExpr::Clamp(Box::new(min), Box::new(value), Box::new(max))
+            },
+        ),
+    ))
+    .parse(input)
+}
+
+/// Parser for strings that represent a [`CalcExpr`].
+///
+/// The syntax is inspired by CSS:
+///
+/// * `calc(100% - 20px)` -> sums and differences, `+` and `-` surrounded by whitespace
+/// * `calc(100% / 3)`, `calc(2 * (10px + 1vw))` -> products and quotients with numbers
+/// * `min(100%, 500px)`, `max(50vw, 200px)` -> smallest or largest of the arguments
+/// * `clamp(200px, 50%, 800px)` -> value limited by a minimum and a maximum
+/// * `20px`, `50%` -> plain lengths as supported by [`super::val_parser`], except `auto`
+///
+/// Math functions can be nested and the expression must result in a length.
+pub fn calc_parser(input: &str) -> IResult<&str, CalcExpr> {
+    let (input, _) = multispace(input)?;
+    let (rest, expr) = alt((
+        |input| math_function_parser(input, calc_length_parser),
+        map(val_length_parser, CalcExpr::Length),
+    ))(input)?;
+    if expr.kind() != Some(CalcKind::Length) {
+        return Err(nom::Err::Error(InputError::expected(
+            input,
+            "<length expression>",
+        )));
+    }
+    let (rest, _) = multispace(rest)?;
+    Ok((rest, expr))
+}
+
+/// Wrapper for [`calc_parser`] that returns an optional [`CalcExpr`]
+pub fn calc_string_parser(input: &str) -> Option<CalcExpr> {
+    calc_parser(input).map(|(_, value)| value).ok()
+}
+
+/// Wrapper for [`calc_parser`] that returns a [`ParseError`] on failure
+///
+/// Unlike [`calc_string_parser`] the whole input must be consumed.
+pub fn calc_result_parser(input: &str) -> Result<CalcExpr, ParseError> {
+    parse_all(input, calc_parser)
+}
+
+/// Wrapper for [`calc_parser`] that implements a serde deserializer
+#[cfg(feature = "serde")]
+pub fn calc_serde_parser<'de, D>(deserializer: D) -> Result<CalcExpr, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::Error;
+    let s: &str = serde::Deserialize::deserialize(deserializer)?;
+    calc_result_parser(s)
+        .map_err(|error| D::Error::custom(format!("invalid calc string: {}", error)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const CONTEXT: CalcContext = CalcContext {
+        percent_base: 400.0,
+        viewport: Vec2::new(1000.0, 500.0),
+    };
+
+    #[test_case("calc(100% - 20px)", 380.0 ; "difference")]
+    #[test_case("calc(100% / 4 + 10px)", 110.0 ; "precedence")]
+    #[test_case("calc(2 * (10px + 1vw))", 40.0 ; "parentheses")]
+    #[test_case("min(100%, 300px)", 300.0 ; "min")]
+    #[test_case("max(10vmin, 20px, 1vmax)", 50.0 ; "max")]
+    #[test_case("clamp(200px, 10%, 800px)", 200.0 ; "clamp below")]
+    #[test_case("clamp(200px, 100vw, 800px)", 800.0 ; "clamp above")]
+    #[test_case("calc(100% - min(50%, 100px) * 2)", 200.0 ; "nested")]
+    #[test_case(" 25% ", 100.0 ; "plain length")]
+    fn test_calc_parser(string: &str, expected: f32) {
+        let expr = calc_result_parser(string).unwrap();
+        assert_eq!(expr.resolve(&CONTEXT), Some(expected));
+    }
+
+    #[test_case("calc(100% / (2 - 2))" ; "division by zero")]
+    #[test_case("calc(10px / 0 * 0)" ; "not a number")]
+    #[test_case("calc(-1px / 0)" ; "negative infinity")]
+    fn test_calc_resolve_non_finite(string: &str) {
+        let expr = calc_result_parser(string).unwrap();
+        assert_eq!(expr.resolve(&CONTEXT), None);
+        assert_eq!(expr.resolve_val(&CONTEXT), None);
+    }
+
+    #[test_case("calc( 50vw-sidebar )" ; "missing whitespace")]
+    #[test_case("calc(100%)px" ; "trailing unit")]
+    #[test_case("calc()" ; "empty")]
+    fn test_calc_parser_errors(string: &str) {
+        assert!(calc_result_parser(string).is_err());
+    }
+
+    #[test]
+    fn test_calc_parser_ast() {
+        assert_eq!(
+            calc_parser("calc(100% - 20px)"),
+            Ok((
+                "",
+                CalcExpr::Sub(
+                    Box::new(CalcExpr::Length(Val::Percent(100.0))),
+                    Box::new(CalcExpr::Length(Val::Px(20.0)))
+                )
+            ))
+        );
+        assert_eq!(
+            calc_string_parser("calc(20px)").and_then(|expr| expr.resolve_val(&CONTEXT)),
+            Some(Val::Px(20.0))
+        );
+    }
+
+    #[test_case("calc(10px + 2)" ; "length plus number")]
+    #[test_case("calc(10px * 10px)" ; "length times length")]
+    #[test_case("calc(10px / 1px)" ; "division by length")]
+    #[test_case("calc(2 * 3)" ; "number")]
+    #[test_case("min(10px, 2)" ; "mixed min")]
+    fn test_calc_parser_type_errors(string: &str) {
+        let error = calc_result_parser(string).unwrap_err();
+        assert_eq!(error.offset, 0);
+        assert_eq!(error.expected, vec!["<length expression>"]);
+    }
+
+    #[test]
+    fn test_calc_parser_long_sum() {
+        let string = format!("calc({})", vec!["1px"; 100].join(" + "));
+        let expr = calc_result_parser(&string).unwrap();
+        assert_eq!(expr.resolve(&CONTEXT), Some(100.0));
+    }
+
+    #[test]
+    fn test_calc_result_parser() {
+        let error = calc_result_parser("calc(100% -20px)").unwrap_err();
+        assert_eq!(error.offset, 10);
+        assert_eq!(error.expected, vec![")"]);
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests_serde {
+    use super::CalcExpr;
+    use bevy::ui::Val;
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    pub struct Foo {
+        #[serde(deserialize_with = "super::calc_serde_parser")]
+        pub width: CalcExpr,
+    }
+
+    #[test]
+    fn test_calc_serde_parser() {
+        let foo: Foo = serde_json::from_str(r#"{"width": "max(10px, 5%)"}"#).unwrap();
+        assert_eq!(
+            foo.width,
+            CalcExpr::Max(vec![
+                CalcExpr::Length(Val::Px(10.0)),
+                CalcExpr::Length(Val::Percent(5.0))
+            ])
+        );
+    }
+}
@@ -2,16 +2,24 @@ use bevy::{render::color::Color, utils::HashMap};
 use lazy_static::lazy_static;
 use nom::{
     branch::alt,
-    bytes::complete::{tag, take_while_m_n},
-    character::complete::multispace0 as multispace,
-    character::streaming::char,
-    combinator::{map, map_res},
-    error::ParseError,
-    number::complete::float,
-    sequence::{delimited, preceded, tuple, Tuple},
-    IResult, Parser,
+    bytes::complete::take_while_m_n,
+    character::complete::{multispace0 as multispace, multispace1},
+    combinator::{map, map_res, opt},
+    error::context,
+    sequence::{delimited, pair, preceded, terminated, tuple, Tuple},
+    Parser,
 };
 
+use super::calc::{math_function_parser, CalcContext, CalcExpr};
+use super::color_mix::color_mix_parser;
+use super::color_registry::{ColorNameRegistry, CSS_COLOR_NAMES};
+use super::color_space::{
+    delta_e_ok, from_linear_srgb, gamut_map_linear_srgb, hwb_to_srgb, linear_srgb_to_oklab,
+    to_linear_srgb, Color3, ColorSpace,
+};
+use super::common::{comma_parser, identifier_parser, number_parser, token};
+use super::error::{parse_all, IResult, InputError, ParseError};
+
 lazy_static! {
     /// Table with Named Colors in CSS
     ///
@@ -171,78 +179,539 @@ lazy_static! {
     };
 }
 
-/// Parses three floats, "1.0, 1.0, 1.0" into tuple of floats
-fn three_float_parser(i: &str) -> IResult<&str, (f32, f32, f32)> {
-    tuple((
-        preceded(multispace, float),
-        preceded(tuple((multispace, char(','), multispace)), float),
-        preceded(tuple((multispace, char(','), multispace)), float),
+/// Interpretation of a single channel of a color function
+#[derive(Clone, Copy)]
+enum Channel {
+    /// A hue angle in degrees, numbers without unit are interpreted as degrees
+    Hue,
+    /// Numbers and percentages multiplied with the given factors
+    Scaled { number: f32, percentage: f32 },
+}
+
+impl Channel {
+    /// Converts a channel value as written in CSS, like `255` for rgb, to the
+    /// value used by the color space
+    fn to_component(self, value: f32) -> f32 {
+        match self {
+            Channel::Hue => value.rem_euclid(360.0),
+            Channel::Scaled { number, .. } => value * number,
+        }
+    }
+
+    /// Converts a component of the color space to the channel value as written in CSS
+    fn to_value(self, component: f32) -> f32 {
+        match self {
+            Channel::Hue => component,
+            Channel::Scaled { number, .. } => component / number,
+        }
+    }
+}
+
+/// Channel in the range of 0.0-1.0, or 0%-100%
+const UNIT_CHANNEL: Channel = Channel::Scaled {
+    number: 1.0,
+    percentage: 0.01,
+};
+
+/// Channel in the range of 0-255, or 0%-100%
+const BYTE_CHANNEL: Channel = Channel::Scaled {
+    number: 1.0 / 255.0,
+    percentage: 0.01,
+};
+
+/// Channel in the range of 0-100, or 0%-100%
+const PERCENT_CHANNEL: Channel = Channel::Scaled {
+    number: 0.01,
+    percentage: 0.01,
+};
+
+/// Lightness of lab and lch, in the range of 0-100, or 0%-100%
+const LAB_LIGHTNESS_CHANNEL: Channel = Channel::Scaled {
+    number: 1.0,
+    percentage: 1.0,
+};
+
+/// The a and b axes of lab, 100% corresponds to 125
+const LAB_AXIS_CHANNEL: Channel = Channel::Scaled {
+    number: 1.0,
+    percentage: 1.25,
+};
+
+/// Chroma of lch, 100% corresponds to 150
+const LCH_CHROMA_CHANNEL: Channel = Channel::Scaled {
+    number: 1.0,
+    percentage: 1.5,
+};
+
+/// The a and b axes of oklab and chroma of oklch, 100% corresponds to 0.4
+const OKLAB_AXIS_CHANNEL: Channel = Channel::Scaled {
+    number: 1.0,
+    percentage: 0.004,
+};
+
+/// The space separated syntax of a color function
+#[derive(Clone, Copy)]
+struct ColorFunction {
+    space: ColorSpace,
+    channels: [Channel; 3],
+    /// Names of the channels in the relative color syntax
+    keywords: [&'static str; 3],
+}
+
+const RGB_FUNCTION: ColorFunction = ColorFunction {
+    space: ColorSpace::Srgb,
+    channels: [BYTE_CHANNEL; 3],
+    keywords: ["r", "g", "b"],
+};
+
+const HSL_FUNCTION: ColorFunction = ColorFunction {
+    space: ColorSpace::Hsl,
+    channels: [Channel::Hue, PERCENT_CHANNEL, PERCENT_CHANNEL],
+    keywords: ["h", "s", "l"],
+};
+
+const HWB_FUNCTION: ColorFunction = ColorFunction {
+    space: ColorSpace::Hwb,
+    channels: [Channel::Hue, PERCENT_CHANNEL, PERCENT_CHANNEL],
+    keywords: ["h", "w", "b"],
+};
+
+const LAB_FUNCTION: ColorFunction = ColorFunction {
+    space: ColorSpace::Lab,
+    channels: [LAB_LIGHTNESS_CHANNEL, LAB_AXIS_CHANNEL, LAB_AXIS_CHANNEL],
+    keywords: ["l", "a", "b"],
+};
+
+const LCH_FUNCTION: ColorFunction = ColorFunction {
+    space: ColorSpace::Lch,
+    channels: [LAB_LIGHTNESS_CHANNEL, LCH_CHROMA_CHANNEL, Channel::Hue],
+    keywords: ["l", "c", "h"],
+};
+
+const OKLAB_FUNCTION: ColorFunction = ColorFunction {
+    space: ColorSpace::Oklab,
+    channels: [UNIT_CHANNEL, OKLAB_AXIS_CHANNEL, OKLAB_AXIS_CHANNEL],
+    keywords: ["l", "a", "b"],
+};
+
+const OKLCH_FUNCTION: ColorFunction = ColorFunction {
+    space: ColorSpace::Oklch,
+    channels: [UNIT_CHANNEL, OKLAB_AXIS_CHANNEL, Channel::Hue],
+    keywords: ["l", "c", "h"],
+};
+
+/// The color() function with one of the predefined color spaces
+fn predefined_function(space: ColorSpace) -> ColorFunction {
+    let keywords = match space {
+        ColorSpace::XyzD50 | ColorSpace::XyzD65 => ["x", "y", "z"],
+        _ => ["r", "g", "b"],
+    };
+    ColorFunction {
+        space,
+        channels: [UNIT_CHANNEL; 3],
+        keywords,
+    }
+}
+
+/// Channel keywords of the relative color syntax with the values of the origin color,
+/// like `[("r", 255.0), ("g", 0.0), ("b", 0.0), ("alpha", 1.0)]`
+type Origin = [(&'static str, f32); 4];
+
+/// Returns the channel keywords of a color function with the values of the origin color
+fn origin_channels(function: ColorFunction, color: Color) -> Origin {
+    let [c0, c1, c2, alpha] = color_to_space(function.space, color);
+    let [k0, k1, k2] = function.keywords;
+    let [ch0, ch1, ch2] = function.channels;
+    [
+        (k0, ch0.to_value(c0)),
+        (k1, ch1.to_value(c1)),
+        (k2, ch2.to_value(c2)),
+        ("alpha", alpha),
+    ]
+}
+
+/// How a channel value is interpreted, with the keywords of the relative color syntax
+#[derive(Clone, Copy)]
+struct ChannelContext {
+    channel: Channel,
+    origin: Option<Origin>,
+}
+
+/// Parses a hue angle into degrees, like "120", "120deg", "0.5turn", "2rad" or "100grad"
+fn hue_parser(i: &str) -> IResult<&str, f32> {
+    alt((
+        terminated(number_parser, token("deg")),
+        map(terminated(number_parser, token("rad")), f32::to_degrees),
+        map(terminated(number_parser, token("grad")), |val| val * 0.9),
+        map(terminated(number_parser, token("turn")), |val| val * 360.0),
+        number_parser,
     ))
     .parse(i)
 }
 
-/// Parses four floats, "1.0, 1.0, 1.0, 1.0" into tuple of floats
-fn four_float_parser(i: &str) -> IResult<&str, (f32, f32, f32, f32)> {
-    tuple((
-        preceded(multispace, float),
-        preceded(tuple((multispace, char(','), multispace)), float),
-        preceded(tuple((multispace, char(','), multispace)), float),
-        preceded(tuple((multispace, char(','), multispace)), float),
-    ))
+/// Parses a number, percentage or angle as written in CSS, like "255", "50%" or "90deg"
+fn dimension_parser(i: &str, channel: Channel) -> IResult<&str, f32> {
+    match channel {
+        Channel::Hue => hue_parser(i),
+        Channel::Scaled { number, percentage } => alt((
+            map(terminated(number_parser, token("%")), |val| {
+                val * percentage / number
+            }),
+            number_parser,
+        ))
+        .parse(i),
+    }
+}
+
+/// Parses a channel keyword of the relative color syntax, like "r" or "alpha"
+fn channel_keyword_parser(i: &str, origin: Option<Origin>) -> IResult<&str, f32> {
+    let Some(origin) = origin else {
+        return Err(nom::Err::Error(InputError {
+            input: i,
+            expected: Vec::new(),
+        }));
+    };
+    let error = || {
+        nom::Err::Error(InputError {
+            input: i,
+            expected: origin.iter().map(|(keyword, _)| *keyword).collect(),
+        })
+    };
+    let (rest, name) = identifier_parser(i).map_err(|_| error())?;
+    origin
+        .iter()
+        .find(|(keyword, _)| *keyword == name)
+        .map(|(_, value)| (rest, *value))
+        .ok_or_else(error)
+}
+
+/// Parses a value of a math expression of a channel, like "l" or "10%"
+fn calc_channel_parser(i: &str, context: ChannelContext) -> IResult<&str, CalcExpr> {
+    map(
+        alt((
+            |i| channel_keyword_parser(i, context.origin),
+            |i| dimension_parser(i, context.channel),
+        )),
+        CalcExpr::Number,
+    )
     .parse(i)
 }
 
-/// Generic parser for color functions, like rgb(1.0, 1.0, 1.0), etc.
+/// Parses a math expression of a channel, like "calc(l + 10%)" or "min(r, 128)"
+fn calc_parser(i: &str, context: ChannelContext) -> IResult<&str, f32> {
+    map(
+        |i| math_function_parser(i, move |i| calc_channel_parser(i, context)),
+        // the channels are numbers, so the context of lengths is unused
+        |expr| expr.evaluate(&CalcContext::default()),
+    )
+    .parse(i)
+}
+
+/// Parses a single channel of a color function, `none` is interpreted as zero
+///
+/// Channels can be math functions like `calc()` or `min()` and, in the relative color syntax,
+/// keywords referring to the channels of the origin color.
+fn channel_parser(
+    channel: Channel,
+    origin: Option<Origin>,
+) -> impl FnMut(&str) -> IResult<&str, f32> {
+    move |i| {
+        let context = ChannelContext { channel, origin };
+        alt((
+            map(token("none"), |_| 0.0),
+            map(
+                alt((
+                    move |i| calc_parser(i, context),
+                    move |i| channel_keyword_parser(i, origin),
+                    move |i| dimension_parser(i, channel),
+                )),
+                move |val| channel.to_component(val),
+            ),
+        ))
+        .parse(i)
+    }
+}
+
+/// Parses the comma separated arguments of a color function, like "1.0, 0.0, 0.0, 1.0"
+///
+/// The alpha is optional and defaults to 1.0.
+fn legacy_arguments_parser(channels: [Channel; 3]) -> impl FnMut(&str) -> IResult<&str, [f32; 4]> {
+    move |i| {
+        map(
+            tuple((
+                channel_parser(channels[0], None),
+                preceded(comma_parser, channel_parser(channels[1], None)),
+                preceded(comma_parser, channel_parser(channels[2], None)),
+                opt(preceded(comma_parser, channel_parser(UNIT_CHANNEL, None))),
+            )),
+            |(a, b, c, alpha)| [a, b, c, alpha.unwrap_or(1.0)],
+        )
+        .parse(i)
+    }
+}
+
+/// Parses the space separated arguments of a color function, like "255 0 0 / 50%"
+///
+/// The alpha is optional and defaults to 1.0, or the alpha of the origin color.
+fn modern_arguments_parser(
+    channels: [Channel; 3],
+    origin: Option<Origin>,
+) -> impl FnMut(&str) -> IResult<&str, [f32; 4]> {
+    move |i| {
+        let default_alpha = origin.map_or(1.0, |[.., (_, alpha)]| alpha);
+        map(
+            tuple((
+                channel_parser(channels[0], origin),
+                preceded(multispace1, channel_parser(channels[1], origin)),
+                preceded(multispace1, channel_parser(channels[2], origin)),
+                opt(preceded(
+                    tuple((multispace, token("/"), multispace)),
+                    channel_parser(UNIT_CHANNEL, origin),
+                )),
+            )),
+            move |(a, b, c, alpha)| [a, b, c, alpha.unwrap_or(default_alpha)],
+        )
+        .parse(i)
+    }
+}
+
+/// Parses the origin color of the relative color syntax, like "from #336699 "
+fn origin_color_parser<'a>(i: &'a str, names: &ColorNameRegistry) -> IResult<&'a str, Color> {
+    delimited(
+        pair(token("from"), multispace1),
+        |i| color_registry_parser(i, names),
+        multispace,
+    )
+    .parse(i)
+}
+
+/// Parses the space separated arguments of a color function, either with absolute
+/// channels like "255 0 0", or relative to an origin color like "from red r g calc(b + 20)"
+fn function_arguments_parser<'a>(
+    i: &'a str,
+    function: ColorFunction,
+    names: &ColorNameRegistry,
+) -> IResult<&'a str, [f32; 4]> {
+    let relative = |i| {
+        let (i, origin) = origin_color_parser(i, names)?;
+        modern_arguments_parser(function.channels, Some(origin_channels(function, origin)))(i)
+    };
+    alt((relative, modern_arguments_parser(function.channels, None))).parse(i)
+}
+
+/// Generic parser for color functions, like rgb(255, 255, 255), etc.
 ///
 /// Args:
 /// name: the function name, for example "rgb" or "hsl"
-fn color_fn_parser<'a, O, E>(
+pub(crate) fn color_fn_parser<'a, O>(
     name: &'static str,
-    inner_parser: impl nom::Parser<&'a str, O, E>,
-) -> impl FnMut(&'a str) -> Result<(&'a str, O), nom::Err<E>>
-where
-    E: ParseError<&'a str>,
-{
+    inner_parser: impl nom::Parser<&'a str, O, InputError<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, O> {
     delimited(
         multispace,
         delimited(
-            tuple((tag(name), tag("("), multispace)),
+            tuple((token(name), token("("), multispace)),
             inner_parser,
-            tuple((multispace, tag(")"))),
+            tuple((multispace, token(")"))),
         ),
         multispace,
     )
 }
 
-/// Parses rgb color function strings, like rgb(1.0, 1.0, 1.0)
-fn color_rgb_parser(i: &str) -> IResult<&str, Color> {
-    map(color_fn_parser("rgb", three_float_parser), |(r, g, b)| {
-        Color::rgb(r, g, b)
-    })
+/// Parses the arguments of the rgb and rgba color functions
+///
+/// Both the legacy syntax, like "255, 0, 0, 0.5", and the modern syntax, like
+/// "255 0 0 / 50%", use channels in the range of 0-255 or percentages.
+fn rgb_arguments_parser<'a>(i: &'a str, names: &ColorNameRegistry) -> IResult<&'a str, [f32; 4]> {
+    alt((legacy_arguments_parser([BYTE_CHANNEL; 3]), |i| {
+        function_arguments_parser(i, RGB_FUNCTION, names)
+    }))
     .parse(i)
 }
 
-/// Parses rgba color function strings, like rgba(1.0, 1.0, 1.0, 1.0)
-fn color_rgba_parser(i: &str) -> IResult<&str, Color> {
-    map(color_fn_parser("rgba", four_float_parser), |(r, g, b, a)| {
-        Color::rgba(r, g, b, a)
-    })
+/// Parses the arguments of the hsl and hsla color functions
+///
+/// The legacy syntax uses saturation and lightness in the range of 0.0-1.0, like
+/// "120, 1.0, 0.5", the modern syntax in the range of 0-100, like "120deg 100 50%".
+fn hsl_arguments_parser<'a>(i: &'a str, names: &ColorNameRegistry) -> IResult<&'a str, [f32; 4]> {
+    alt((
+        legacy_arguments_parser([Channel::Hue, UNIT_CHANNEL, UNIT_CHANNEL]),
+        |i| function_arguments_parser(i, HSL_FUNCTION, names),
+    ))
     .parse(i)
 }
 
-/// Parses hsl color function strings, like hsl(1.0, 1.0, 1.0)
-fn color_hsl_parser(i: &str) -> IResult<&str, Color> {
-    map(color_fn_parser("hsl", three_float_parser), |(r, g, b)| {
-        Color::hsl(r, g, b)
-    })
+/// Parses rgb color function strings, like rgb(255, 0, 0) or rgb(255 0 0 / 50%)
+///
+/// `rgba` is an alias of `rgb`, both accept an optional alpha. Channels outside
+/// of the sRGB gamut are clamped, like in css.
+fn color_rgb_parser<'a>(i: &'a str, names: &ColorNameRegistry) -> IResult<&'a str, Color> {
+    let arguments = |i| rgb_arguments_parser(i, names);
+    map(
+        alt((
+            color_fn_parser("rgb", arguments),
+            color_fn_parser("rgba", arguments),
+        )),
+        |channels| {
+            let [r, g, b, a] = channels.map(|channel| channel.clamp(0.0, 1.0));
+            Color::rgba(r, g, b, a)
+        },
+    )
+    .parse(i)
+}
+
+/// Parses hsl color function strings, like hsl(0, 1.0, 0.5) or hsl(120deg 100% 50% / 0.3)
+///
+/// `hsla` is an alias of `hsl`, both accept an optional alpha.
+fn color_hsl_parser<'a>(i: &'a str, names: &ColorNameRegistry) -> IResult<&'a str, Color> {
+    let arguments = |i| hsl_arguments_parser(i, names);
+    map(
+        alt((
+            color_fn_parser("hsl", arguments),
+            color_fn_parser("hsla", arguments),
+        )),
+        |[h, s, l, a]| Color::hsla(h, s, l, a),
+    )
     .parse(i)
 }
 
-/// Parses hsla color function strings, like hsla(1.0, 1.0, 1.0, 1.0)
-fn color_hsla_parser(i: &str) -> IResult<&str, Color> {
-    map(color_fn_parser("hsla", four_float_parser), |(r, g, b, a)| {
-        Color::hsla(r, g, b, a)
-    })
+/// Parser for color functions with the space separated syntax only
+fn color_function_parser<'a>(
+    i: &'a str,
+    name: &'static str,
+    function: ColorFunction,
+    names: &ColorNameRegistry,
+) -> IResult<&'a str, Color> {
+    map(
+        color_fn_parser(name, |i| function_arguments_parser(i, function, names)),
+        |[c0, c1, c2, alpha]| color_from_space(function.space, [c0, c1, c2], alpha),
+    )
+    .parse(i)
+}
+
+/// Parses hwb color function strings, like hwb(120deg 20% 30% / 0.5)
+fn color_hwb_parser<'a>(i: &'a str, names: &ColorNameRegistry) -> IResult<&'a str, Color> {
+    color_function_parser(i, "hwb", HWB_FUNCTION, names)
+}
+
+/// Parses lab color function strings, like lab(54.3 80.8 69.9 / 0.5)
+fn color_lab_parser<'a>(i: &'a str, names: &ColorNameRegistry) -> IResult<&'a str, Color> {
+    color_function_parser(i, "lab", LAB_FUNCTION, names)
+}
+
+/// Parses lch color function strings, like lch(54.3% 106.8 40.9deg / 0.5)
+fn color_lch_parser<'a>(i: &'a str, names: &ColorNameRegistry) -> IResult<&'a str, Color> {
+    color_function_parser(i, "lch", LCH_FUNCTION, names)
+}
+
+/// Parses oklab color function strings, like oklab(62.8% 0.22 0.13 / 0.5)
+fn color_oklab_parser<'a>(i: &'a str, names: &ColorNameRegistry) -> IResult<&'a str, Color> {
+    color_function_parser(i, "oklab", OKLAB_FUNCTION, names)
+}
+
+/// Parses oklch color function strings, like oklch(62.8% 0.26 29.2deg / 0.5)
+fn color_oklch_parser<'a>(i: &'a str, names: &ColorNameRegistry) -> IResult<&'a str, Color> {
+    color_function_parser(i, "oklch", OKLCH_FUNCTION, names)
+}
+
+/// Creates a color from linear sRGB, out of gamut colors are mapped into the sRGB gamut
+fn gamut_mapped_color(rgb: Color3, alpha: f32) -> Color {
+    let [r, g, b] = gamut_map_linear_srgb(rgb);
+    Color::rgba_linear(r, g, b, alpha).as_rgba()
+}
+
+/// Creates a [`Color`] from the components of a color in the given color space
+///
+/// Colors in `srgb` are returned as [`Color::Rgba`] and in `hsl` as [`Color::Hsla`].
+/// Colors in the linear `srgb-linear` and `xyz` spaces are returned as [`Color::RgbaLinear`]
+/// with exact values, as are `display-p3` and `rec2020` after mapping them into the sRGB
+/// gamut. All other colors are mapped into the sRGB gamut and returned as [`Color::Rgba`].
+pub(crate) fn color_from_space(space: ColorSpace, color: Color3, alpha: f32) -> Color {
+    let linear = |[r, g, b]: Color3| Color::rgba_linear(r, g, b, alpha);
+    let [c0, c1, c2] = color;
+    match space {
+        ColorSpace::Srgb => Color::rgba(c0, c1, c2, alpha),
+        ColorSpace::Hsl => Color::hsla(c0.rem_euclid(360.0), c1, c2, alpha),
+        ColorSpace::Hwb => {
+            let [r, g, b] = hwb_to_srgb(color);
+            Color::rgba(r, g, b, alpha)
+        }
+        ColorSpace::SrgbLinear | ColorSpace::XyzD50 | ColorSpace::XyzD65 => {
+            linear(to_linear_srgb(space, color))
+        }
+        ColorSpace::DisplayP3 | ColorSpace::Rec2020 => {
+            linear(gamut_map_linear_srgb(to_linear_srgb(space, color)))
+        }
+        ColorSpace::Lab | ColorSpace::Lch => {
+            gamut_mapped_color(to_linear_srgb(space, [c0.clamp(0.0, 100.0), c1, c2]), alpha)
+        }
+        ColorSpace::Oklab | ColorSpace::Oklch => {
+            gamut_mapped_color(to_linear_srgb(space, [c0.clamp(0.0, 1.0), c1, c2]), alpha)
+        }
+    }
+}
+
+/// Converts a [`Color`] to the components of a color in the given color space and its alpha
+pub(crate) fn color_to_space(space: ColorSpace, color: Color) -> [f32; 4] {
+    match color {
+        Color::Rgba {
+            red,
+            green,
+            blue,
+            alpha,
+        } if space == ColorSpace::Srgb => [red, green, blue, alpha],
+        Color::Hsla {
+            hue,
+            saturation,
+            lightness,
+            alpha,
+        } if space == ColorSpace::Hsl => [hue, saturation, lightness, alpha],
+        _ => {
+            let [r, g, b, alpha] = color.as_linear_rgba_f32();
+            let [c0, c1, c2] = from_linear_srgb(space, [r, g, b]);
+            [c0, c1, c2, alpha]
+        }
+    }
+}
+
+/// Parses the name of a predefined color space, like "srgb" or "display-p3"
+pub(crate) fn predefined_color_space_parser(i: &str) -> IResult<&str, ColorSpace> {
+    alt((
+        map(token("srgb-linear"), |_| ColorSpace::SrgbLinear),
+        map(token("srgb"), |_| ColorSpace::Srgb),
+        map(token("display-p3"), |_| ColorSpace::DisplayP3),
+        map(token("rec2020"), |_| ColorSpace::Rec2020),
+        map(token("xyz-d50"), |_| ColorSpace::XyzD50),
+        map(token("xyz-d65"), |_| ColorSpace::XyzD65),
+        map(token("xyz"), |_| ColorSpace::XyzD65),
+    ))
+    .parse(i)
+}
+
+/// Parses the arguments of the color() function, like "display-p3 1 0 0"
+/// or "from red srgb-linear r g b"
+fn predefined_arguments_parser<'a>(
+    i: &'a str,
+    names: &ColorNameRegistry,
+) -> IResult<&'a str, (ColorSpace, [f32; 4])> {
+    let (i, origin) = opt(|i| origin_color_parser(i, names))(i)?;
+    let (i, space) = terminated(predefined_color_space_parser, multispace1)(i)?;
+    let function = predefined_function(space);
+    let origin = origin.map(|color| origin_channels(function, color));
+    let (i, channels) = modern_arguments_parser(function.channels, origin)(i)?;
+    Ok((i, (space, channels)))
+}
+
+/// Parses color function strings with a predefined color space, like color(display-p3 1 0 0)
+///
+/// Colors in `srgb` are returned as [`Color::Rgba`], all other color spaces are converted
+/// to [`Color::RgbaLinear`]. Only the bounded `display-p3` and `rec2020` color spaces are
+/// mapped into the sRGB gamut, values in the other color spaces are kept as is.
+fn color_predefined_parser<'a>(i: &'a str, names: &ColorNameRegistry) -> IResult<&'a str, Color> {
+    map(
+        color_fn_parser("color", |i| predefined_arguments_parser(i, names)),
+        |(space, [c0, c1, c2, alpha])| color_from_space(space, [c0, c1, c2], alpha),
+    )
     .parse(i)
 }
 
@@ -263,19 +732,27 @@ fn is_hex_digit(c: char) -> bool {
 
 /// Takes a two letter hexadecimal from the input and return it as a byte
 fn hex_primary(input: &str) -> IResult<&str, u8> {
-    map_res(take_while_m_n(2, 2, is_hex_digit), from_hex).parse(input)
+    context(
+        "<hex digit>",
+        map_res(take_while_m_n(2, 2, is_hex_digit), from_hex),
+    )
+    .parse(input)
 }
 
 /// Takes a single letter hexadecimal from the input and return it as a byte
 fn hex_half(input: &str) -> IResult<&str, u8> {
-    map_res(take_while_m_n(1, 1, is_hex_digit), from_half_hex).parse(input)
+    context(
+        "<hex digit>",
+        map_res(take_while_m_n(1, 1, is_hex_digit), from_half_hex),
+    )
+    .parse(input)
 }
 
 /// Takes a 6 character hexadecimal color prefixed with `#` and parses it to a Color
 ///
 /// For example: `#FF0000`
 fn color_hex6_parser(input: &str) -> IResult<&str, Color> {
-    let (input, _) = tag("#")(input)?;
+    let (input, _) = token("#")(input)?;
     let (input, (r, g, b)) = (hex_primary, hex_primary, hex_primary).parse(input)?;
     Ok((input, Color::rgb_u8(r, g, b)))
 }
@@ -284,7 +761,7 @@ fn color_hex6_parser(input: &str) -> IResult<&str, Color> {
 ///
 /// For example: `#FF0000FF`
 fn color_hex8_parser(input: &str) -> IResult<&str, Color> {
-    let (input, _) = tag("#")(input)?;
+    let (input, _) = token("#")(input)?;
     let (input, (r, g, b, a)) = (hex_primary, hex_primary, hex_primary, hex_primary).parse(input)?;
     Ok((input, Color::rgba_u8(r, g, b, a)))
 }
@@ -293,20 +770,37 @@ fn color_hex8_parser(input: &str) -> IResult<&str, Color> {
 ///
 /// For example: `#F00`
 fn color_hex3_parser(input: &str) -> IResult<&str, Color> {
-    let (input, _) = tag("#")(input)?;
+    let (input, _) = token("#")(input)?;
     let (input, (r, g, b)) = (hex_half, hex_half, hex_half).parse(input)?;
     Ok((input, Color::rgb_u8(r, g, b)))
 }
 
-/// Takes a string found in the css color table and return its color
-fn color_css_names_parser(input: &str) -> IResult<&str, Color> {
-    if let Some(color) = CSS_COLOR_TABLE.get(input.trim()) {
-        Ok(("", *color))
-    } else {
-        Err(nom::Err::Error(nom::error::Error::new(
-            input,
-            nom::error::ErrorKind::Tag,
-        )))
+/// Takes a name found in the color name registry and return its color
+///
+/// `transparent` is a fully transparent black, unless the registry defines it.
+fn color_names_parser<'a>(input: &'a str, names: &ColorNameRegistry) -> IResult<&'a str, Color> {
+    let error = || nom::Err::Error(InputError::expected(input, "<color name>"));
+    let (rest, name) = identifier_parser(input).map_err(|_| error())?;
+    match names.get(name) {
+        Some(color) => Ok((rest, color)),
+        None if name == "transparent" => Ok((rest, Color::NONE)),
+        None => Err(error()),
+    }
+}
+
+/// Parses a reference to a registered color name, like `var(--accent)`,
+/// with an optional fallback color, like `var(--accent, #336699)`
+fn color_var_parser<'a>(input: &'a str, names: &ColorNameRegistry) -> IResult<&'a str, Color> {
+    let (name_input, _) = tuple((token("var"), token("("), multispace, token("--")))(input)?;
+    let (i, name) = identifier_parser(name_input)?;
+    let (i, fallback) = opt(preceded(comma_parser, |i| color_registry_parser(i, names)))(i)?;
+    let (rest, _) = pair(multispace, token(")"))(i)?;
+    match names.get(name).or(fallback) {
+        Some(color) => Ok((rest, color)),
+        None => Err(nom::Err::Error(InputError::expected(
+            name_input,
+            "<color name>",
+        ))),
     }
 }
 
@@ -317,23 +811,63 @@ fn color_css_names_parser(input: &str) -> IResult<&str, Color> {
 /// * `red, blue -> css color names (see https://drafts.csswg.org/css-color/#named-colors)
 /// * `#f0f`, `#ff00ff` -> hex color (3 or 6 digits)
 /// * `#ff00ff00` -> hex color with alpha (8 digits)
-/// * `rgb(1.0, 0.0, 0.0)` -> rgb color (0.0-1.0)
-/// * `rgba(1.0, 0.0, 0.0, 1.0)` -> rgb color with alpha (0.0-1.0)
-/// * `hsl(0.0, 1.0, 0.5)` -> hsl color (0.0-1.0)
-/// * `hsla(0.0, 1.0, 0.5, 1.0)` -> hsl color with alpha (0.0-1.0)
+/// * `rgb(255, 0, 0)`, `rgb(100%, 0%, 0%)` -> rgb color (0-255 or percentages)
+/// * `rgba(255, 0, 0, 0.5)` -> rgb color with alpha (0.0-1.0 or percentage)
+/// * `rgb(255 0 0)`, `rgb(100% 0% 0% / 50%)` -> rgb color (0-255 or percentages)
+/// * `hsl(0.0, 1.0, 0.5)` -> hsl color (hue in degrees, 0.0-1.0)
+/// * `hsla(0.0, 1.0, 0.5, 1.0)` -> hsl color with alpha (hue in degrees, 0.0-1.0)
+/// * `hsl(120deg 100% 50%)`, `hsl(0.5turn 100 50 / 0.3)` -> hsl color (hue angle, 0-100 or percentages)
 ///
+/// * `hwb(120deg 20% 30%)` -> hwb color (hue angle, 0-100 or percentages)
+/// * `lab(54.3 80.8 69.9)`, `lch(54.3% 106.8 40.9deg)` -> CIE lab / lch color
+/// * `oklab(62.8% 0.22 0.13)`, `oklch(0.628 0.26 29.2deg)` -> oklab / oklch color
+/// * `color(srgb 1 0 0)`, `color(srgb-linear 2 0.5 0)` -> color in a predefined color space
+///   (`srgb`, `srgb-linear`, `display-p3`, `rec2020`, `xyz-d50`, `xyz-d65` or `xyz`)
+/// * `color-mix(in oklch, red 40%, blue)`, `color-mix(in hsl longer hue, red, lime)` -> mix of two colors
+///   in a color space, 50% each unless given otherwise
+/// * `rgb(from #336699 r g b / 50%)`, `hsl(from red h s calc(l + 10%))` -> relative color derived
+///   from the channels of an origin color, supported by all space separated color functions
+///
+/// The comma separated `rgb()` takes channels in the range of 0-255 like css, while the comma
+/// separated `hsl()` keeps saturation and lightness in the range of 0.0-1.0, the space
+/// separated syntax follows CSS Color Level 4. `rgba` and `hsla` are aliases of `rgb` and `hsl`,
+/// alpha is optional in all of them and channels can be `none`, which is interpreted as zero.
+/// Channels of the space separated syntax can also be math functions like `calc()` or `min()`.
+/// Colors outside of the sRGB gamut are mapped into it by reducing their chroma,
+/// except for the unbounded `srgb`, `srgb-linear` and `xyz` spaces of `color()`,
+/// where linear colors are returned as [`Color::RgbaLinear`] with exact values.
+///
+/// Color names are resolved with the css color table, see [`color_registry_parser`]
+/// to resolve custom color names as well.
 pub fn color_parser(input: &str) -> IResult<&str, Color> {
+    color_registry_parser(input, &CSS_COLOR_NAMES)
+}
+
+/// Parser for colors like [`color_parser`], that resolves color names with a [`ColorNameRegistry`]
+///
+/// Registered names can be used like css color names, like `brand-primary`, or as
+/// `var(--brand-primary)`, which also accepts a fallback color: `var(--brand-primary, #336699)`.
+pub fn color_registry_parser<'a>(
+    input: &'a str,
+    names: &ColorNameRegistry,
+) -> IResult<&'a str, Color> {
     delimited(
         multispace,
         alt((
-            color_rgb_parser,
-            color_rgba_parser,
-            color_hsl_parser,
-            color_hsla_parser,
+            |i| color_rgb_parser(i, names),
+            |i| color_hsl_parser(i, names),
+            |i| color_hwb_parser(i, names),
+            |i| color_lab_parser(i, names),
+            |i| color_lch_parser(i, names),
+            |i| color_oklab_parser(i, names),
+            |i| color_oklch_parser(i, names),
+            |i| color_mix_parser(i, names),
+            |i| color_predefined_parser(i, names),
+            |i| color_var_parser(i, names),
             color_hex8_parser,
             color_hex6_parser,
             color_hex3_parser,
-            color_css_names_parser,
+            |i| color_names_parser(i, names),
         )),
         multispace,
     )(input)
@@ -344,7 +878,130 @@ pub fn color_string_parser(input: &str) -> Option<Color> {
     color_parser(input).map(|(_, value)| value).ok()
 }
 
-/// Wrapper for [`angle_parser`] that implements a serde deserializer
+/// Wrapper for [`color_parser`] that returns a [`ParseError`] on failure
+///
+/// Unlike [`color_string_parser`] the whole input must be consumed.
+pub fn color_result_parser(input: &str) -> Result<Color, ParseError> {
+    parse_all(input, color_parser)
+}
+
+/// Wrapper for [`color_registry_parser`] that returns an optional [`bevy::render::color::Color`]
+pub fn color_registry_string_parser(input: &str, names: &ColorNameRegistry) -> Option<Color> {
+    color_registry_parser(input, names)
+        .map(|(_, value)| value)
+        .ok()
+}
+
+/// Wrapper for [`color_registry_parser`] that returns a [`ParseError`] on failure
+///
+/// Unlike [`color_registry_string_parser`] the whole input must be consumed.
+pub fn color_registry_result_parser(
+    input: &str,
+    names: &ColorNameRegistry,
+) -> Result<Color, ParseError> {
+    parse_all(input, |i| color_registry_parser(i, names))
+}
+
+/// Returns the channel as a byte, if the byte represents the channel exactly
+fn exact_byte(channel: f32) -> Option<u8> {
+    let byte = (channel * 255.0).round();
+    ((0.0..=255.0).contains(&byte) && byte / 255.0 == channel).then_some(byte as u8)
+}
+
+/// Formats a [`bevy::render::color::Color`] as a string, the counterpart of [`color_parser`]
+///
+/// The output is canonical and parses back to the same color:
+///
+/// * `#ff0000`, `#ff000080` -> [`Color::Rgba`] with channels that are exact bytes
+/// * `rgb(255 128 0 / 0.25)` -> [`Color::Rgba`] with channels that are exact bytes
+/// * `color(srgb 1 0.5 0.1)`, `color(srgb 1 0.5 0.1 / 0.25)` -> any other [`Color::Rgba`]
+/// * `hsl(120, 1, 0.5)`, `hsla(120, 1, 0.5, 0.25)` -> [`Color::Hsla`]
+/// * `color(srgb-linear 2 0.5 0)`, `color(srgb-linear 2 0.5 0 / 0.25)` -> [`Color::RgbaLinear`]
+///
+/// [`Color::Lcha`] has no css counterpart and is formatted as an sRGB color.
+pub fn color_formatter(color: Color) -> String {
+    match color {
+        Color::Rgba {
+            red,
+            green,
+            blue,
+            alpha,
+        } => match (
+            exact_byte(red),
+            exact_byte(green),
+            exact_byte(blue),
+            exact_byte(alpha),
+        ) {
+            (Some(r), Some(g), Some(b), Some(255)) => format!("#{:02x}{:02x}{:02x}", r, g, b),
+            (Some(r), Some(g), Some(b), Some(a)) => {
+                format!("#{:02x}{:02x}{:02x}{:02x}", r, g, b, a)
+            }
+            (Some(r), Some(g), Some(b), None) => format!("rgb({} {} {} / {})", r, g, b, alpha),
+            _ if alpha == 1.0 => format!("color(srgb {} {} {})", red, green, blue),
+            _ => format!("color(srgb {} {} {} / {})", red, green, blue, alpha),
+        },
+        Color::Hsla {
+            hue,
+            saturation,
+            lightness,
+            alpha,
+        } => {
+            // the parser normalizes hues into 0-360, so the output does as well
+            let hue = hue.rem_euclid(360.0);
+            if alpha == 1.0 {
+                format!("hsl({}, {}, {})", hue, saturation, lightness)
+            } else {
+                format!("hsla({}, {}, {}, {})", hue, saturation, lightness, alpha)
+            }
+        }
+        Color::RgbaLinear {
+            red,
+            green,
+            blue,
+            alpha,
+        } => {
+            if alpha == 1.0 {
+                format!("color(srgb-linear {} {} {})", red, green, blue)
+            } else {
+                format!("color(srgb-linear {} {} {} / {})", red, green, blue, alpha)
+            }
+        }
+        Color::Lcha { .. } => color_formatter(color.as_rgba()),
+    }
+}
+
+/// Formats a [`bevy::render::color::Color`] as a css color name if one matches exactly,
+/// otherwise like [`color_formatter`]
+///
+/// Of names with the same color the alphabetically first one is used, like `aqua` for `cyan`.
+pub fn color_name_formatter(color: Color) -> String {
+    CSS_COLOR_TABLE
+        .iter()
+        .filter(|(_, named)| **named == color)
+        .map(|(name, _)| *name)
+        .min()
+        .map_or_else(|| color_formatter(color), str::to_string)
+}
+
+/// Returns the name of the css color that is perceptually closest to the color
+///
+/// The distance is measured in OKLab and the alpha is ignored, so unlike
+/// [`color_formatter`] this is lossy.
+pub fn nearest_css_color_name(color: Color) -> &'static str {
+    let oklab = |color: Color| {
+        let [r, g, b, _] = color.as_linear_rgba_f32();
+        linear_srgb_to_oklab([r, g, b])
+    };
+    let target = oklab(color);
+    CSS_COLOR_TABLE
+        .iter()
+        .map(|(name, named)| (delta_e_ok(oklab(*named), target), *name))
+        .min_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(_, name)| name)
+        .expect("the css color table is not empty")
+}
+
+/// Wrapper for [`color_parser`] that implements a serde deserializer
 #[cfg(feature = "serde")]
 pub fn color_serde_parser<'de, D>(deserializer: D) -> Result<Color, D::Error>
 where
@@ -352,7 +1009,17 @@ where
 {
     use serde::de::Error;
     let s: &str = serde::Deserialize::deserialize(deserializer)?;
-    color_string_parser(s).ok_or(D::Error::custom("invalid color string"))
+    color_result_parser(s)
+        .map_err(|error| D::Error::custom(format!("invalid color string: {}", error)))
+}
+
+/// Wrapper for [`color_formatter`] that implements a serde serializer
+#[cfg(feature = "serde")]
+pub fn color_serde_serializer<S>(color: &Color, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.serialize_str(&color_formatter(*color))
 }
 
 #[cfg(test)]
@@ -364,32 +1031,215 @@ mod tests {
     #[test_case("#FF0000FF", Color::RED ; "hex8 red")]
     #[test_case("#F00", Color::RED ; "hex3 red")]
     #[test_case("#f00", Color::RED ; "hex3 red lowercase")]
-    #[test_case("rgb(1.0, 0, 0)", Color::RED ; "rgb red")]
-    #[test_case("rgba(1.0, 0, 0, 1)", Color::RED ; "rgba red")]
+    #[test_case("rgb(255, 0, 0)", Color::RED ; "rgb red")]
+    #[test_case("rgba(255, 0, 0, 1)", Color::RED ; "rgba red")]
+    #[test_case("rgba(255, 128, 0, 0.5)", Color::rgba(1.0, 128.0 / 255.0, 0.0, 0.5) ; "rgba orange")]
     #[test_case("hsl(0, 1.0, 0.5)", Color::RED.as_hsla() ; "hsl red")]
     #[test_case("hsla(0, 1.0, 0.5, 1)", Color::RED.as_hsla() ; "hsla red")]
+    #[test_case("rgb(255, 0, 0, 1)", Color::RED ; "rgb with alpha red")]
+    #[test_case("rgba(255, 0, 0)", Color::RED ; "rgba without alpha red")]
+    #[test_case("rgb(100%, 0%, 0%)", Color::RED ; "rgb percentages red")]
+    #[test_case("rgb(300, -10, 0, 150%)", Color::RED ; "rgb clamped red")]
+    #[test_case("rgb(300 -10 0 / 1.5)", Color::RED ; "rgb modern clamped red")]
+    #[test_case("rgb(255 0 0)", Color::RED ; "rgb modern red")]
+    #[test_case("rgb(255 0 none)", Color::RED ; "rgb modern none red")]
+    #[test_case("rgb(255 0 0 / 50%)", Color::rgba(1.0, 0.0, 0.0, 0.5) ; "rgb modern alpha percentage")]
+    #[test_case("rgba(255 0 0/0.5)", Color::rgba(1.0, 0.0, 0.0, 0.5) ; "rgba modern alpha")]
+    #[test_case("rgb(100% 0% 0%)", Color::RED ; "rgb modern percentages red")]
+    #[test_case("hsl(0, 1.0, 0.5, 1)", Color::RED.as_hsla() ; "hsl with alpha red")]
+    #[test_case("hsla(0, 100%, 50%)", Color::RED.as_hsla() ; "hsla percentages red")]
+    #[test_case("hsl(120deg 100% 50% / 0.3)", Color::hsla(120.0, 1.0, 0.5, 0.3) ; "hsl modern")]
+    #[test_case("hsl(120 100 50)", Color::hsl(120.0, 1.0, 0.5) ; "hsl modern numbers")]
+    #[test_case("hsl(0.5turn 100% 50%)", Color::hsl(180.0, 1.0, 0.5) ; "hsl modern turn")]
+    #[test_case("hsl(-90deg 100% 50%)", Color::hsl(270.0, 1.0, 0.5) ; "hsl modern negative hue")]
+    #[test_case("hsl(none 100% 50%)", Color::RED.as_hsla() ; "hsl modern none hue")]
     #[test_case("red", Color::RED ; "css name red")]
     #[test_case("fuchsia", Color::FUCHSIA ; "css name fuchsia")]
+    #[test_case("transparent", Color::NONE ; "transparent")]
     fn test_color_parser_variants(string: &str, expected: Color) {
         assert_eq!(color_parser(string), Ok(("", expected)));
     }
 
+    fn assert_color_close(actual: Color, expected: Color) {
+        let (actual, expected) = (actual.as_rgba_f32(), expected.as_rgba_f32());
+        for (a, e) in actual.iter().zip(expected.iter()) {
+            assert!((a - e).abs() < 0.005, "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    #[test_case("hwb(0 0% 0%)", Color::RED ; "hwb red")]
+    #[test_case("hwb(120deg 0% 50%)", Color::rgb(0.0, 0.5, 0.0) ; "hwb green")]
+    #[test_case("hwb(0 60 60 / 50%)", Color::rgba(0.5, 0.5, 0.5, 0.5) ; "hwb gray")]
+    #[test_case("lab(54.29 80.82 69.91)", Color::RED ; "lab red")]
+    #[test_case("lab(100% 0 0)", Color::WHITE ; "lab white")]
+    #[test_case("lch(54.29% 106.84 40.85deg / 0.5)", Color::rgba(1.0, 0.0, 0.0, 0.5) ; "lch red")]
+    #[test_case("oklab(62.8% 0.2249 0.1258)", Color::RED ; "oklab red")]
+    #[test_case("oklab(0 none none)", Color::BLACK ; "oklab black")]
+    #[test_case("oklch(0.628 0.2577 29.23)", Color::RED ; "oklch red")]
+    #[test_case("oklch(45.2% 78.25% 264.05deg)", Color::BLUE ; "oklch blue percentage chroma")]
+    fn test_color_parser_spaces(string: &str, expected: Color) {
+        let (rest, color) = color_parser(string).unwrap();
+        assert_eq!(rest, "");
+        assert_color_close(color, expected);
+    }
+
+    #[test_case("color(srgb 1 0 0)", Color::RED ; "srgb red")]
+    #[test_case("color(srgb 100% 0% none / 50%)", Color::rgba(1.0, 0.0, 0.0, 0.5) ; "srgb percentages")]
+    #[test_case("color(srgb-linear 2 0.5 0)", Color::rgba_linear(2.0, 0.5, 0.0, 1.0) ; "srgb-linear exact")]
+    #[test_case("color(xyz-d65 0.95047 1 1.08883)", Color::rgba_linear(1.0, 1.0, 1.0, 1.0) ; "xyz-d65 white")]
+    #[test_case("color(xyz 0.95047 1 1.08883)", Color::rgba_linear(1.0, 1.0, 1.0, 1.0) ; "xyz white")]
+    #[test_case("color(xyz-d50 0.9643 1 0.8251)", Color::rgba_linear(1.0, 1.0, 1.0, 1.0) ; "xyz-d50 white")]
+    #[test_case("color(display-p3 1 1 1)", Color::rgba_linear(1.0, 1.0, 1.0, 1.0) ; "display-p3 white")]
+    #[test_case("color(rec2020 0 0 0 / 0.5)", Color::rgba_linear(0.0, 0.0, 0.0, 0.5) ; "rec2020 black")]
+    fn test_color_parser_predefined(string: &str, expected: Color) {
+        let (rest, color) = color_parser(string).unwrap();
+        assert_eq!(rest, "");
+        assert_eq!(
+            std::mem::discriminant(&color),
+            std::mem::discriminant(&expected)
+        );
+        let (actual, expected) = (color.as_linear_rgba_f32(), expected.as_linear_rgba_f32());
+        for (a, e) in actual.iter().zip(expected.iter()) {
+            assert!((a - e).abs() < 0.001, "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    #[test]
+    fn test_color_parser_predefined_exact() {
+        assert_eq!(
+            color_string_parser("color(srgb-linear 0.1 0.2 0.3)"),
+            Some(Color::rgba_linear(0.1, 0.2, 0.3, 1.0))
+        );
+        let [r, g, b, _] = color_string_parser("color(display-p3 1 0 0)")
+            .unwrap()
+            .as_linear_rgba_f32();
+        assert!([r, g, b]
+            .iter()
+            .all(|channel| (0.0..=1.0).contains(channel)));
+    }
+
+    #[test]
+    fn test_color_parser_gamut_mapping() {
+        let [r, g, b, _] = color_string_parser("oklch(0.7 0.4 30)")
+            .unwrap()
+            .as_rgba_f32();
+        assert!([r, g, b]
+            .iter()
+            .all(|channel| (0.0..=1.0).contains(channel)));
+        assert!(r > g && r > b);
+        assert_color_close(color_string_parser("lab(120 0 0)").unwrap(), Color::WHITE);
+    }
+
+    #[test_case("rgb(from red r g b / 50%)", Color::rgba(1.0, 0.0, 0.0, 0.5) ; "rgb alpha")]
+    #[test_case("rgb(from #336699 b g r)", Color::rgb_u8(0x99, 0x66, 0x33) ; "rgb swapped channels")]
+    #[test_case("rgb(from rgb(255 0 0 / 0.5) r g 255)", Color::rgba(1.0, 0.0, 1.0, 0.5) ; "rgb origin alpha")]
+    #[test_case("rgb(from red calc(r / 2) calc((g + 51) * 2) none)", Color::rgb(0.5, 0.4, 0.0) ; "rgb calc")]
+    #[test_case("hsl(from #336699 h s calc(l + 10%))", Color::hsl(210.0, 0.5, 0.5) ; "hsl lighter")]
+    #[test_case("hsl(from red calc(h + 120) s l / calc(alpha / 2))", Color::hsla(120.0, 1.0, 0.5, 0.5) ; "hsl rotated hue")]
+    #[test_case("hwb(from red h w 50%)", Color::rgb(0.5, 0.0, 0.0) ; "hwb darker")]
+    #[test_case("lab(from red l a b)", Color::RED ; "lab identity")]
+    #[test_case("oklch(from red l c calc(h + 1turn))", Color::RED ; "oklch full turn")]
+    #[test_case("color(from red srgb-linear calc(r / 2) g b)", Color::rgba_linear(0.5, 0.0, 0.0, 1.0) ; "srgb-linear")]
+    #[test_case("rgb(calc(255 / 2) 0 0)", Color::rgb(0.5, 0.0, 0.0) ; "absolute calc")]
+    #[test_case("rgb(from #336699 min(r, 40) g clamp(0, b * 2, 255))", Color::rgb_u8(40, 102, 255) ; "min and clamp")]
+    fn test_color_parser_relative(string: &str, expected: Color) {
+        let (rest, color) = color_parser(string).unwrap();
+        assert_eq!(rest, "");
+        assert_color_close(color, expected);
+    }
+
+    #[test]
+    fn test_color_parser_relative_errors() {
+        let error = color_result_parser("rgb(from red r g x)").unwrap_err();
+        assert_eq!(error.offset, 17);
+        assert_eq!(
+            error.expected,
+            vec!["none", "calc", "min", "max", "clamp", "r", "g", "b", "alpha", "<number>"]
+        );
+        let error = color_result_parser("hsl(from red h s calc(l -10%))").unwrap_err();
+        assert_eq!(error.offset, 24);
+        assert_eq!(error.expected, vec![")"]);
+        assert_eq!(color_string_parser("rgb(r g b)"), None);
+    }
+
+    #[test_case(Color::RED, "#ff0000" ; "hex")]
+    #[test_case(Color::rgba_u8(0x33, 0x66, 0x99, 0x80), "#33669980" ; "hex with alpha")]
+    #[test_case(Color::rgba(1.0, 0.0, 0.0, 0.5), "rgb(255 0 0 / 0.5)" ; "rgb with alpha")]
+    #[test_case(Color::rgb(0.5, 0.25, 0.1), "color(srgb 0.5 0.25 0.1)" ; "srgb")]
+    #[test_case(Color::rgba(0.5, 0.25, 0.1, 0.5), "color(srgb 0.5 0.25 0.1 / 0.5)" ; "srgb with alpha")]
+    #[test_case(Color::rgb(1.5, -0.5, 0.0), "color(srgb 1.5 -0.5 0)" ; "srgb out of range")]
+    #[test_case(Color::hsl(210.0, 0.5, 0.4), "hsl(210, 0.5, 0.4)" ; "hsl")]
+    #[test_case(Color::hsla(210.0, 0.5, 0.4, 0.3), "hsla(210, 0.5, 0.4, 0.3)" ; "hsla")]
+    #[test_case(Color::rgba_linear(2.0, 0.5, 0.1, 1.0), "color(srgb-linear 2 0.5 0.1)" ; "linear")]
+    #[test_case(Color::rgba_linear(0.1, 0.2, 0.3, 0.4), "color(srgb-linear 0.1 0.2 0.3 / 0.4)" ; "linear with alpha")]
+    fn test_color_formatter(color: Color, expected: &str) {
+        assert_eq!(color_formatter(color), expected);
+        assert_eq!(color_result_parser(expected), Ok(color));
+    }
+
+    #[test]
+    fn test_color_formatter_round_trip() {
+        for color in [
+            Color::rgba(0.123_456_79, 0.987_654_3, 1.0 / 3.0, 0.1),
+            Color::rgb_u8(1, 2, 3),
+            Color::hsla(359.9, 0.333_333_34, 0.1, 0.999),
+            Color::rgba_linear(0.000_001, 1e9, 0.5, 0.0),
+        ] {
+            assert_eq!(color_result_parser(&color_formatter(color)), Ok(color));
+        }
+    }
+
+    #[test_case(Color::hsl(400.0, 0.5, 0.4), "hsl(40, 0.5, 0.4)" ; "above 360")]
+    #[test_case(Color::hsl(360.0, 0.5, 0.4), "hsl(0, 0.5, 0.4)" ; "360")]
+    #[test_case(Color::hsla(-90.0, 0.5, 0.4, 0.3), "hsla(270, 0.5, 0.4, 0.3)" ; "negative")]
+    fn test_color_formatter_hue_normalization(color: Color, expected: &str) {
+        assert_eq!(color_formatter(color), expected);
+        let parsed = color_result_parser(expected).unwrap();
+        assert_eq!(color_formatter(parsed), expected);
+    }
+
+    #[test]
+    fn test_color_name_formatter() {
+        assert_eq!(color_name_formatter(Color::RED), "red");
+        assert_eq!(color_name_formatter(Color::rgb_u8(0, 255, 255)), "aqua");
+        assert_eq!(color_name_formatter(Color::rgb_u8(1, 2, 3)), "#010203");
+        assert_eq!(nearest_css_color_name(Color::rgb_u8(250, 5, 5)), "red");
+        assert_eq!(
+            nearest_css_color_name(Color::rgb_u8(0x33, 0x66, 0x99)),
+            "steelblue"
+        );
+    }
+
     #[test]
     fn test_color_parser() {
         assert_eq!(color_parser("  red"), Ok(("", Color::RED)));
         assert_eq!(color_parser("red  "), Ok(("", Color::RED)));
         assert_eq!(color_parser(" red "), Ok(("", Color::RED)));
     }
+
+    #[test]
+    fn test_color_result_parser() {
+        assert_eq!(color_result_parser("#f00"), Ok(Color::RED));
+        let error = color_result_parser("#fz0").unwrap_err();
+        assert_eq!(error.offset, 2);
+        assert_eq!(error.expected, vec!["<hex digit>"]);
+        let error = color_result_parser("rgb(255, 0, 0").unwrap_err();
+        assert_eq!(error.offset, 13);
+        assert_eq!(error.expected, vec![")"]);
+    }
 }
 
 #[cfg(all(test, feature = "serde"))]
 mod tests_serde {
     use bevy::render::color::Color;
-    use serde::Deserialize;
+    use serde::{Deserialize, Serialize};
 
-    #[derive(Deserialize)]
+    #[derive(Deserialize, Serialize)]
     pub struct Foo {
-        #[serde(deserialize_with = "super::color_serde_parser")]
+        #[serde(
+            deserialize_with = "super::color_serde_parser",
+            serialize_with = "super::color_serde_serializer"
+        )]
         pub color: Color,
     }
 
@@ -398,4 +1248,15 @@ mod tests_serde {
         let foo: Foo = serde_json::from_str(r#"{"color": "red"}"#).unwrap();
         assert_eq!(foo.color, Color::RED);
     }
+
+    #[test]
+    fn test_color_serde_serializer() {
+        let foo = Foo {
+            color: Color::rgba(1.0, 0.0, 0.0, 0.5),
+        };
+        let json = serde_json::to_string(&foo).unwrap();
+        assert_eq!(json, r#"{"color":"rgb(255 0 0 / 0.5)"}"#);
+        let foo: Foo = serde_json::from_str(&json).unwrap();
+        assert_eq!(foo.color, Color::rgba(1.0, 0.0, 0.0, 0.5));
+    }
 }
\ No newline at end of file
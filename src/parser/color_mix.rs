@@ -0,0 +1,255 @@
+use bevy::render::color::Color;
+use nom::{
+    branch::alt,
+    character::complete::multispace1,
+    combinator::{map, opt, verify},
+    error::context,
+    sequence::{delimited, pair, preceded, terminated, tuple},
+    Parser,
+};
+
+use super::color::{
+    color_fn_parser, color_from_space, color_registry_parser, color_to_space,
+    predefined_color_space_parser,
+};
+use super::color_registry::ColorNameRegistry;
+use super::color_space::{ColorSpace, HueInterpolation};
+use super::common::{comma_parser, number_parser, token};
+use super::error::{IResult, InputError};
+
+/// Parses the name of a polar color space, like "hsl" or "oklch"
+fn polar_color_space_parser(i: &str) -> IResult<&str, ColorSpace> {
+    alt((
+        map(token("hsl"), |_| ColorSpace::Hsl),
+        map(token("hwb"), |_| ColorSpace::Hwb),
+        map(token("lch"), |_| ColorSpace::Lch),
+        map(token("oklch"), |_| ColorSpace::Oklch),
+    ))
+    .parse(i)
+}
+
+/// Parses the name of a rectangular color space, like "srgb" or "oklab"
+fn rectangular_color_space_parser(i: &str) -> IResult<&str, ColorSpace> {
+    alt((
+        map(token("lab"), |_| ColorSpace::Lab),
+        map(token("oklab"), |_| ColorSpace::Oklab),
+        predefined_color_space_parser,
+    ))
+    .parse(i)
+}
+
+/// Parses a hue interpolation method, like "shorter" or "increasing"
+fn hue_interpolation_parser(i: &str) -> IResult<&str, HueInterpolation> {
+    alt((
+        map(token("shorter"), |_| HueInterpolation::Shorter),
+        map(token("longer"), |_| HueInterpolation::Longer),
+        map(token("increasing"), |_| HueInterpolation::Increasing),
+        map(token("decreasing"), |_| HueInterpolation::Decreasing),
+    ))
+    .parse(i)
+}
+
+/// Parses the interpolation method of color-mix(), like "in oklch" or "in hsl longer hue"
+pub(crate) fn interpolation_method_parser(
+    i: &str,
+) -> IResult<&str, (ColorSpace, HueInterpolation)> {
+    preceded(
+        pair(token("in"), multispace1),
+        alt((
+            pair(
+                polar_color_space_parser,
+                map(
+                    opt(delimited(
+                        multispace1,
+                        hue_interpolation_parser,
+                        pair(multispace1, token("hue")),
+                    )),
+                    Option::unwrap_or_default,
+                ),
+            ),
+            map(rectangular_color_space_parser, |space| {
+                (space, HueInterpolation::Shorter)
+            }),
+        )),
+    )
+    .parse(i)
+}
+
+/// Parses a percentage in the range of 0%-100%, like "40%"
+fn mix_percentage_parser(i: &str) -> IResult<&str, f32> {
+    context(
+        "<percentage>",
+        verify(terminated(number_parser, token("%")), |percentage| {
+            (0.0..=100.0).contains(percentage)
+        }),
+    )
+    .parse(i)
+}
+
+/// Parses a color with an optional percentage before or after it, like "red 40%"
+fn mix_component_parser<'a>(
+    i: &'a str,
+    names: &ColorNameRegistry,
+) -> IResult<&'a str, (Color, Option<f32>)> {
+    let color = |i| color_registry_parser(i, names);
+    alt((
+        map(
+            pair(terminated(mix_percentage_parser, multispace1), color),
+            |(percentage, color)| (color, Some(percentage)),
+        ),
+        pair(color, opt(mix_percentage_parser)),
+    ))
+    .parse(i)
+}
+
+/// Mixes two colors in the color space, following the algorithm of color-mix()
+///
+/// https://drafts.csswg.org/css-color-5/#color-mix-result
+///
+/// Returns `None` if both percentages are zero.
+fn mix_colors(
+    (space, hue_interpolation): (ColorSpace, HueInterpolation),
+    (first, first_percentage): (Color, Option<f32>),
+    (second, second_percentage): (Color, Option<f32>),
+) -> Option<Color> {
+    let (p1, p2) = match (first_percentage, second_percentage) {
+        (None, None) => (50.0, 50.0),
+        (Some(p1), None) => (p1, 100.0 - p1),
+        (None, Some(p2)) => (100.0 - p2, p2),
+        (Some(p1), Some(p2)) => (p1, p2),
+    };
+    let sum = p1 + p2;
+    if sum <= 0.0 {
+        return None;
+    }
+    let alpha_multiplier = (sum / 100.0).min(1.0);
+    let mixed = interpolate_colors((space, hue_interpolation), first, second, p2 / sum);
+    Some(mixed.with_a(mixed.a() * alpha_multiplier))
+}
+
+/// Interpolates between two colors in the color space, `t` is the fraction of the second color
+///
+/// Powerless hues take the hue of the other color and the interpolation uses premultiplied alpha.
+pub(crate) fn interpolate_colors(
+    (space, hue_interpolation): (ColorSpace, HueInterpolation),
+    first: Color,
+    second: Color,
+    t: f32,
+) -> Color {
+    let mut a = color_to_space(space, first);
+    let mut b = color_to_space(space, second);
+    let hue_index = space.hue_index();
+    if let Some(index) = hue_index {
+        // a powerless hue takes the hue of the other color
+        match (
+            space.is_achromatic([a[0], a[1], a[2]]),
+            space.is_achromatic([b[0], b[1], b[2]]),
+        ) {
+            (true, false) => a[index] = b[index],
+            (false, true) => b[index] = a[index],
+            _ => {}
+        }
+        (a[index], b[index]) = hue_interpolation.fixup(a[index], b[index]);
+    }
+
+    // interpolate with premultiplied alpha, except for the hue
+    let alpha = a[3] + (b[3] - a[3]) * t;
+    let mut mixed = [0.0; 3];
+    for (index, component) in mixed.iter_mut().enumerate() {
+        if hue_index == Some(index) {
+            *component = a[index] + (b[index] - a[index]) * t;
+        } else {
+            let (a, b) = (a[index] * a[3], b[index] * b[3]);
+            let value = a + (b - a) * t;
+            *component = if alpha == 0.0 { value } else { value / alpha };
+        }
+    }
+    color_from_space(space, mixed, alpha)
+}
+
+/// Parses color-mix() function strings, like color-mix(in oklch, red 40%, blue)
+///
+/// When the percentages sum up to less than 100%, the result is made transparent
+/// accordingly, the percentages can't both be zero.
+pub(crate) fn color_mix_parser<'a>(
+    i: &'a str,
+    names: &ColorNameRegistry,
+) -> IResult<&'a str, Color> {
+    let (rest, (method, first, second)) = color_fn_parser(
+        "color-mix",
+        tuple((
+            interpolation_method_parser,
+            preceded(comma_parser, |i| mix_component_parser(i, names)),
+            preceded(comma_parser, |i| mix_component_parser(i, names)),
+        )),
+    )(i)?;
+    match mix_colors(method, first, second) {
+        Some(color) => Ok((rest, color)),
+        None => Err(nom::Err::Error(InputError::expected(
+            i,
+            "<percentages summing to more than 0%>",
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color_parser;
+    use crate::parser::color_space::from_linear_srgb;
+    use test_case::test_case;
+
+    fn assert_color_close(actual: Color, expected: Color) {
+        let (actual, expected) = (actual.as_rgba_f32(), expected.as_rgba_f32());
+        for (a, e) in actual.iter().zip(expected.iter()) {
+            assert!((a - e).abs() < 0.005, "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    #[test_case("color-mix(in srgb, red, blue)", Color::rgb(0.5, 0.0, 0.5) ; "srgb halfway")]
+    #[test_case("color-mix(in srgb, red 40%, blue)", Color::rgb(0.4, 0.0, 0.6) ; "srgb first percentage")]
+    #[test_case("color-mix(in srgb, red, 25% blue)", Color::rgb(0.75, 0.0, 0.25) ; "srgb percentage before color")]
+    #[test_case("color-mix(in srgb, red 30%, blue 30%)", Color::rgba(0.5, 0.0, 0.5, 0.6) ; "srgb percentages below 100")]
+    #[test_case("color-mix(in srgb, red 100%, blue 100%)", Color::rgb(0.5, 0.0, 0.5) ; "srgb percentages above 100")]
+    #[test_case("color-mix(in srgb, rgb(255 0 0 / 0), blue)", Color::rgba(0.0, 0.0, 1.0, 0.5) ; "srgb premultiplied")]
+    #[test_case("color-mix(in srgb-linear, white, black)", Color::rgba_linear(0.5, 0.5, 0.5, 1.0) ; "srgb-linear")]
+    #[test_case("color-mix(in hsl, red, lime)", Color::hsl(60.0, 1.0, 0.5) ; "hsl shorter hue")]
+    #[test_case("color-mix(in hsl longer hue, red, lime)", Color::hsl(240.0, 1.0, 0.5) ; "hsl longer hue")]
+    #[test_case("color-mix( in oklab , red 100% , blue 0% )", Color::RED ; "oklab whitespace")]
+    fn test_color_mix_parser(string: &str, expected: Color) {
+        let (rest, color) = color_parser(string).unwrap();
+        assert_eq!(rest, "");
+        assert_color_close(color, expected);
+    }
+
+    fn oklch_hue(color: Color) -> f32 {
+        let [r, g, b, _] = color.as_linear_rgba_f32();
+        from_linear_srgb(ColorSpace::Oklch, [r, g, b])[2]
+    }
+
+    #[test]
+    fn test_color_mix_parser_hue() {
+        // red is at 29.2deg and blue at 264.1deg, the shorter arc crosses 0deg
+        let (_, color) = color_parser("color-mix(in oklch, red 40%, blue)").unwrap();
+        assert!(
+            (oklch_hue(color) - 314.0).abs() < 2.0,
+            "{}",
+            oklch_hue(color)
+        );
+        // the powerless hue of white takes the hue of blue
+        let (_, color) = color_parser("color-mix(in oklch, white, blue)").unwrap();
+        assert!(
+            (oklch_hue(color) - 264.1).abs() < 2.0,
+            "{}",
+            oklch_hue(color)
+        );
+    }
+
+    #[test]
+    fn test_color_mix_parser_errors() {
+        assert!(color_parser("color-mix(in srgb, red 0%, blue 0%)").is_err());
+        assert!(color_parser("color-mix(in srgb, red 120%, blue)").is_err());
+        assert!(color_parser("color-mix(in srgb longer hue, red, blue)").is_err());
+        assert!(color_parser("color-mix(red, blue)").is_err());
+    }
+}
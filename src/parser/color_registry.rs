@@ -0,0 +1,212 @@
+use bevy::{ecs::system::Resource, render::color::Color, utils::HashMap};
+use lazy_static::lazy_static;
+
+use super::color::CSS_COLOR_TABLE;
+
+lazy_static! {
+    /// Registry with the css color names only, used by [`super::color_parser`]
+    pub(crate) static ref CSS_COLOR_NAMES: ColorNameRegistry = ColorNameRegistry::new();
+}
+
+/// Registry of color names, used to resolve names like `brand-primary` or `var(--danger)`
+///
+/// Names are registered in scopes: names of an inner scope shadow names of the outer
+/// scopes until the inner scope is popped again. The css color table is the outermost
+/// layer, so registered names can shadow css names like `red` as well.
+///
+/// The registry can be used as a plain value or as a bevy [`Resource`]:
+///
+/// ```rust
+/// use bevy::render::color::Color;
+/// use bevy_ui_string_parser::{color_registry_string_parser, ColorNameRegistry};
+///
+/// let mut names = ColorNameRegistry::new();
+/// names.register("brand-primary", Color::rgb_u8(0x33, 0x66, 0x99));
+/// assert_eq!(
+///     color_registry_string_parser("brand-primary", &names),
+///     Some(Color::rgb_u8(0x33, 0x66, 0x99))
+/// );
+/// assert_eq!(color_registry_string_parser("red", &names), Some(Color::RED));
+/// ```
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct ColorNameRegistry {
+    /// Registered names, from the outermost to the innermost scope
+    scopes: Vec<HashMap<String, Color>>,
+    /// Whether the css color table is used as the fallback layer
+    css_names: bool,
+}
+
+impl Default for ColorNameRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ColorNameRegistry {
+    /// Creates a registry with a single empty scope and the css color names as fallback
+    pub fn new() -> Self {
+        Self {
+            scopes: vec![HashMap::default()],
+            css_names: true,
+        }
+    }
+
+    /// Creates a registry with a single empty scope and without the css color names
+    pub fn without_css_names() -> Self {
+        Self {
+            css_names: false,
+            ..Self::new()
+        }
+    }
+
+    /// Registers a color name in the innermost scope, returning the color it replaced
+    /// in that scope, if any
+    ///
+    /// Names are css identifiers, like `brand-primary` or `danger`.
+    pub fn register(&mut self, name: impl Into<String>, color: Color) -> Option<Color> {
+        self.innermost_scope().insert(name.into(), color)
+    }
+
+    /// Registers all names of a palette in the innermost scope
+    pub fn register_palette<N: Into<String>>(
+        &mut self,
+        palette: impl IntoIterator<Item = (N, Color)>,
+    ) {
+        let scope = self.innermost_scope();
+        scope.extend(
+            palette
+                .into_iter()
+                .map(|(name, color)| (name.into(), color)),
+        );
+    }
+
+    /// Removes a color name from the innermost scope, returning its color
+    pub fn unregister(&mut self, name: &str) -> Option<Color> {
+        self.innermost_scope().remove(name)
+    }
+
+    /// Opens a new scope, names registered from now on shadow the names of the outer scopes
+    pub fn push_scope(&mut self) {
+        self.scopes.push(HashMap::default());
+    }
+
+    /// Closes the innermost scope and removes its names
+    ///
+    /// Returns false if there is only the outermost scope left, which is never removed.
+    pub fn pop_scope(&mut self) -> bool {
+        if self.scopes.len() > 1 {
+            self.scopes.pop();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns the number of scopes, which is 1 for a new registry
+    pub fn scope_depth(&self) -> usize {
+        self.scopes.len()
+    }
+
+    /// Resolves a color name, looking at the innermost scope first and
+    /// the css color table last
+    pub fn get(&self, name: &str) -> Option<Color> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name))
+            .or_else(|| self.css_names.then(|| CSS_COLOR_TABLE.get(name)).flatten())
+            .copied()
+    }
+
+    /// Returns true if the color name can be resolved
+    pub fn contains(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    fn innermost_scope(&mut self) -> &mut HashMap<String, Color> {
+        self.scopes
+            .last_mut()
+            .expect("the outermost scope is never removed")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{color_registry_result_parser, color_registry_string_parser};
+    use bevy::ecs::world::World;
+
+    #[test]
+    fn test_color_name_registry_scopes() {
+        let mut names = ColorNameRegistry::new();
+        names.register_palette([("danger", Color::RED), ("red", Color::ORANGE_RED)]);
+        assert_eq!(names.get("danger"), Some(Color::RED));
+        assert_eq!(names.get("red"), Some(Color::ORANGE_RED));
+        assert_eq!(names.get("blue"), Some(Color::BLUE));
+
+        names.push_scope();
+        assert_eq!(names.register("danger", Color::MAROON), None);
+        assert_eq!(names.get("danger"), Some(Color::MAROON));
+        assert!(names.pop_scope());
+        assert_eq!(names.get("danger"), Some(Color::RED));
+        assert!(!names.pop_scope());
+        assert_eq!(names.scope_depth(), 1);
+
+        assert_eq!(names.unregister("red"), Some(Color::ORANGE_RED));
+        assert_eq!(names.get("red"), Some(Color::RED));
+    }
+
+    #[test]
+    fn test_color_name_registry_without_css_names() {
+        let mut names = ColorNameRegistry::without_css_names();
+        names.register("brand-primary", Color::BLUE);
+        assert!(names.contains("brand-primary"));
+        assert!(!names.contains("red"));
+        assert_eq!(color_registry_string_parser("red", &names), None);
+    }
+
+    #[test]
+    fn test_color_registry_parser() {
+        let mut names = ColorNameRegistry::new();
+        names.register("accent", Color::rgb_u8(0x33, 0x66, 0x99));
+        assert_eq!(
+            color_registry_string_parser(" accent ", &names),
+            Some(Color::rgb_u8(0x33, 0x66, 0x99))
+        );
+        assert_eq!(
+            color_registry_string_parser("var(--accent)", &names),
+            Some(Color::rgb_u8(0x33, 0x66, 0x99))
+        );
+        assert_eq!(
+            color_registry_string_parser("var(--missing, red)", &names),
+            Some(Color::RED)
+        );
+        assert_eq!(
+            color_registry_string_parser("rgb(from var(--accent) r g b / 50%)", &names),
+            Some(Color::rgb_u8(0x33, 0x66, 0x99).with_a(0.5))
+        );
+        assert_eq!(
+            color_registry_string_parser("color-mix(in srgb, accent, accent)", &names)
+                .map(|color| color.as_rgba_u8()),
+            Some([0x33, 0x66, 0x99, 0xff])
+        );
+
+        let error = color_registry_result_parser("var(--missing)", &names).unwrap_err();
+        assert_eq!(error.offset, 6);
+        assert_eq!(error.expected, vec!["<color name>"]);
+    }
+
+    #[test]
+    fn test_color_name_registry_resource() {
+        let mut world = World::new();
+        world.init_resource::<ColorNameRegistry>();
+        world
+            .resource_mut::<ColorNameRegistry>()
+            .register("danger", Color::RED);
+        let names = world.resource::<ColorNameRegistry>();
+        assert_eq!(
+            color_registry_string_parser("danger", names),
+            Some(Color::RED)
+        );
+    }
+}
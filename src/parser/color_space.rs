@@ -0,0 +1,557 @@
+//! Conversions between the color spaces of CSS Color Level 4
+//!
+//! Matrices and algorithms follow the sample code of the specification:
+//! https://drafts.csswg.org/css-color-4/#color-conversion-code
+
+use bevy::render::color::SrgbColorSpace;
+
+/// A color with three components in some color space
+pub(crate) type Color3 = [f32; 3];
+
+/// Multiplies a row-major 3x3 matrix with a vector
+fn transform(matrix: &[Color3; 3], v: Color3) -> Color3 {
+    [
+        matrix[0][0] * v[0] + matrix[0][1] * v[1] + matrix[0][2] * v[2],
+        matrix[1][0] * v[0] + matrix[1][1] * v[1] + matrix[1][2] * v[2],
+        matrix[2][0] * v[0] + matrix[2][1] * v[1] + matrix[2][2] * v[2],
+    ]
+}
+
+/// Bradford chromatic adaptation from D50 to D65
+const XYZ_D50_TO_XYZ_D65: [Color3; 3] = [
+    [0.955_473_4, -0.023_098_455, 0.063_259_24],
+    [-0.028_369_71, 1.009_995_4, 0.021_041_441],
+    [0.012_314_015, -0.020_507_649, 1.330_365_9],
+];
+
+const XYZ_D65_TO_LINEAR_SRGB: [Color3; 3] = [
+    [3.240_97, -1.537_383_2, -0.498_610_76],
+    [-0.969_243_65, 1.875_967_5, 0.041_555_06],
+    [0.055_630_08, -0.203_976_96, 1.056_971_5],
+];
+
+/// Bradford chromatic adaptation from D65 to D50
+const XYZ_D65_TO_XYZ_D50: [Color3; 3] = [
+    [1.047_929_8, 0.022_946_793, -0.050_192_23],
+    [0.029_627_815, 0.990_434_5, -0.017_073_825],
+    [-0.009_243_058, 0.015_055_145, 0.751_874_3],
+];
+
+const LINEAR_SRGB_TO_XYZ_D65: [Color3; 3] = [
+    [0.412_390_8, 0.357_584_33, 0.180_480_8],
+    [0.212_639, 0.715_168_7, 0.072_192_32],
+    [0.019_330_818, 0.119_194_78, 0.950_532_14],
+];
+
+const XYZ_D65_TO_LINEAR_DISPLAY_P3: [Color3; 3] = [
+    [2.493_497, -0.931_383_6, -0.402_710_78],
+    [-0.829_489, 1.762_664_1, 0.023_624_686],
+    [0.035_845_83, -0.076_172_39, 0.956_884_5],
+];
+
+const XYZ_D65_TO_LINEAR_REC2020: [Color3; 3] = [
+    [1.716_651_2, -0.355_670_78, -0.253_366_3],
+    [-0.666_684_3, 1.616_481_2, 0.015_768_546],
+    [0.017_639_857, -0.042_770_613, 0.942_103_1],
+];
+
+const LINEAR_DISPLAY_P3_TO_XYZ_D65: [Color3; 3] = [
+    [0.486_570_95, 0.265_667_7, 0.198_217_29],
+    [0.228_974_56, 0.691_738_5, 0.079_286_91],
+    [0.0, 0.045_113_38, 1.043_944_4],
+];
+
+const LINEAR_REC2020_TO_XYZ_D65: [Color3; 3] = [
+    [0.636_958_05, 0.144_616_9, 0.168_880_98],
+    [0.262_700_2, 0.677_998_1, 0.059_301_716],
+    [0.0, 0.028_072_693, 1.060_985_1],
+];
+
+/// Reference white of the D50 illuminant in XYZ
+const D50_WHITE: Color3 = [0.964_295_7, 1.0, 0.825_104_6];
+
+/// CIE constants for the conversion from and to Lab
+const CIE_EPSILON: f32 = 216.0 / 24389.0;
+const CIE_KAPPA: f32 = 24389.0 / 27.0;
+
+/// Just noticeable difference in OKLab, used for gamut mapping
+const JND: f32 = 0.02;
+
+/// Constants of the rec2020 transfer function
+const REC2020_ALPHA: f32 = 1.099_296_8;
+const REC2020_BETA: f32 = 0.018_053_97;
+
+/// Color spaces that colors can be converted from and to, and interpolated in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorSpace {
+    Srgb,
+    SrgbLinear,
+    DisplayP3,
+    Rec2020,
+    XyzD50,
+    XyzD65,
+    Hsl,
+    Hwb,
+    Lab,
+    Lch,
+    Oklab,
+    Oklch,
+}
+
+impl ColorSpace {
+    /// Index of the hue component in polar color spaces
+    pub(crate) fn hue_index(self) -> Option<usize> {
+        match self {
+            ColorSpace::Hsl | ColorSpace::Hwb => Some(0),
+            ColorSpace::Lch | ColorSpace::Oklch => Some(2),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the hue of the color is powerless, like the hue of white or gray
+    pub(crate) fn is_achromatic(self, [_, c1, c2]: Color3) -> bool {
+        match self {
+            ColorSpace::Hsl => c1.abs() < 0.000_1,
+            ColorSpace::Hwb => c1 + c2 >= 0.999_9,
+            ColorSpace::Lch => c1 < 0.01,
+            ColorSpace::Oklch => c1 < 0.000_4,
+            _ => false,
+        }
+    }
+}
+
+/// Methods for interpolating between two hue angles
+///
+/// https://drafts.csswg.org/css-color-4/#hue-interpolation
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HueInterpolation {
+    #[default]
+    Shorter,
+    Longer,
+    Increasing,
+    Decreasing,
+}
+
+impl HueInterpolation {
+    /// Adjusts two hues in degrees, so that interpolating linearly between them
+    /// follows the interpolation method
+    pub(crate) fn fixup(self, mut a: f32, mut b: f32) -> (f32, f32) {
+        let difference = b - a;
+        match self {
+            HueInterpolation::Shorter if difference > 180.0 => a += 360.0,
+            HueInterpolation::Shorter if difference < -180.0 => b += 360.0,
+            HueInterpolation::Longer if 0.0 < difference && difference < 180.0 => a += 360.0,
+            HueInterpolation::Longer if -180.0 < difference && difference <= 0.0 => b += 360.0,
+            HueInterpolation::Increasing if b < a => b += 360.0,
+            HueInterpolation::Decreasing if a < b => a += 360.0,
+            _ => {}
+        }
+        (a, b)
+    }
+}
+
+/// Converts the components of a color in the given color space to linear sRGB
+pub(crate) fn to_linear_srgb(space: ColorSpace, color: Color3) -> Color3 {
+    match space {
+        ColorSpace::Srgb => srgb_to_linear(color),
+        ColorSpace::SrgbLinear => color,
+        ColorSpace::DisplayP3 => display_p3_to_linear_srgb(color),
+        ColorSpace::Rec2020 => rec2020_to_linear_srgb(color),
+        ColorSpace::XyzD50 => xyz_d65_to_linear_srgb(xyz_d50_to_xyz_d65(color)),
+        ColorSpace::XyzD65 => xyz_d65_to_linear_srgb(color),
+        ColorSpace::Hsl => srgb_to_linear(hsl_to_srgb(color)),
+        ColorSpace::Hwb => srgb_to_linear(hwb_to_srgb(color)),
+        ColorSpace::Lab => xyz_d65_to_linear_srgb(xyz_d50_to_xyz_d65(lab_to_xyz_d50(color))),
+        ColorSpace::Lch => to_linear_srgb(ColorSpace::Lab, lch_to_lab(color)),
+        ColorSpace::Oklab => oklab_to_linear_srgb(color),
+        ColorSpace::Oklch => oklab_to_linear_srgb(lch_to_lab(color)),
+    }
+}
+
+/// Converts a linear sRGB color to the components of the given color space
+pub(crate) fn from_linear_srgb(space: ColorSpace, rgb: Color3) -> Color3 {
+    match space {
+        ColorSpace::Srgb => linear_to_srgb(rgb),
+        ColorSpace::SrgbLinear => rgb,
+        ColorSpace::DisplayP3 => linear_srgb_to_display_p3(rgb),
+        ColorSpace::Rec2020 => linear_srgb_to_rec2020(rgb),
+        ColorSpace::XyzD50 => xyz_d65_to_xyz_d50(linear_srgb_to_xyz_d65(rgb)),
+        ColorSpace::XyzD65 => linear_srgb_to_xyz_d65(rgb),
+        ColorSpace::Hsl => srgb_to_hsl(linear_to_srgb(rgb)),
+        ColorSpace::Hwb => srgb_to_hwb(linear_to_srgb(rgb)),
+        ColorSpace::Lab => xyz_d50_to_lab(xyz_d65_to_xyz_d50(linear_srgb_to_xyz_d65(rgb))),
+        ColorSpace::Lch => lab_to_lch(from_linear_srgb(ColorSpace::Lab, rgb)),
+        ColorSpace::Oklab => linear_srgb_to_oklab(rgb),
+        ColorSpace::Oklch => lab_to_lch(linear_srgb_to_oklab(rgb)),
+    }
+}
+
+/// Applies the inverse sRGB transfer function, extended to negative values
+fn srgb_to_linear(rgb: Color3) -> Color3 {
+    rgb.map(|channel| channel.signum() * channel.abs().nonlinear_to_linear_srgb())
+}
+
+/// Applies the sRGB transfer function, extended to negative values
+fn linear_to_srgb(rgb: Color3) -> Color3 {
+    rgb.map(|channel| channel.signum() * channel.abs().linear_to_nonlinear_srgb())
+}
+
+/// Converts a hue with whiteness and blackness (0.0-1.0) to non-linear sRGB
+pub(crate) fn hwb_to_srgb([hue, white, black]: Color3) -> Color3 {
+    if white + black >= 1.0 {
+        let gray = white / (white + black);
+        return [gray; 3];
+    }
+    let [r, g, b] = hsl_to_srgb([hue, 1.0, 0.5]);
+    let scale = 1.0 - white - black;
+    [r * scale + white, g * scale + white, b * scale + white]
+}
+
+/// Converts non-linear sRGB to a hue in degrees with whiteness and blackness (0.0-1.0)
+fn srgb_to_hwb(rgb: Color3) -> Color3 {
+    let [hue, _, _] = srgb_to_hsl(rgb);
+    let white = rgb[0].min(rgb[1]).min(rgb[2]);
+    let black = 1.0 - rgb[0].max(rgb[1]).max(rgb[2]);
+    [hue, white, black]
+}
+
+/// Converts a hue in degrees with saturation and lightness (0.0-1.0) to non-linear sRGB
+pub(crate) fn hsl_to_srgb([hue, saturation, lightness]: Color3) -> Color3 {
+    let hue = hue.rem_euclid(360.0);
+    let f = |n: f32| {
+        let k = (n + hue / 30.0) % 12.0;
+        let a = saturation * lightness.min(1.0 - lightness);
+        lightness - a * (k - 3.0).min(9.0 - k).clamp(-1.0, 1.0)
+    };
+    [f(0.0), f(8.0), f(4.0)]
+}
+
+/// Converts non-linear sRGB to a hue in degrees with saturation and lightness (0.0-1.0)
+fn srgb_to_hsl([r, g, b]: Color3) -> Color3 {
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let lightness = (min + max) / 2.0;
+    let delta = max - min;
+    if delta == 0.0 {
+        return [0.0, 0.0, lightness];
+    }
+    let saturation = if lightness == 0.0 || lightness == 1.0 {
+        0.0
+    } else {
+        (max - lightness) / lightness.min(1.0 - lightness)
+    };
+    let hue = if max == r {
+        (g - b) / delta + if g < b { 6.0 } else { 0.0 }
+    } else if max == g {
+        (b - r) / delta + 2.0
+    } else {
+        (r - g) / delta + 4.0
+    } * 60.0;
+    if saturation < 0.0 {
+        [(hue + 180.0).rem_euclid(360.0), -saturation, lightness]
+    } else {
+        [hue.rem_euclid(360.0), saturation, lightness]
+    }
+}
+
+/// Converts polar coordinates (lightness, chroma, hue in degrees) to rectangular ones
+pub(crate) fn lch_to_lab([lightness, chroma, hue]: Color3) -> Color3 {
+    let chroma = chroma.max(0.0);
+    let hue = hue.to_radians();
+    [lightness, chroma * hue.cos(), chroma * hue.sin()]
+}
+
+/// Converts rectangular coordinates (lightness, a, b) to polar ones, hue in degrees
+pub(crate) fn lab_to_lch([lightness, a, b]: Color3) -> Color3 {
+    let hue = b.atan2(a).to_degrees().rem_euclid(360.0);
+    [lightness, (a * a + b * b).sqrt(), hue]
+}
+
+/// Converts CIE Lab to CIE XYZ relative to D50
+pub(crate) fn lab_to_xyz_d50([lightness, a, b]: Color3) -> Color3 {
+    let f1 = (lightness + 16.0) / 116.0;
+    let f0 = a / 500.0 + f1;
+    let f2 = f1 - b / 200.0;
+    let x = if f0.powi(3) > CIE_EPSILON {
+        f0.powi(3)
+    } else {
+        (116.0 * f0 - 16.0) / CIE_KAPPA
+    };
+    let y = if lightness > CIE_KAPPA * CIE_EPSILON {
+        f1.powi(3)
+    } else {
+        lightness / CIE_KAPPA
+    };
+    let z = if f2.powi(3) > CIE_EPSILON {
+        f2.powi(3)
+    } else {
+        (116.0 * f2 - 16.0) / CIE_KAPPA
+    };
+    [x * D50_WHITE[0], y * D50_WHITE[1], z * D50_WHITE[2]]
+}
+
+/// Converts CIE XYZ relative to D50 to CIE Lab
+fn xyz_d50_to_lab(xyz: Color3) -> Color3 {
+    let [x, y, z] = [0, 1, 2].map(|index| {
+        let value = xyz[index] / D50_WHITE[index];
+        if value > CIE_EPSILON {
+            value.cbrt()
+        } else {
+            (CIE_KAPPA * value + 16.0) / 116.0
+        }
+    });
+    [116.0 * y - 16.0, 500.0 * (x - y), 200.0 * (y - z)]
+}
+
+pub(crate) fn xyz_d50_to_xyz_d65(xyz: Color3) -> Color3 {
+    transform(&XYZ_D50_TO_XYZ_D65, xyz)
+}
+
+fn xyz_d65_to_xyz_d50(xyz: Color3) -> Color3 {
+    transform(&XYZ_D65_TO_XYZ_D50, xyz)
+}
+
+pub(crate) fn xyz_d65_to_linear_srgb(xyz: Color3) -> Color3 {
+    transform(&XYZ_D65_TO_LINEAR_SRGB, xyz)
+}
+
+fn linear_srgb_to_xyz_d65(rgb: Color3) -> Color3 {
+    transform(&LINEAR_SRGB_TO_XYZ_D65, rgb)
+}
+
+/// Converts display-p3 to linear sRGB, display-p3 uses the sRGB transfer function
+pub(crate) fn display_p3_to_linear_srgb(rgb: Color3) -> Color3 {
+    xyz_d65_to_linear_srgb(transform(
+        &LINEAR_DISPLAY_P3_TO_XYZ_D65,
+        srgb_to_linear(rgb),
+    ))
+}
+
+/// Converts linear sRGB to display-p3
+fn linear_srgb_to_display_p3(rgb: Color3) -> Color3 {
+    linear_to_srgb(transform(
+        &XYZ_D65_TO_LINEAR_DISPLAY_P3,
+        linear_srgb_to_xyz_d65(rgb),
+    ))
+}
+
+/// Converts rec2020 to linear sRGB
+pub(crate) fn rec2020_to_linear_srgb(rgb: Color3) -> Color3 {
+    let linear = rgb.map(|channel| {
+        let value = channel.abs();
+        let linear = if value < REC2020_BETA * 4.5 {
+            value / 4.5
+        } else {
+            ((value + REC2020_ALPHA - 1.0) / REC2020_ALPHA).powf(1.0 / 0.45)
+        };
+        channel.signum() * linear
+    });
+    xyz_d65_to_linear_srgb(transform(&LINEAR_REC2020_TO_XYZ_D65, linear))
+}
+
+/// Converts linear sRGB to rec2020
+fn linear_srgb_to_rec2020(rgb: Color3) -> Color3 {
+    let linear = transform(&XYZ_D65_TO_LINEAR_REC2020, linear_srgb_to_xyz_d65(rgb));
+    linear.map(|channel| {
+        let value = channel.abs();
+        let encoded = if value < REC2020_BETA {
+            value * 4.5
+        } else {
+            REC2020_ALPHA * value.powf(0.45) - (REC2020_ALPHA - 1.0)
+        };
+        channel.signum() * encoded
+    })
+}
+
+/// Converts OKLab to linear sRGB
+///
+/// https://bottosson.github.io/posts/oklab/
+pub(crate) fn oklab_to_linear_srgb([lightness, a, b]: Color3) -> Color3 {
+    let l = (lightness + 0.396_337_78 * a + 0.215_803_76 * b).powi(3);
+    let m = (lightness - 0.105_561_346 * a - 0.063_854_17 * b).powi(3);
+    let s = (lightness - 0.089_484_18 * a - 1.291_485_5 * b).powi(3);
+    [
+        4.076_741_7 * l - 3.307_711_6 * m + 0.230_969_94 * s,
+        -1.268_438 * l + 2.609_757_4 * m - 0.341_319_38 * s,
+        -0.004_196_086_3 * l - 0.703_418_6 * m + 1.707_614_7 * s,
+    ]
+}
+
+/// Converts linear sRGB to OKLab
+///
+/// https://bottosson.github.io/posts/oklab/
+pub(crate) fn linear_srgb_to_oklab([r, g, b]: Color3) -> Color3 {
+    let l = (0.412_221_46 * r + 0.536_332_55 * g + 0.051_445_995 * b).cbrt();
+    let m = (0.211_903_5 * r + 0.680_699_5 * g + 0.107_396_96 * b).cbrt();
+    let s = (0.088_302_46 * r + 0.281_718_85 * g + 0.629_978_7 * b).cbrt();
+    [
+        0.210_454_26 * l + 0.793_617_8 * m - 0.004_072_047 * s,
+        1.977_998_5 * l - 2.428_592_2 * m + 0.450_593_7 * s,
+        0.025_904_037 * l + 0.782_771_77 * m - 0.808_675_77 * s,
+    ]
+}
+
+/// Returns true if the linear sRGB color is within the sRGB gamut
+fn in_srgb_gamut(rgb: Color3) -> bool {
+    const EPSILON: f32 = 0.000_1;
+    rgb.iter()
+        .all(|channel| (-EPSILON..=1.0 + EPSILON).contains(channel))
+}
+
+/// Euclidean distance of two colors in OKLab
+pub(crate) fn delta_e_ok(a: Color3, b: Color3) -> f32 {
+    let [dl, da, db] = [a[0] - b[0], a[1] - b[1], a[2] - b[2]];
+    (dl * dl + da * da + db * db).sqrt()
+}
+
+/// Maps a linear sRGB color into the sRGB gamut
+///
+/// Reduces the chroma in OKLCH until clipping the color produces a color
+/// that is not noticeably different, following the CSS gamut mapping algorithm:
+/// https://drafts.csswg.org/css-color-4/#binsearch
+pub(crate) fn gamut_map_linear_srgb(rgb: Color3) -> Color3 {
+    const EPSILON: f32 = 0.000_1;
+    let clip = |rgb: Color3| rgb.map(|channel| channel.clamp(0.0, 1.0));
+
+    if in_srgb_gamut(rgb) {
+        return clip(rgb);
+    }
+    let origin = lab_to_lch(linear_srgb_to_oklab(rgb));
+    if origin[0] >= 1.0 {
+        return [1.0; 3];
+    }
+    if origin[0] <= 0.0 {
+        return [0.0; 3];
+    }
+
+    let to_rgb = |chroma: f32| oklab_to_linear_srgb(lch_to_lab([origin[0], chroma, origin[2]]));
+    let mut clipped = clip(rgb);
+    if delta_e_ok(linear_srgb_to_oklab(clipped), linear_srgb_to_oklab(rgb)) < JND {
+        return clipped;
+    }
+
+    let (mut min, mut max) = (0.0, origin[1]);
+    let mut min_in_gamut = true;
+    while max - min > EPSILON {
+        let chroma = (min + max) / 2.0;
+        let current = to_rgb(chroma);
+        if min_in_gamut && in_srgb_gamut(current) {
+            min = chroma;
+            continue;
+        }
+        clipped = clip(current);
+        let delta = delta_e_ok(linear_srgb_to_oklab(clipped), linear_srgb_to_oklab(current));
+        if delta < JND {
+            if JND - delta < EPSILON {
+                return clipped;
+            }
+            min_in_gamut = false;
+            min = chroma;
+        } else {
+            max = chroma;
+        }
+    }
+    clipped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: Color3, expected: Color3) {
+        for (a, e) in actual.iter().zip(expected.iter()) {
+            assert!((a - e).abs() < 0.001, "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    #[test]
+    fn test_oklab_round_trip() {
+        let rgb = [0.2, 0.5, 0.8];
+        assert_close(oklab_to_linear_srgb(linear_srgb_to_oklab(rgb)), rgb);
+        assert_close(linear_srgb_to_oklab([1.0, 1.0, 1.0]), [1.0, 0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_lab_to_linear_srgb() {
+        let xyz = xyz_d50_to_xyz_d65(lab_to_xyz_d50([54.29, 80.82, 69.91]));
+        assert_close(xyz_d65_to_linear_srgb(xyz), [1.0, 0.0, 0.0]);
+        assert_close(
+            lab_to_lch(lch_to_lab([50.0, 30.0, 270.0])),
+            [50.0, 30.0, 270.0],
+        );
+    }
+
+    #[test]
+    fn test_wide_gamut_to_linear_srgb() {
+        assert_close(display_p3_to_linear_srgb([1.0, 1.0, 1.0]), [1.0, 1.0, 1.0]);
+        assert_close(rec2020_to_linear_srgb([1.0, 1.0, 1.0]), [1.0, 1.0, 1.0]);
+        assert_close(
+            display_p3_to_linear_srgb([0.917_488, 0.200_287, 0.138_561]),
+            [1.0, 0.0, 0.0],
+        );
+        let [r, g, b] = display_p3_to_linear_srgb([1.0, 0.0, 0.0]);
+        assert!(r > 1.0 && g < 0.0 && b < 0.0);
+    }
+
+    #[test]
+    fn test_color_space_round_trip() {
+        let rgb = [0.2, 0.5, 0.8];
+        for space in [
+            ColorSpace::Srgb,
+            ColorSpace::SrgbLinear,
+            ColorSpace::DisplayP3,
+            ColorSpace::Rec2020,
+            ColorSpace::XyzD50,
+            ColorSpace::XyzD65,
+            ColorSpace::Hsl,
+            ColorSpace::Hwb,
+            ColorSpace::Lab,
+            ColorSpace::Lch,
+            ColorSpace::Oklab,
+            ColorSpace::Oklch,
+        ] {
+            assert_close(to_linear_srgb(space, from_linear_srgb(space, rgb)), rgb);
+        }
+        assert_close(
+            from_linear_srgb(ColorSpace::Hsl, [1.0, 0.0, 0.0]),
+            [0.0, 1.0, 0.5],
+        );
+        assert_close(
+            from_linear_srgb(ColorSpace::Lab, [1.0, 1.0, 1.0]),
+            [100.0, 0.0, 0.0],
+        );
+    }
+
+    #[test]
+    fn test_hue_interpolation_fixup() {
+        assert_eq!(HueInterpolation::Shorter.fixup(10.0, 350.0), (370.0, 350.0));
+        assert_eq!(HueInterpolation::Shorter.fixup(350.0, 10.0), (350.0, 370.0));
+        assert_eq!(HueInterpolation::Longer.fixup(10.0, 50.0), (370.0, 50.0));
+        assert_eq!(
+            HueInterpolation::Increasing.fixup(50.0, 10.0),
+            (50.0, 370.0)
+        );
+        assert_eq!(
+            HueInterpolation::Decreasing.fixup(10.0, 50.0),
+            (370.0, 50.0)
+        );
+    }
+
+    #[test]
+    fn test_hwb_to_srgb() {
+        assert_close(hwb_to_srgb([0.0, 0.0, 0.0]), [1.0, 0.0, 0.0]);
+        assert_close(hwb_to_srgb([120.0, 0.0, 0.5]), [0.0, 0.5, 0.0]);
+        assert_close(hwb_to_srgb([0.0, 0.6, 0.6]), [0.5, 0.5, 0.5]);
+    }
+
+    #[test]
+    fn test_gamut_map_linear_srgb() {
+        assert_close(gamut_map_linear_srgb([0.5, 0.5, 0.5]), [0.5, 0.5, 0.5]);
+        assert_close(gamut_map_linear_srgb([1.2, 1.2, 1.2]), [1.0, 1.0, 1.0]);
+        let mapped = gamut_map_linear_srgb(oklab_to_linear_srgb(lch_to_lab([0.7, 0.4, 30.0])));
+        assert!(mapped.iter().all(|channel| (0.0..=1.0).contains(channel)));
+        // the hue is preserved
+        let [_, _, hue] = lab_to_lch(linear_srgb_to_oklab(mapped));
+        assert!((hue - 30.0).abs() < 2.0, "{}", hue);
+    }
+}
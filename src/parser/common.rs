@@ -0,0 +1,164 @@
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_while, take_while1},
+    character::complete::{char, digit0, digit1, multispace0, one_of},
+    combinator::{map_res, opt, recognize},
+    error::context,
+    sequence::{delimited, pair, tuple},
+    Parser,
+};
+
+use super::error::{IResult, InputError};
+
+/// Recognizes a float number, like `1`, `-.5` or `1e3`
+///
+/// Unlike nom's `float`, an `e` is only parsed as exponent if digits follow,
+/// so that units like `em` can follow the number.
+fn number_recognizer(input: &str) -> IResult<&str, &str> {
+    recognize(tuple((
+        opt(one_of("+-")),
+        alt((
+            recognize(pair(digit1, opt(pair(char('.'), digit0)))),
+            recognize(pair(char('.'), digit1)),
+        )),
+        opt(tuple((one_of("eE"), opt(one_of("+-")), digit1))),
+    )))(input)
+}
+
+/// Parses a float number, reporting `number` as the expected token on failure
+pub(crate) fn number_parser(input: &str) -> IResult<&str, f32> {
+    context("<number>", map_res(number_recognizer, str::parse::<f32>))(input)
+}
+
+/// Parses a double precision float number, like [`number_parser`]
+pub(crate) fn number_parser_f64(input: &str) -> IResult<&str, f64> {
+    context("<number>", map_res(number_recognizer, str::parse::<f64>))(input)
+}
+
+/// Parses the given literal token, reporting it as the expected token on failure
+pub(crate) fn token(name: &'static str) -> impl FnMut(&str) -> IResult<&str, &str> {
+    move |input| context(name, tag(name))(input)
+}
+
+/// Parses a css identifier, like `red`, `flex-start` or `brand-primary`
+pub(crate) fn identifier_parser(input: &str) -> IResult<&str, &str> {
+    context(
+        "<identifier>",
+        recognize(pair(
+            take_while1(|c: char| c.is_ascii_alphabetic() || c == '-' || c == '_'),
+            take_while(|c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_'),
+        )),
+    )(input)
+}
+
+/// Parses a css function with the given name, like `min(...)`, the inner parser
+/// is applied to the arguments between the parentheses
+pub(crate) fn function_parser<'a, O>(
+    name: &'static str,
+    inner_parser: impl Parser<&'a str, O, InputError<&'a str>>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, O> {
+    delimited(
+        tuple((token(name), token("("), multispace0)),
+        inner_parser,
+        tuple((multispace0, token(")"))),
+    )
+}
+
+/// Parses a comma separator between function arguments or list entries
+pub(crate) fn comma_parser(input: &str) -> IResult<&str, &str> {
+    delimited(multispace0, token(","), multispace0)(input)
+}
+
+/// Parses a number argument of a function, like `-0.5`
+pub(crate) fn number_argument_parser(input: &str) -> IResult<&str, f32> {
+    delimited(multispace0, number_parser, multispace0)(input)
+}
+
+/// Parses one or two values, a single value is used for both, like `10px` or `10px 20%`
+///
+/// The values must consume surrounding whitespace themselves, so that values
+/// with inner whitespace like `calc(100% - 10px)` are parsed as a whole. The second
+/// value is only parsed if whitespace separates it from the first, like in css.
+pub(crate) fn pair_parser<'a, O: Clone>(
+    mut parser: impl FnMut(&'a str) -> IResult<&'a str, O>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, (O, O)> {
+    move |start: &'a str| {
+        let (input, first) = parser(start)?;
+        let consumed = &start[..start.len() - input.len()];
+        if !consumed.ends_with(char::is_whitespace) {
+            return Ok((input, (first.clone(), first)));
+        }
+        match parser(input) {
+            Ok((input, second)) => Ok((input, (first, second))),
+            Err(nom::Err::Error(_)) => Ok((input, (first.clone(), first))),
+            Err(error) => Err(error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{angle_parser, color_parser, val_parser};
+    use bevy::{render::color::Color, ui::Val};
+    use std::f32::consts::PI;
+    use test_case::test_case;
+
+    #[test_case("1e1", 10.0, "" ; "exponent")]
+    #[test_case("1E+1", 10.0, "" ; "signed exponent")]
+    #[test_case("1em", 1.0, "em" ; "em unit")]
+    #[test_case("1e", 1.0, "e" ; "exponent without digits")]
+    #[test_case(".5", 0.5, "" ; "leading dot")]
+    #[test_case("-.5", -0.5, "" ; "negative leading dot")]
+    #[test_case("5.", 5.0, "" ; "trailing dot")]
+    fn test_number_parser(string: &str, expected: f32, rest: &str) {
+        assert_eq!(number_parser(string), Ok((rest, expected)));
+    }
+
+    #[test_case("10px 20px", (Val::Px(10.0), Val::Px(20.0)), "" ; "two values")]
+    #[test_case(" 10px ", (Val::Px(10.0), Val::Px(10.0)), "" ; "one value")]
+    #[test_case("10px20px", (Val::Px(10.0), Val::Px(10.0)), "20px" ; "no separator")]
+    #[test_case("10px,20px", (Val::Px(10.0), Val::Px(10.0)), ",20px" ; "comma")]
+    fn test_pair_parser(string: &str, expected: (Val, Val), rest: &str) {
+        assert_eq!(pair_parser(val_parser)(string), Ok((rest, expected)));
+    }
+
+    #[test]
+    fn test_number_parser_negative_zero() {
+        let (_, value) = number_parser("-0").unwrap();
+        assert_eq!(value, 0.0);
+        assert!(value.is_sign_negative());
+    }
+
+    #[test_case("1e1px", Val::Px(10.0) ; "exponent")]
+    #[test_case(".5%", Val::Percent(0.5) ; "leading dot")]
+    #[test_case("-0px", Val::Px(0.0) ; "negative zero")]
+    fn test_number_lexing_val(string: &str, expected: Val) {
+        assert_eq!(val_parser(string), Ok(("", expected)));
+    }
+
+    #[test_case("1e1deg", 10.0_f32.to_radians() ; "exponent")]
+    #[test_case(".5turn", PI ; "leading dot")]
+    #[test_case("-0rad", 0.0 ; "negative zero")]
+    fn test_number_lexing_angle(string: &str, expected: f32) {
+        assert_eq!(angle_parser(string), Ok(("", expected)));
+    }
+
+    #[test_case("rgb(1e1 .5 -0)", Color::rgb(10.0 / 255.0, 0.5 / 255.0, 0.0) ; "rgb")]
+    #[test_case("#1e1", Color::rgb_u8(0x11, 0xee, 0x11) ; "hex with e")]
+    #[test_case("hsl(-0 0% 1e2%)", Color::hsl(0.0, 0.0, 1.0) ; "hsl")]
+    fn test_number_lexing_color(string: &str, expected: Color) {
+        assert_eq!(color_parser(string), Ok(("", expected)));
+    }
+
+    #[test]
+    fn test_number_lexing_em() {
+        // `e` of the unit must not be read as an exponent
+        assert!(val_parser("1em").is_err());
+        assert_eq!(angle_parser("1em"), Ok(("em", 1.0)));
+        assert_eq!(
+            crate::length_parser("1em"),
+            Ok(("", crate::Length::Em(1.0)))
+        );
+    }
+}
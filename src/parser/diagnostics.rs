@@ -0,0 +1,194 @@
+use super::error::ParseError;
+use super::CSS_COLOR_TABLE;
+
+/// Maximum edit distance for a token to be offered as a suggestion.
+const MAX_SUGGESTION_DISTANCE: usize = 2;
+
+/// Renders a [`ParseError`] as a human-readable, multi-line report.
+///
+/// The report contains the source line with a caret underline below the
+/// offending fragment, the expected tokens and a suggestion if one is found:
+///
+/// ```text
+/// error: unexpected "pc" at line 1, column 3
+///   |
+/// 1 | 10pc
+///   |   ^^ expected one of: px, %, vw, vh, vmin, vmax
+///   |
+///   = help: did you mean "10px"?
+/// ```
+pub fn render_diagnostic(source: &str, error: &ParseError) -> String {
+    // the error may have been built by hand, so the offset is floored to a char boundary
+    let mut offset = error.offset.min(source.len());
+    while !source.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    let line_start = source[..offset].rfind('\n').map_or(0, |index| index + 1);
+    let line_end = source[offset..]
+        .find('\n')
+        .map_or(source.len(), |index| offset + index);
+    let line_number = source[..line_start].matches('\n').count() + 1;
+    let column = source[line_start..offset].chars().count();
+
+    let gutter = " ".repeat(line_number.to_string().len());
+    let underline = "^".repeat(error.fragment.chars().count().max(1));
+    let found = if error.fragment.is_empty() {
+        "end of input".to_string()
+    } else {
+        format!("\"{}\"", error.fragment)
+    };
+
+    let mut report = format!(
+        "error: unexpected {} at line {}, column {}\n",
+        found,
+        line_number,
+        column + 1
+    );
+    report += &format!("{} |\n", gutter);
+    report += &format!("{} | {}\n", line_number, &source[line_start..line_end]);
+    report += &format!("{} | {}{}", gutter, " ".repeat(column), underline);
+    if let Some(message) = error.expected_message() {
+        report += &format!(" {}", message);
+    }
+    report += "\n";
+    if let Some(suggestion) = suggest_correction(source, error) {
+        report += &format!("{} |\n", gutter);
+        report += &format!("{} = help: did you mean \"{}\"?\n", gutter, suggestion);
+    }
+    report
+}
+
+/// Suggests a corrected version of the source for a [`ParseError`].
+///
+/// The offending fragment is replaced with the closest expected token,
+/// or the closest css color name where a color name is expected, for
+/// example `10pc` -> `10px` or `lightgreyy` -> `lightgrey`.
+pub fn suggest_correction(source: &str, error: &ParseError) -> Option<String> {
+    if error.fragment.is_empty() {
+        return None;
+    }
+    let fragment = error.fragment.to_lowercase();
+    let literals = error
+        .expected
+        .iter()
+        .copied()
+        .filter(|token| !token.starts_with('<'));
+    let names = error
+        .expected
+        .contains(&"<color name>")
+        .then(|| CSS_COLOR_TABLE.keys().copied())
+        .into_iter()
+        .flatten();
+
+    let (_, replacement) = literals
+        .chain(names)
+        .map(|candidate| (edit_distance(&fragment, candidate), candidate))
+        .filter(|(distance, candidate)| {
+            *distance <= MAX_SUGGESTION_DISTANCE && *distance < candidate.chars().count()
+        })
+        .min()?;
+
+    let before = source.get(..error.offset)?;
+    let after = source.get(error.offset + error.fragment.len()..)?;
+    Some(format!("{}{}{}", before, replacement, after))
+}
+
+/// Levenshtein distance between two strings
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{color_result_parser, val_result_parser};
+
+    #[test]
+    fn test_render_diagnostic() {
+        let error = val_result_parser("10pc").unwrap_err();
+        assert_eq!(
+            render_diagnostic("10pc", &error),
+            concat!(
+                "error: unexpected \"pc\" at line 1, column 3\n",
+                "  |\n",
+                "1 | 10pc\n",
+                "  |   ^^ expected one of: px, %, vw, vh, vmin, vmax\n",
+                "  |\n",
+                "  = help: did you mean \"10px\"?\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_render_diagnostic_end_of_input() {
+        let error = color_result_parser("rgb(255, 0, 0").unwrap_err();
+        assert_eq!(
+            render_diagnostic("rgb(255, 0, 0", &error),
+            concat!(
+                "error: unexpected end of input at line 1, column 14\n",
+                "  |\n",
+                "1 | rgb(255, 0, 0\n",
+                "  |              ^ expected )\n",
+            )
+        );
+    }
+
+    #[test]
+    fn test_render_diagnostic_multiline() {
+        let source = "red\n  12pc";
+        let error = ParseError {
+            offset: 8,
+            expected: vec!["px"],
+            fragment: "pc".to_string(),
+        };
+        let report = render_diagnostic(source, &error);
+        assert!(report.starts_with("error: unexpected \"pc\" at line 2, column 5\n"));
+        assert!(report.contains("2 |   12pc\n  |     ^^ "));
+    }
+
+    #[test]
+    fn test_render_diagnostic_inside_char() {
+        let source = "10€x";
+        let error = ParseError {
+            offset: 3,
+            expected: vec!["px"],
+            fragment: "x".to_string(),
+        };
+        let report = render_diagnostic(source, &error);
+        assert!(report.starts_with("error: unexpected \"x\" at line 1, column 3\n"));
+        assert!(report.contains("1 | 10€x\n  |   ^ "));
+    }
+
+    #[test]
+    fn test_suggest_correction() {
+        let error = color_result_parser("lightgreyy").unwrap_err();
+        assert_eq!(
+            suggest_correction("lightgreyy", &error),
+            Some("lightgrey".to_string())
+        );
+        let error = val_result_parser("10pc").unwrap_err();
+        assert_eq!(suggest_correction("10pc", &error), Some("10px".to_string()));
+        let error = val_result_parser("10").unwrap_err();
+        assert_eq!(suggest_correction("10", &error), None);
+        let error = color_result_parser("banana").unwrap_err();
+        assert_eq!(suggest_correction("banana", &error), None);
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("pc", "px"), 1);
+        assert_eq!(edit_distance("lightgreyy", "lightgrey"), 1);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+    }
+}
@@ -0,0 +1,170 @@
+use std::time::Duration;
+
+use nom::{
+    branch::alt,
+    character::complete::multispace0 as multispace,
+    character::complete::multispace1,
+    combinator::{map, not, verify},
+    sequence::{delimited, pair, preceded},
+};
+
+use super::common::{number_parser_f64, token};
+use super::error::{parse_all, IResult, ParseError};
+
+/// Parser for strings that represent a [`Duration`], like `300ms` or `1.5s`.
+///
+/// The unit is required, negative durations are not supported.
+pub fn duration_parser(input: &str) -> IResult<&str, Duration> {
+    delimited(
+        multispace,
+        map(
+            verify(
+                pair(
+                    number_parser_f64,
+                    alt((map(token("ms"), |_| 1e6), map(token("s"), |_| 1e9))),
+                ),
+                |(value, _)| *value >= 0.0,
+            ),
+            |(value, nanos)| Duration::from_nanos((value * nanos).round() as u64),
+        ),
+        multispace,
+    )(input)
+}
+
+/// Wrapper for [`duration_parser`] that returns an optional [`Duration`]
+pub fn duration_string_parser(input: &str) -> Option<Duration> {
+    duration_parser(input).map(|(_, value)| value).ok()
+}
+
+/// Wrapper for [`duration_parser`] that returns a [`ParseError`] on failure
+///
+/// Unlike [`duration_string_parser`] the whole input must be consumed.
+pub fn duration_result_parser(input: &str) -> Result<Duration, ParseError> {
+    parse_all(input, duration_parser)
+}
+
+/// Formats a [`Duration`] as a string in milliseconds, like `1500ms`
+pub fn duration_formatter(duration: Duration) -> String {
+    format!("{}ms", duration.as_nanos() as f64 / 1e6)
+}
+
+/// Parses a delay in seconds, like [`duration_parser`] but also negative, like `-200ms`
+pub(crate) fn delay_parser(input: &str) -> IResult<&str, f32> {
+    alt((
+        map(duration_parser, |duration| duration.as_secs_f32()),
+        map(
+            preceded(
+                pair(multispace, pair(token("-"), not(multispace1))),
+                duration_parser,
+            ),
+            |duration| -duration.as_secs_f32(),
+        ),
+    ))(input)
+}
+
+/// Formats a delay in seconds as a string in milliseconds, the counterpart of [`delay_parser`]
+pub(crate) fn delay_formatter(delay: f32) -> String {
+    // rounded to microseconds, the precision of `f32` seconds
+    let duration = Duration::from_micros((delay.abs() as f64 * 1e6).round() as u64);
+    if delay < 0.0 {
+        format!("-{}", duration_formatter(duration))
+    } else {
+        duration_formatter(duration)
+    }
+}
+
+/// Wrapper for [`duration_parser`] that implements a serde deserializer
+#[cfg(feature = "serde")]
+pub fn duration_serde_parser<'de, D>(deserializer: D) -> Result<Duration, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::Error;
+    let s: &str = serde::Deserialize::deserialize(deserializer)?;
+    duration_result_parser(s)
+        .map_err(|error| D::Error::custom(format!("invalid duration string: {}", error)))
+}
+
+/// Wrapper for [`duration_formatter`] that implements a serde serializer
+#[cfg(feature = "serde")]
+pub fn duration_serde_serializer<S>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.serialize_str(&duration_formatter(*duration))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("300ms", Duration::from_millis(300) ; "milliseconds")]
+    #[test_case("1.5s", Duration::from_millis(1500) ; "seconds")]
+    #[test_case(" .25s ", Duration::from_millis(250) ; "fraction with whitespace")]
+    #[test_case("0.5ms", Duration::from_micros(500) ; "fractional milliseconds")]
+    #[test_case("0s", Duration::ZERO ; "zero")]
+    fn test_duration_parser(string: &str, expected: Duration) {
+        assert_eq!(duration_result_parser(string), Ok(expected));
+    }
+
+    #[test_case("300" ; "without unit")]
+    #[test_case("-1s" ; "negative")]
+    #[test_case("2min" ; "unknown unit")]
+    fn test_duration_parser_invalid(string: &str) {
+        assert!(duration_result_parser(string).is_err());
+    }
+
+    #[test_case("-200ms", -0.2 ; "negative")]
+    #[test_case(" 50ms ", 0.05 ; "positive")]
+    #[test_case("-0s", 0.0 ; "negative zero")]
+    fn test_delay_parser(string: &str, expected: f32) {
+        assert_eq!(delay_parser(string), Ok(("", expected)));
+        assert_eq!(delay_parser(&delay_formatter(expected)), Ok(("", expected)));
+    }
+
+    #[test_case("- 1s" ; "whitespace after sign")]
+    #[test_case("--1s" ; "double sign")]
+    fn test_delay_parser_invalid(string: &str) {
+        assert!(parse_all(string, delay_parser).is_err());
+    }
+
+    #[test_case(Duration::from_millis(300), "300ms" ; "milliseconds")]
+    #[test_case(Duration::from_micros(1500), "1.5ms" ; "fractional milliseconds")]
+    #[test_case(Duration::from_secs(2), "2000ms" ; "seconds")]
+    fn test_duration_formatter(duration: Duration, expected: &str) {
+        assert_eq!(duration_formatter(duration), expected);
+        assert_eq!(duration_result_parser(expected), Ok(duration));
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests_serde {
+    use serde::{Deserialize, Serialize};
+    use std::time::Duration;
+
+    #[derive(Deserialize, Serialize)]
+    pub struct Foo {
+        #[serde(
+            deserialize_with = "super::duration_serde_parser",
+            serialize_with = "super::duration_serde_serializer"
+        )]
+        pub duration: Duration,
+    }
+
+    #[test]
+    fn test_duration_serde_parser() {
+        let foo: Foo = serde_json::from_str(r#"{"duration": "1.5s"}"#).unwrap();
+        assert_eq!(foo.duration, Duration::from_millis(1500));
+        assert!(serde_json::from_str::<Foo>(r#"{"duration": "1.5"}"#).is_err());
+    }
+
+    #[test]
+    fn test_duration_serde_serializer() {
+        let foo = Foo {
+            duration: Duration::from_millis(1500),
+        };
+        let json = serde_json::to_string(&foo).unwrap();
+        assert_eq!(json, r#"{"duration":"1500ms"}"#);
+    }
+}
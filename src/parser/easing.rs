@@ -0,0 +1,505 @@
+use nom::{
+    branch::alt,
+    character::complete::{multispace0 as multispace, u32 as integer},
+    combinator::{map, opt, verify},
+    error::context,
+    multi::{many_m_n, separated_list1},
+    sequence::{delimited, pair, preceded, terminated, tuple},
+};
+
+use super::common::{comma_parser, function_parser, number_argument_parser, number_parser, token};
+use super::error::{parse_all, IResult, ParseError};
+
+/// Where the jumps of a `steps(...)` easing function happen
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StepPosition {
+    /// `jump-start` or `start`, the first jump happens at the start
+    JumpStart,
+    /// `jump-end` or `end`, the last jump happens at the end
+    #[default]
+    JumpEnd,
+    /// `jump-none`, no jump at the start or the end
+    JumpNone,
+    /// `jump-both`, jumps at the start and the end
+    JumpBoth,
+}
+
+/// A control point of a `linear(...)` easing function
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LinearStop {
+    pub output: f32,
+    /// The input progress, between `0.0` and `1.0`
+    pub input: f32,
+}
+
+/// An easing function, like the value of `transition-timing-function`
+///
+/// Use [`Easing::sample`] to map the progress of an animation to the eased progress.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Easing {
+    /// `linear`, the progress is unchanged
+    Linear,
+    /// `cubic-bezier(x1, y1, x2, y2)`, the keywords like `ease` are cubic bezier curves
+    CubicBezier(f32, f32, f32, f32),
+    /// `steps(count, position)`
+    Steps(u32, StepPosition),
+    /// `linear(...)` with the input progress of all points resolved
+    LinearStops(Vec<LinearStop>),
+}
+
+impl Easing {
+    /// `ease`
+    pub const EASE: Easing = Easing::CubicBezier(0.25, 0.1, 0.25, 1.0);
+    /// `ease-in`
+    pub const EASE_IN: Easing = Easing::CubicBezier(0.42, 0.0, 1.0, 1.0);
+    /// `ease-out`
+    pub const EASE_OUT: Easing = Easing::CubicBezier(0.0, 0.0, 0.58, 1.0);
+    /// `ease-in-out`
+    pub const EASE_IN_OUT: Easing = Easing::CubicBezier(0.42, 0.0, 0.58, 1.0);
+
+    /// Returns the eased progress for the given progress between `0.0` and `1.0`
+    pub fn sample(&self, progress: f32) -> f32 {
+        let progress = progress.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => progress,
+            Easing::CubicBezier(x1, y1, x2, y2) => {
+                let s = solve_cubic_bezier(*x1, *x2, progress);
+                cubic_bezier(*y1, *y2, s)
+            }
+            Easing::Steps(count, position) => sample_steps(*count, *position, progress),
+            Easing::LinearStops(stops) => sample_linear_stops(stops, progress),
+        }
+    }
+}
+
+impl Default for Easing {
+    /// The css default `ease`
+    fn default() -> Self {
+        Easing::EASE
+    }
+}
+
+/// One axis of a cubic bezier curve from `(0, 0)` to `(1, 1)` at the curve parameter `s`
+fn cubic_bezier(p1: f32, p2: f32, s: f32) -> f32 {
+    let r = 1.0 - s;
+    3.0 * r * r * s * p1 + 3.0 * r * s * s * p2 + s * s * s
+}
+
+/// Finds the curve parameter of a cubic bezier curve for the given `x`
+///
+/// Uses newton iterations and falls back to bisection, `x` is monotonic as
+/// the control points are within `0.0..=1.0`.
+fn solve_cubic_bezier(x1: f32, x2: f32, x: f32) -> f32 {
+    let mut s = x;
+    for _ in 0..8 {
+        let error = cubic_bezier(x1, x2, s) - x;
+        if error.abs() < 1e-6 {
+            return s;
+        }
+        let r = 1.0 - s;
+        let slope = 3.0 * r * r * x1 + 6.0 * r * s * (x2 - x1) + 3.0 * s * s * (1.0 - x2);
+        if slope.abs() < 1e-6 {
+            break;
+        }
+        s -= error / slope;
+    }
+    let (mut low, mut high) = (0.0, 1.0);
+    s = x;
+    for _ in 0..32 {
+        let value = cubic_bezier(x1, x2, s);
+        if (value - x).abs() < 1e-6 {
+            break;
+        }
+        if value < x {
+            low = s;
+        } else {
+            high = s;
+        }
+        s = (low + high) / 2.0;
+    }
+    s
+}
+
+/// The step easing function as specified in css
+///
+/// The parser rejects step counts without jumps, like `steps(0)` or `steps(1, jump-none)`,
+/// for those the progress is unchanged.
+fn sample_steps(count: u32, position: StepPosition, progress: f32) -> f32 {
+    let steps = count as f32;
+    let mut step = (progress * steps).floor();
+    if matches!(position, StepPosition::JumpStart | StepPosition::JumpBoth) {
+        step += 1.0;
+    }
+    let jumps = match position {
+        StepPosition::JumpStart | StepPosition::JumpEnd => steps,
+        StepPosition::JumpNone => steps - 1.0,
+        StepPosition::JumpBoth => steps + 1.0,
+    };
+    if jumps <= 0.0 {
+        return progress;
+    }
+    step.min(jumps) / jumps
+}
+
+/// The linear easing function as specified in css, the stops are sorted by input
+///
+/// The parser always creates at least two stops, without stops the progress is
+/// unchanged and a single stop is a constant output.
+fn sample_linear_stops(stops: &[LinearStop], progress: f32) -> f32 {
+    match stops {
+        [] => return progress,
+        [stop] => return stop.output,
+        _ => {}
+    }
+    let index = stops
+        .iter()
+        .rposition(|stop| stop.input <= progress)
+        .unwrap_or(0)
+        .min(stops.len() - 2);
+    let (a, b) = (stops[index], stops[index + 1]);
+    if a.input == b.input {
+        return b.output;
+    }
+    let t = (progress - a.input) / (b.input - a.input);
+    a.output + (b.output - a.output) * t
+}
+
+/// Resolves the missing and decreasing input progress of `linear(...)` points as in css
+fn resolve_linear_stops(points: Vec<(f32, Option<f32>)>) -> Vec<LinearStop> {
+    let last = points.len() - 1;
+    let mut inputs: Vec<Option<f32>> = Vec::with_capacity(points.len());
+    let mut largest = 0.0_f32;
+    for (index, (_, input)) in points.iter().enumerate() {
+        let input = match (index, input) {
+            (0, None) => Some(0.0),
+            (index, None) if index == last => Some(largest.max(1.0)),
+            (_, input) => *input,
+        };
+        let input = input.map(|input| input.max(largest));
+        largest = input.unwrap_or(largest);
+        inputs.push(input);
+    }
+    // points without input are spaced evenly between their neighbors
+    let mut start = 0;
+    for index in 1..inputs.len() {
+        if let Some(end_input) = inputs[index] {
+            let start_input = inputs[start].unwrap_or(0.0);
+            let count = (index - start) as f32;
+            for (offset, input) in inputs[start + 1..index].iter_mut().enumerate() {
+                let t = (offset + 1) as f32 / count;
+                *input = Some(start_input + (end_input - start_input) * t);
+            }
+            start = index;
+        }
+    }
+    points
+        .into_iter()
+        .zip(inputs)
+        .map(|((output, _), input)| LinearStop {
+            output,
+            input: input.unwrap_or(0.0),
+        })
+        .collect()
+}
+
+/// Parses a cubic bezier control point x coordinate, which must be within `0..=1`
+fn bezier_x_parser(input: &str) -> IResult<&str, f32> {
+    context(
+        "<number between 0 and 1>",
+        verify(number_argument_parser, |value| (0.0..=1.0).contains(value)),
+    )(input)
+}
+
+fn step_position_parser(input: &str) -> IResult<&str, StepPosition> {
+    delimited(
+        multispace,
+        alt((
+            map(token("jump-start"), |_| StepPosition::JumpStart),
+            map(token("jump-end"), |_| StepPosition::JumpEnd),
+            map(token("jump-none"), |_| StepPosition::JumpNone),
+            map(token("jump-both"), |_| StepPosition::JumpBoth),
+            map(token("start"), |_| StepPosition::JumpStart),
+            map(token("end"), |_| StepPosition::JumpEnd),
+        )),
+        multispace,
+    )(input)
+}
+
+fn steps_parser(input: &str) -> IResult<&str, Easing> {
+    map(
+        function_parser(
+            "steps",
+            verify(
+                pair(
+                    delimited(multispace, context("<integer>", integer), multispace),
+                    opt(preceded(comma_parser, step_position_parser)),
+                ),
+                |(count, position)| match position {
+                    Some(StepPosition::JumpNone) => *count > 1,
+                    _ => *count > 0,
+                },
+            ),
+        ),
+        |(count, position)| Easing::Steps(count, position.unwrap_or_default()),
+    )(input)
+}
+
+/// Parses a point of `linear(...)`, an output with up to two input percentages
+fn linear_point_parser(input: &str) -> IResult<&str, Vec<(f32, Option<f32>)>> {
+    map(
+        pair(
+            number_argument_parser,
+            many_m_n(
+                0,
+                2,
+                delimited(
+                    multispace,
+                    terminated(number_parser, token("%")),
+                    multispace,
+                ),
+            ),
+        ),
+        |(output, inputs)| match inputs[..] {
+            [] => vec![(output, None)],
+            _ => inputs
+                .into_iter()
+                .map(|input| (output, Some(input / 100.0)))
+                .collect(),
+        },
+    )(input)
+}
+
+fn linear_stops_parser(input: &str) -> IResult<&str, Easing> {
+    map(
+        function_parser(
+            "linear",
+            verify(
+                map(
+                    separated_list1(comma_parser, linear_point_parser),
+                    |points| points.concat(),
+                ),
+                |points: &Vec<_>| points.len() > 1,
+            ),
+        ),
+        |points| Easing::LinearStops(resolve_linear_stops(points)),
+    )(input)
+}
+
+/// Parser for strings that represent an [`Easing`] function, like the value of `transition-timing-function`.
+///
+/// Supported Formats:
+/// * `linear`, `ease`, `ease-in`, `ease-out`, `ease-in-out`
+/// * `cubic-bezier(0.1, 0.7, 1.0, 0.1)` -> the x coordinates must be within `0..=1`
+/// * `steps(4)`, `steps(4, jump-end)` -> with `jump-start`, `jump-end`, `jump-none`, `jump-both`, `start` or `end`
+/// * `step-start`, `step-end` -> `steps(1, jump-start)` and `steps(1, jump-end)`
+/// * `linear(0, 0.25 75%, 1)` -> points with an output and up to two input percentages
+///
+/// See [mdn](https://developer.mozilla.org/en-US/docs/Web/CSS/easing-function) for more information.
+pub fn easing_parser(input: &str) -> IResult<&str, Easing> {
+    delimited(
+        multispace,
+        alt((
+            map(
+                function_parser(
+                    "cubic-bezier",
+                    tuple((
+                        bezier_x_parser,
+                        preceded(comma_parser, number_argument_parser),
+                        preceded(comma_parser, bezier_x_parser),
+                        preceded(comma_parser, number_argument_parser),
+                    )),
+                ),
+                |(x1, y1, x2, y2)| Easing::CubicBezier(x1, y1, x2, y2),
+            ),
+            steps_parser,
+            linear_stops_parser,
+            map(token("linear"), |_| Easing::Linear),
+            map(token("ease-in-out"), |_| Easing::EASE_IN_OUT),
+            map(token("ease-in"), |_| Easing::EASE_IN),
+            map(token("ease-out"), |_| Easing::EASE_OUT),
+            map(token("ease"), |_| Easing::EASE),
+            map(token("step-start"), |_| {
+                Easing::Steps(1, StepPosition::JumpStart)
+            }),
+            map(token("step-end"), |_| {
+                Easing::Steps(1, StepPosition::JumpEnd)
+            }),
+        )),
+        multispace,
+    )(input)
+}
+
+/// Wrapper for [`easing_parser`] that returns an optional [`Easing`]
+pub fn easing_string_parser(input: &str) -> Option<Easing> {
+    easing_parser(input).map(|(_, value)| value).ok()
+}
+
+/// Wrapper for [`easing_parser`] that returns a [`ParseError`] on failure
+///
+/// Unlike [`easing_string_parser`] the whole input must be consumed.
+pub fn easing_result_parser(input: &str) -> Result<Easing, ParseError> {
+    parse_all(input, easing_parser)
+}
+
+/// Formats an [`Easing`] as a string, the counterpart of [`easing_parser`]
+///
+/// The cubic bezier curves of the keywords are formatted as the keyword, like `ease-out`.
+pub fn easing_formatter(easing: &Easing) -> String {
+    match easing {
+        Easing::Linear => "linear".to_string(),
+        easing if *easing == Easing::EASE => "ease".to_string(),
+        easing if *easing == Easing::EASE_IN => "ease-in".to_string(),
+        easing if *easing == Easing::EASE_OUT => "ease-out".to_string(),
+        easing if *easing == Easing::EASE_IN_OUT => "ease-in-out".to_string(),
+        Easing::CubicBezier(x1, y1, x2, y2) => {
+            format!("cubic-bezier({}, {}, {}, {})", x1, y1, x2, y2)
+        }
+        Easing::Steps(count, position) => {
+            let position = match position {
+                StepPosition::JumpStart => "jump-start",
+                StepPosition::JumpEnd => "jump-end",
+                StepPosition::JumpNone => "jump-none",
+                StepPosition::JumpBoth => "jump-both",
+            };
+            format!("steps({}, {})", count, position)
+        }
+        Easing::LinearStops(stops) => format!(
+            "linear({})",
+            stops
+                .iter()
+                .map(|stop| format!("{} {}%", stop.output, stop.input * 100.0))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// Wrapper for [`easing_parser`] that implements a serde deserializer
+#[cfg(feature = "serde")]
+pub fn easing_serde_parser<'de, D>(deserializer: D) -> Result<Easing, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::Error;
+    let s: &str = serde::Deserialize::deserialize(deserializer)?;
+    easing_result_parser(s)
+        .map_err(|error| D::Error::custom(format!("invalid easing string: {}", error)))
+}
+
+/// Wrapper for [`easing_formatter`] that implements a serde serializer
+#[cfg(feature = "serde")]
+pub fn easing_serde_serializer<S>(easing: &Easing, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.serialize_str(&easing_formatter(easing))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    fn stops(points: &[(f32, f32)]) -> Easing {
+        Easing::LinearStops(
+            points
+                .iter()
+                .map(|&(output, input)| LinearStop { output, input })
+                .collect(),
+        )
+    }
+
+    #[test_case("linear", Easing::Linear ; "linear")]
+    #[test_case("ease", Easing::EASE ; "ease")]
+    #[test_case("ease-in", Easing::EASE_IN ; "ease in")]
+    #[test_case(" ease-out ", Easing::EASE_OUT ; "ease out")]
+    #[test_case("ease-in-out", Easing::EASE_IN_OUT ; "ease in out")]
+    #[test_case("cubic-bezier(0.1, 0.7, 1.0, 0.1)", Easing::CubicBezier(0.1, 0.7, 1.0, 0.1) ; "cubic bezier")]
+    #[test_case("cubic-bezier(0,-1,1,2)", Easing::CubicBezier(0.0, -1.0, 1.0, 2.0) ; "cubic bezier overshoot")]
+    #[test_case("steps(4, jump-end)", Easing::Steps(4, StepPosition::JumpEnd) ; "steps")]
+    #[test_case("steps(3)", Easing::Steps(3, StepPosition::JumpEnd) ; "steps default position")]
+    #[test_case("steps(2, start)", Easing::Steps(2, StepPosition::JumpStart) ; "steps start")]
+    #[test_case("step-start", Easing::Steps(1, StepPosition::JumpStart) ; "step start")]
+    #[test_case("step-end", Easing::Steps(1, StepPosition::JumpEnd) ; "step end")]
+    #[test_case("linear(0, 0.25 75%, 1)", stops(&[(0.0, 0.0), (0.25, 0.75), (1.0, 1.0)]) ; "linear stops")]
+    #[test_case("linear(0, 0.5, 0.75, 1)", stops(&[(0.0, 0.0), (0.5, 1.0 / 3.0), (0.75, 2.0 / 3.0), (1.0, 1.0)]) ; "linear stops spaced evenly")]
+    #[test_case("linear(0, 0.5 25% 75%, 1)", stops(&[(0.0, 0.0), (0.5, 0.25), (0.5, 0.75), (1.0, 1.0)]) ; "linear stops with two inputs")]
+    #[test_case("linear(0 50%, 1 20%)", stops(&[(0.0, 0.5), (1.0, 0.5)]) ; "linear stops decreasing input")]
+    fn test_easing_parser(string: &str, expected: Easing) {
+        assert_eq!(easing_result_parser(string), Ok(expected));
+    }
+
+    #[test_case("cubic-bezier(1.5, 0, 0, 1)" ; "bezier x out of range")]
+    #[test_case("cubic-bezier(0, 0, 1)" ; "bezier missing argument")]
+    #[test_case("steps(0)" ; "zero steps")]
+    #[test_case("steps(1, jump-none)" ; "single step without jumps")]
+    #[test_case("steps(2.5)" ; "fractional steps")]
+    #[test_case("linear(1)" ; "single linear stop")]
+    #[test_case("bounce" ; "unknown keyword")]
+    fn test_easing_parser_invalid(string: &str) {
+        assert!(easing_result_parser(string).is_err());
+    }
+
+    #[test_case(Easing::Linear, 0.3, 0.3 ; "linear")]
+    #[test_case(Easing::EASE_IN_OUT, 0.5, 0.5 ; "ease in out center")]
+    #[test_case(Easing::EASE, 0.0, 0.0 ; "ease start")]
+    #[test_case(Easing::EASE, 1.0, 1.0 ; "ease end")]
+    #[test_case(Easing::EASE, 0.25, 0.4094 ; "ease quarter")]
+    #[test_case(Easing::EASE_IN, 0.5, 0.3153 ; "ease in center")]
+    #[test_case(Easing::Steps(4, StepPosition::JumpEnd), 0.3, 0.25 ; "steps jump end")]
+    #[test_case(Easing::Steps(4, StepPosition::JumpStart), 0.3, 0.5 ; "steps jump start")]
+    #[test_case(Easing::Steps(3, StepPosition::JumpNone), 0.5, 0.5 ; "steps jump none")]
+    #[test_case(Easing::Steps(3, StepPosition::JumpBoth), 0.5, 0.5 ; "steps jump both")]
+    #[test_case(Easing::Steps(4, StepPosition::JumpEnd), 1.0, 1.0 ; "steps end")]
+    #[test_case(Easing::Steps(0, StepPosition::JumpEnd), 0.5, 0.5 ; "zero steps")]
+    #[test_case(Easing::Steps(1, StepPosition::JumpNone), 0.3, 0.3 ; "single step jump none")]
+    #[test_case(stops(&[(0.0, 0.0), (0.25, 0.75), (1.0, 1.0)]), 0.375, 0.125 ; "linear stops first segment")]
+    #[test_case(stops(&[(0.0, 0.0), (0.25, 0.75), (1.0, 1.0)]), 0.875, 0.625 ; "linear stops second segment")]
+    #[test_case(stops(&[(0.0, 0.5), (1.0, 0.5)]), 0.5, 1.0 ; "linear stops jump")]
+    #[test_case(Easing::LinearStops(vec![]), 0.3, 0.3 ; "no linear stops")]
+    #[test_case(stops(&[(0.7, 0.5)]), 0.3, 0.7 ; "single linear stop")]
+    fn test_easing_sample(easing: Easing, progress: f32, expected: f32) {
+        let value = easing.sample(progress);
+        assert!((value - expected).abs() < 1e-3, "{} != {}", value, expected);
+    }
+
+    #[test_case("ease-in" ; "keyword")]
+    #[test_case("cubic-bezier(0.1, 0.7, 1, 0.1)" ; "cubic bezier")]
+    #[test_case("steps(4, jump-none)" ; "steps")]
+    #[test_case("linear(0 0%, 0.25 75%, 1 100%)" ; "linear stops")]
+    fn test_easing_formatter(string: &str) {
+        let easing = easing_result_parser(string).unwrap();
+        assert_eq!(easing_formatter(&easing), string);
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests_serde {
+    use super::{Easing, StepPosition};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Deserialize, Serialize)]
+    pub struct Foo {
+        #[serde(
+            deserialize_with = "super::easing_serde_parser",
+            serialize_with = "super::easing_serde_serializer"
+        )]
+        pub easing: Easing,
+    }
+
+    #[test]
+    fn test_easing_serde_parser() {
+        let foo: Foo = serde_json::from_str(r#"{"easing": "steps(4, end)"}"#).unwrap();
+        assert_eq!(foo.easing, Easing::Steps(4, StepPosition::JumpEnd));
+        assert!(serde_json::from_str::<Foo>(r#"{"easing": "steps(0)"}"#).is_err());
+    }
+
+    #[test]
+    fn test_easing_serde_serializer() {
+        let foo = Foo {
+            easing: Easing::Steps(4, StepPosition::JumpEnd),
+        };
+        let json = serde_json::to_string(&foo).unwrap();
+        assert_eq!(json, r#"{"easing":"steps(4, jump-end)"}"#);
+    }
+}
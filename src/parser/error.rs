@@ -0,0 +1,225 @@
+use nom::{
+    error::{ContextError, ErrorKind, FromExternalError},
+    InputLength, Offset,
+};
+use std::fmt;
+
+/// Result type returned by the nom parsers of this crate.
+pub type IResult<I, O> = nom::IResult<I, O, InputError<I>>;
+
+/// Error type used by the nom parsers of this crate.
+///
+/// Keeps track of the remaining input at the point of failure and the tokens
+/// that would have been accepted there. When alternatives fail, the error that
+/// got furthest into the input wins, errors at the same position are merged.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputError<I> {
+    /// The remaining input at the point of failure.
+    pub input: I,
+    /// Tokens that would have been accepted at this position.
+    ///
+    /// Literal tokens are stored as is, like `px` or `(`, while descriptions
+    /// of a class of tokens are enclosed in angle brackets, like `<number>`.
+    pub expected: Vec<&'static str>,
+}
+
+impl<I> InputError<I> {
+    /// Creates an error at the given input position, expecting a single token.
+    pub fn expected(input: I, token: &'static str) -> Self {
+        Self {
+            input,
+            expected: vec![token],
+        }
+    }
+
+    fn add_expected(&mut self, token: &'static str) {
+        if !self.expected.contains(&token) {
+            self.expected.push(token);
+        }
+    }
+}
+
+impl<I: InputLength> nom::error::ParseError<I> for InputError<I> {
+    fn from_error_kind(input: I, _kind: ErrorKind) -> Self {
+        Self {
+            input,
+            expected: Vec::new(),
+        }
+    }
+
+    fn append(_input: I, _kind: ErrorKind, other: Self) -> Self {
+        other
+    }
+
+    fn or(mut self, other: Self) -> Self {
+        if self.input.input_len() < other.input.input_len() {
+            self
+        } else if other.input.input_len() < self.input.input_len() {
+            other
+        } else {
+            for token in other.expected {
+                self.add_expected(token);
+            }
+            self
+        }
+    }
+}
+
+impl<I: InputLength> ContextError<I> for InputError<I> {
+    /// The context is treated as the name of the expected token, if the
+    /// error occurred at the beginning of the context.
+    fn add_context(input: I, ctx: &'static str, mut other: Self) -> Self {
+        if input.input_len() == other.input.input_len() {
+            other.expected.clear();
+            other.add_expected(ctx);
+        }
+        other
+    }
+}
+
+impl<I: InputLength, E> FromExternalError<I, E> for InputError<I> {
+    fn from_external_error(input: I, kind: ErrorKind, _e: E) -> Self {
+        <Self as nom::error::ParseError<I>>::from_error_kind(input, kind)
+    }
+}
+
+/// Error returned by the `*_result_parser` functions.
+///
+/// Contains the byte offset into the input string where parsing failed,
+/// the tokens that would have been accepted there and the offending fragment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Byte offset into the input string.
+    pub offset: usize,
+    /// Tokens that would have been accepted at the offset, for example `px` or `%`.
+    pub expected: Vec<&'static str>,
+    /// The fragment of the input found at the offset, empty at the end of input.
+    pub fragment: String,
+}
+
+impl ParseError {
+    /// Creates a parse error for the `input` from the error of a nom parser.
+    pub fn new(input: &str, error: nom::Err<InputError<&str>>) -> Self {
+        match error {
+            nom::Err::Error(error) | nom::Err::Failure(error) => {
+                Self::at(input, error.input, error.expected)
+            }
+            nom::Err::Incomplete(_) => Self::at(input, &input[input.len()..], Vec::new()),
+        }
+    }
+
+    /// Creates a parse error for the `input` at the position of `remaining`.
+    fn at(input: &str, remaining: &str, expected: Vec<&'static str>) -> Self {
+        let offset = input.offset(remaining).min(input.len());
+        Self {
+            offset,
+            expected,
+            fragment: fragment(&input[offset..]).to_string(),
+        }
+    }
+
+    /// Describes the expected tokens, like `expected one of: px, %`.
+    pub(crate) fn expected_message(&self) -> Option<String> {
+        match self.expected.as_slice() {
+            [] => None,
+            [token] => Some(format!("expected {}", token)),
+            tokens => Some(format!("expected one of: {}", tokens.join(", "))),
+        }
+    }
+}
+
+/// Returns the token at the start of the input, up to the next whitespace or delimiter.
+fn fragment(input: &str) -> &str {
+    let end = input
+        .char_indices()
+        .skip(1)
+        .find(|(_, c)| {
+            c.is_whitespace() || matches!(c, ',' | '(' | ')' | '/' | ':' | ';' | '"' | '\'')
+        })
+        .map(|(index, _)| index)
+        .unwrap_or(input.len());
+    &input[..end]
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.fragment.is_empty() {
+            write!(f, "unexpected end of input at offset {}", self.offset)?;
+        } else {
+            write!(
+                f,
+                "unexpected \"{}\" at offset {}",
+                self.fragment, self.offset
+            )?;
+        }
+        match self.expected_message() {
+            Some(message) => write!(f, ", {}", message),
+            None => Ok(()),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Runs the parser on the whole input, which must be consumed entirely
+/// except for surrounding whitespace.
+pub(crate) fn parse_all<'a, O>(
+    input: &'a str,
+    mut parser: impl FnMut(&'a str) -> IResult<&'a str, O>,
+) -> Result<O, ParseError> {
+    match parser(input) {
+        Ok((remaining, value)) => {
+            let remaining = remaining.trim_start();
+            if remaining.is_empty() {
+                Ok(value)
+            } else {
+                Err(ParseError::at(input, remaining, vec!["<end of input>"]))
+            }
+        }
+        Err(error) => Err(ParseError::new(input, error)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{color_result_parser, val_result_parser};
+
+    #[test]
+    fn test_parse_error_expected_units() {
+        let error = val_result_parser("10pc").unwrap_err();
+        assert_eq!(error.offset, 2);
+        assert_eq!(error.expected, vec!["px", "%", "vw", "vh", "vmin", "vmax"]);
+        assert_eq!(error.fragment, "pc");
+        assert_eq!(
+            error.to_string(),
+            "unexpected \"pc\" at offset 2, expected one of: px, %, vw, vh, vmin, vmax"
+        );
+    }
+
+    #[test]
+    fn test_parse_error_furthest_alternative() {
+        let error = color_result_parser("rgb(255, 0 0)").unwrap_err();
+        assert_eq!(error.offset, 11);
+        assert_eq!(error.expected, vec![","]);
+        assert_eq!(error.fragment, "0");
+    }
+
+    #[test]
+    fn test_parse_error_trailing_input() {
+        let error = val_result_parser("10px 20px").unwrap_err();
+        assert_eq!(error.offset, 5);
+        assert_eq!(error.expected, vec!["<end of input>"]);
+        assert_eq!(error.fragment, "20px");
+    }
+
+    #[test]
+    fn test_parse_error_end_of_input() {
+        let error = color_result_parser("rgb(255, 0, 0").unwrap_err();
+        assert_eq!(error.offset, 13);
+        assert_eq!(error.fragment, "");
+        assert_eq!(
+            error.to_string(),
+            "unexpected end of input at offset 13, expected )"
+        );
+    }
+}
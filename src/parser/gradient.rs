@@ -0,0 +1,574 @@
+use std::f32::consts::{PI, TAU};
+
+use bevy::{render::color::Color, ui::Val};
+use nom::{
+    branch::alt,
+    character::complete::{multispace0 as multispace, multispace1},
+    combinator::{map, opt},
+    multi::many_m_n,
+    sequence::{delimited, pair, preceded, terminated, tuple},
+};
+
+use super::angle::angle_parser;
+use super::color_mix::interpolation_method_parser;
+use super::color_parser;
+use super::color_space::{ColorSpace, HueInterpolation};
+use super::common::{comma_parser, function_parser, number_parser, token};
+use super::error::{parse_all, IResult, InputError, ParseError};
+use super::position::{position_parser, Position};
+use super::val::{val_length_or_zero_parser, val_length_parser};
+
+/// Direction of a linear gradient
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LinearDirection {
+    /// Angle in radians, `0` points up and positive angles turn clockwise
+    Angle(f32),
+    /// Towards a corner, like `to top right`, the angle depends on the aspect ratio of the box
+    Corner { right: bool, bottom: bool },
+}
+
+/// Shape of a radial gradient
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RadialShape {
+    Circle,
+    Ellipse,
+}
+
+/// Size of the ending shape of a radial gradient
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RadialSize {
+    ClosestSide,
+    FarthestSide,
+    ClosestCorner,
+    FarthestCorner,
+    /// Horizontal and vertical radius, both are the same for circles
+    Explicit(Val, Val),
+}
+
+/// Geometry of a gradient
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GradientKind {
+    Linear(LinearDirection),
+    Radial {
+        shape: RadialShape,
+        size: RadialSize,
+        center: Position,
+    },
+    Conic {
+        /// Angle of the start of the gradient in radians, clockwise from the top
+        from: f32,
+        center: Position,
+    },
+}
+
+/// A color stop of a gradient
+///
+/// The position is `None` if the stop is placed halfway between its neighbours,
+/// positions of conic gradients are percentages of a full turn.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColorStop {
+    pub color: Color,
+    pub position: Option<Val>,
+    /// Position of the transition hint to the next stop, where the colors are mixed equally
+    pub hint: Option<Val>,
+}
+
+/// A css gradient, like `linear-gradient(45deg, red, blue)`
+///
+/// Use [`Gradient::rasterize`] to render it into an image.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gradient {
+    pub kind: GradientKind,
+    pub stops: Vec<ColorStop>,
+    pub repeating: bool,
+    pub color_space: ColorSpace,
+    pub hue_interpolation: HueInterpolation,
+}
+
+/// Color space and hue interpolation method that the colors of a gradient are interpolated with
+type InterpolationMethod = (ColorSpace, HueInterpolation);
+
+/// Parses a side or corner of a linear gradient, like `right` or `top left`
+fn side_or_corner_parser(input: &str) -> IResult<&str, LinearDirection> {
+    let side = delimited(
+        multispace,
+        alt((
+            map(token("top"), |_| (0, 1)),
+            map(token("right"), |_| (1, 0)),
+            map(token("bottom"), |_| (0, -1)),
+            map(token("left"), |_| (-1, 0)),
+        )),
+        multispace,
+    );
+    let (rest, sides) = many_m_n(1, 2, side)(input)?;
+    let direction = match sides[..] {
+        [(x, y)] => Some(LinearDirection::Angle(
+            f32::atan2(x as f32, y as f32).rem_euclid(TAU),
+        )),
+        [(x, 0), (0, y)] | [(0, y), (x, 0)] => Some(LinearDirection::Corner {
+            right: x > 0,
+            bottom: y < 0,
+        }),
+        _ => None,
+    };
+    direction
+        .map(|direction| (rest, direction))
+        .ok_or_else(|| nom::Err::Error(InputError::expected(input, "<side or corner>")))
+}
+
+/// Parses the direction of a linear gradient, like `45deg` or `to right`
+fn linear_direction_parser(input: &str) -> IResult<&str, LinearDirection> {
+    alt((
+        map(angle_parser, LinearDirection::Angle),
+        preceded(
+            tuple((multispace, token("to"), multispace1)),
+            side_or_corner_parser,
+        ),
+    ))(input)
+}
+
+/// Parses the shape of a radial gradient
+fn radial_shape_parser(input: &str) -> IResult<&str, RadialShape> {
+    delimited(
+        multispace,
+        alt((
+            map(token("circle"), |_| RadialShape::Circle),
+            map(token("ellipse"), |_| RadialShape::Ellipse),
+        )),
+        multispace,
+    )(input)
+}
+
+/// Parses the size of a radial gradient, with the shape implied by explicit radii
+fn radial_size_parser(input: &str) -> IResult<&str, (RadialSize, Option<RadialShape>)> {
+    let radius = delimited(multispace, val_length_parser, multispace);
+    alt((
+        map(
+            delimited(
+                multispace,
+                alt((
+                    map(token("closest-side"), |_| RadialSize::ClosestSide),
+                    map(token("farthest-side"), |_| RadialSize::FarthestSide),
+                    map(token("closest-corner"), |_| RadialSize::ClosestCorner),
+                    map(token("farthest-corner"), |_| RadialSize::FarthestCorner),
+                )),
+                multispace,
+            ),
+            |size| (size, None),
+        ),
+        map(many_m_n(1, 2, radius), |radii| match radii[..] {
+            [radius] => (
+                RadialSize::Explicit(radius, radius),
+                Some(RadialShape::Circle),
+            ),
+            [x, y, ..] => (RadialSize::Explicit(x, y), Some(RadialShape::Ellipse)),
+            [] => unreachable!("at least one radius is parsed"),
+        }),
+    ))(input)
+}
+
+/// Parses the shape, size and center of a radial gradient, like `circle closest-side at 30% 30%`
+fn radial_geometry_parser(input: &str) -> IResult<&str, GradientKind> {
+    let (rest, shape) = opt(radial_shape_parser)(input)?;
+    let (rest, size) = opt(radial_size_parser)(rest)?;
+    let (rest, shape) = match shape {
+        Some(shape) => (rest, Some(shape)),
+        None => opt(radial_shape_parser)(rest)?,
+    };
+    let implied_shape = size.and_then(|(_, shape)| shape);
+    if shape.is_some() && implied_shape.is_some() && shape != implied_shape {
+        return Err(nom::Err::Error(InputError::expected(
+            input,
+            "<radial size of the shape>",
+        )));
+    }
+    let (rest, center) = opt(center_parser)(rest)?;
+    Ok((
+        rest,
+        GradientKind::Radial {
+            shape: shape.or(implied_shape).unwrap_or(RadialShape::Ellipse),
+            size: size.map_or(RadialSize::FarthestCorner, |(size, _)| size),
+            center: center.unwrap_or_default(),
+        },
+    ))
+}
+
+/// Parses the start angle and center of a conic gradient, like `from 90deg at center`
+fn conic_geometry_parser(input: &str) -> IResult<&str, GradientKind> {
+    map(
+        pair(
+            opt(preceded(pair(multispace, token("from")), angle_parser)),
+            opt(center_parser),
+        ),
+        |(from, center)| GradientKind::Conic {
+            from: from.unwrap_or(0.0),
+            center: center.unwrap_or_default(),
+        },
+    )(input)
+}
+
+/// Parses the center of a gradient, like `at 30% 30%`
+fn center_parser(input: &str) -> IResult<&str, Position> {
+    preceded(pair(multispace, token("at")), position_parser)(input)
+}
+
+/// Parses a color interpolation method with surrounding whitespace, like `in oklch longer hue`
+fn method_parser(input: &str) -> IResult<&str, InterpolationMethod> {
+    delimited(multispace, interpolation_method_parser, multispace)(input)
+}
+
+/// Parses the position of a color stop of a linear or radial gradient, like `10px`, `50%` or `0`
+fn length_stop_position_parser(input: &str) -> IResult<&str, Val> {
+    delimited(multispace, val_length_or_zero_parser, multispace)(input)
+}
+
+/// Parses the position of a color stop of a conic gradient, like `90deg` or `25%`
+fn angle_stop_position_parser(input: &str) -> IResult<&str, Val> {
+    delimited(
+        multispace,
+        alt((
+            map(terminated(number_parser, token("%")), Val::Percent),
+            map(angle_parser, |radians| Val::Percent(radians / TAU * 100.0)),
+        )),
+        multispace,
+    )(input)
+}
+
+/// Parses a comma separated list of color stops and transition hints,
+/// like `red, blue 60%, 80%, transparent`
+fn color_stop_list_parser(
+    input: &str,
+    position: fn(&str) -> IResult<&str, Val>,
+) -> IResult<&str, Vec<ColorStop>> {
+    let mut stop = map(
+        tuple((color_parser, opt(pair(position, opt(position))))),
+        |(color, positions)| match positions {
+            Some((first, second)) => (color, Some(first), second),
+            None => (color, None, None),
+        },
+    );
+    let mut stops = Vec::new();
+    let push = |stops: &mut Vec<ColorStop>, (color, first, second): (Color, _, Option<Val>)| {
+        stops.push(ColorStop {
+            color,
+            position: first,
+            hint: None,
+        });
+        if second.is_some() {
+            stops.push(ColorStop {
+                color,
+                position: second,
+                hint: None,
+            });
+        }
+    };
+    let (mut input, first) = stop(input)?;
+    push(&mut stops, first);
+    let mut items = 1;
+    while let Ok((rest, _)) = comma_parser(input) {
+        let (rest, hint) = opt(terminated(position, comma_parser))(rest)?;
+        let (rest, next) = stop(rest)?;
+        if let Some(last) = stops.last_mut() {
+            last.hint = hint;
+        }
+        push(&mut stops, next);
+        items += 1;
+        input = rest;
+    }
+    if items < 2 {
+        return Err(nom::Err::Error(InputError::expected(input, ",")));
+    }
+    Ok((input, stops))
+}
+
+/// Parses the arguments of a gradient function, the geometry and interpolation
+/// method followed by the color stops
+fn gradient_arguments_parser<'a>(
+    input: &'a str,
+    mut geometry: impl FnMut(&'a str) -> IResult<&'a str, GradientKind>,
+    default_kind: GradientKind,
+    position: fn(&str) -> IResult<&str, Val>,
+) -> IResult<&'a str, (GradientKind, Option<InterpolationMethod>, Vec<ColorStop>)> {
+    let mut preamble = |input: &'a str| {
+        let (rest, method) = opt(method_parser)(input)?;
+        let (rest, kind) = geometry(rest)?;
+        let (rest, method) = match method {
+            Some(method) => (rest, Some(method)),
+            None => opt(method_parser)(rest)?,
+        };
+        Ok((rest, (kind, method)))
+    };
+    let (input, preamble) = opt(terminated(&mut preamble, comma_parser))(input)?;
+    let (kind, method) = preamble.unwrap_or((default_kind, None));
+    let (input, stops) = color_stop_list_parser(input, position)?;
+    Ok((input, (kind, method, stops)))
+}
+
+/// Parser for strings that represent a [`Gradient`].
+///
+/// The syntax follows CSS, see [mdn](https://developer.mozilla.org/en-US/docs/Web/CSS/gradient):
+///
+/// * `linear-gradient(45deg, red, blue 60%, transparent)`, `linear-gradient(to top right, red, blue)`
+/// * `radial-gradient(circle at 30% 30%, white, black)`, `radial-gradient(closest-side, red, blue)`
+/// * `conic-gradient(from 90deg at center, red, yellow 90deg, red)`
+/// * `repeating-linear-gradient(red, blue 20px)` and the other repeating gradients
+///
+/// Color stops can have one or two positions and transition hints can be placed between them,
+/// like `red, 30%, blue`, a zero length position may omit the unit. Colors are interpolated in sRGB, unless an interpolation method is
+/// given, like `linear-gradient(in oklch longer hue, red, blue)`.
+pub fn gradient_parser(input: &str) -> IResult<&str, Gradient> {
+    let linear = |input| {
+        gradient_arguments_parser(
+            input,
+            map(opt(linear_direction_parser), |direction| {
+                GradientKind::Linear(direction.unwrap_or(LinearDirection::Angle(PI)))
+            }),
+            GradientKind::Linear(LinearDirection::Angle(PI)),
+            length_stop_position_parser,
+        )
+    };
+    let radial = |input| {
+        gradient_arguments_parser(
+            input,
+            radial_geometry_parser,
+            GradientKind::Radial {
+                shape: RadialShape::Ellipse,
+                size: RadialSize::FarthestCorner,
+                center: Position::default(),
+            },
+            length_stop_position_parser,
+        )
+    };
+    let conic = |input| {
+        gradient_arguments_parser(
+            input,
+            conic_geometry_parser,
+            GradientKind::Conic {
+                from: 0.0,
+                center: Position::default(),
+            },
+            angle_stop_position_parser,
+        )
+    };
+    map(
+        delimited(
+            multispace,
+            alt((
+                map(function_parser("linear-gradient", linear), |args| {
+                    (false, args)
+                }),
+                map(function_parser("radial-gradient", radial), |args| {
+                    (false, args)
+                }),
+                map(function_parser("conic-gradient", conic), |args| {
+                    (false, args)
+                }),
+                map(
+                    function_parser("repeating-linear-gradient", linear),
+                    |args| (true, args),
+                ),
+                map(
+                    function_parser("repeating-radial-gradient", radial),
+                    |args| (true, args),
+                ),
+                map(function_parser("repeating-conic-gradient", conic), |args| {
+                    (true, args)
+                }),
+            )),
+            multispace,
+        ),
+        |(repeating, (kind, method, stops))| {
+            let (color_space, hue_interpolation) =
+                method.unwrap_or((ColorSpace::Srgb, HueInterpolation::Shorter));
+            Gradient {
+                kind,
+                stops,
+                repeating,
+                color_space,
+                hue_interpolation,
+            }
+        },
+    )(input)
+}
+
+/// Wrapper for [`gradient_parser`] that returns an optional [`Gradient`]
+pub fn gradient_string_parser(input: &str) -> Option<Gradient> {
+    gradient_parser(input).map(|(_, value)| value).ok()
+}
+
+/// Wrapper for [`gradient_parser`] that returns a [`ParseError`] on failure
+///
+/// Unlike [`gradient_string_parser`] the whole input must be consumed.
+pub fn gradient_result_parser(input: &str) -> Result<Gradient, ParseError> {
+    parse_all(input, gradient_parser)
+}
+
+/// Wrapper for [`gradient_parser`] that implements a serde deserializer
+#[cfg(feature = "serde")]
+pub fn gradient_serde_parser<'de, D>(deserializer: D) -> Result<Gradient, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::Error;
+    let s: &str = serde::Deserialize::deserialize(deserializer)?;
+    gradient_result_parser(s)
+        .map_err(|error| D::Error::custom(format!("invalid gradient string: {}", error)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    fn stop(color: Color, position: Option<Val>) -> ColorStop {
+        ColorStop {
+            color,
+            position,
+            hint: None,
+        }
+    }
+
+    #[test]
+    fn test_gradient_parser_linear() {
+        let gradient =
+            gradient_result_parser("linear-gradient(45deg, red, blue 60%, transparent)").unwrap();
+        assert_eq!(
+            gradient,
+            Gradient {
+                kind: GradientKind::Linear(LinearDirection::Angle(45_f32.to_radians())),
+                stops: vec![
+                    stop(Color::RED, None),
+                    stop(Color::BLUE, Some(Val::Percent(60.0))),
+                    stop(Color::NONE, None),
+                ],
+                repeating: false,
+                color_space: ColorSpace::Srgb,
+                hue_interpolation: HueInterpolation::Shorter,
+            }
+        );
+    }
+
+    #[test_case("linear-gradient(red, blue)", LinearDirection::Angle(PI) ; "default")]
+    #[test_case("linear-gradient(to top, red, blue)", LinearDirection::Angle(0.0) ; "to top")]
+    #[test_case("linear-gradient(to left, red, blue)", LinearDirection::Angle(1.5 * PI) ; "to left")]
+    #[test_case("linear-gradient(0.25turn, red, blue)", LinearDirection::Angle(0.5 * PI) ; "turn")]
+    #[test_case("linear-gradient(to top right, red, blue)", LinearDirection::Corner { right: true, bottom: false } ; "to top right")]
+    #[test_case("linear-gradient(to left bottom, red, blue)", LinearDirection::Corner { right: false, bottom: true } ; "to left bottom")]
+    fn test_gradient_parser_linear_direction(string: &str, expected: LinearDirection) {
+        let gradient = gradient_result_parser(string).unwrap();
+        assert_eq!(gradient.kind, GradientKind::Linear(expected));
+    }
+
+    #[test_case("radial-gradient(red, blue)", RadialShape::Ellipse, RadialSize::FarthestCorner, (50.0, 50.0) ; "default")]
+    #[test_case("radial-gradient(circle at 30% 30%, red, blue)", RadialShape::Circle, RadialSize::FarthestCorner, (30.0, 30.0) ; "circle at")]
+    #[test_case("radial-gradient(closest-side circle, red, blue)", RadialShape::Circle, RadialSize::ClosestSide, (50.0, 50.0) ; "size before shape")]
+    #[test_case("radial-gradient(10px, red, blue)", RadialShape::Circle, RadialSize::Explicit(Val::Px(10.0), Val::Px(10.0)), (50.0, 50.0) ; "circle radius")]
+    #[test_case("radial-gradient(ellipse 10px 20% at left, red, blue)", RadialShape::Ellipse, RadialSize::Explicit(Val::Px(10.0), Val::Percent(20.0)), (0.0, 50.0) ; "ellipse radii")]
+    fn test_gradient_parser_radial(
+        string: &str,
+        shape: RadialShape,
+        size: RadialSize,
+        (x, y): (f32, f32),
+    ) {
+        let gradient = gradient_result_parser(string).unwrap();
+        assert_eq!(
+            gradient.kind,
+            GradientKind::Radial {
+                shape,
+                size,
+                center: Position {
+                    x: Val::Percent(x),
+                    y: Val::Percent(y),
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn test_gradient_parser_conic() {
+        let gradient = gradient_result_parser(
+            "conic-gradient(from 90deg at center, red, yellow 90deg, red 50%)",
+        )
+        .unwrap();
+        assert_eq!(
+            gradient.kind,
+            GradientKind::Conic {
+                from: 90_f32.to_radians(),
+                center: Position::default(),
+            }
+        );
+        assert_eq!(gradient.stops[1].position, Some(Val::Percent(25.0)));
+        assert_eq!(gradient.stops[2].position, Some(Val::Percent(50.0)));
+    }
+
+    #[test]
+    fn test_gradient_parser_stops() {
+        let gradient =
+            gradient_result_parser("repeating-linear-gradient(red 10% 20%, 30%, blue 40px)")
+                .unwrap();
+        assert!(gradient.repeating);
+        assert_eq!(
+            gradient.stops,
+            vec![
+                stop(Color::RED, Some(Val::Percent(10.0))),
+                ColorStop {
+                    color: Color::RED,
+                    position: Some(Val::Percent(20.0)),
+                    hint: Some(Val::Percent(30.0)),
+                },
+                stop(Color::BLUE, Some(Val::Px(40.0))),
+            ]
+        );
+
+        let gradient = gradient_result_parser("linear-gradient(red 0, blue)").unwrap();
+        assert_eq!(gradient.stops[0], stop(Color::RED, Some(Val::Px(0.0))));
+    }
+
+    #[test_case("linear-gradient(in oklch longer hue, red, blue)", ColorSpace::Oklch, HueInterpolation::Longer ; "method only")]
+    #[test_case("linear-gradient(to right in oklab, red, blue)", ColorSpace::Oklab, HueInterpolation::Shorter ; "method after direction")]
+    #[test_case("radial-gradient(in hsl circle, red, blue)", ColorSpace::Hsl, HueInterpolation::Shorter ; "method before shape")]
+    #[test_case("conic-gradient(from 10deg in srgb-linear, red, blue)", ColorSpace::SrgbLinear, HueInterpolation::Shorter ; "conic")]
+    fn test_gradient_parser_interpolation(
+        string: &str,
+        color_space: ColorSpace,
+        hue_interpolation: HueInterpolation,
+    ) {
+        let gradient = gradient_result_parser(string).unwrap();
+        assert_eq!(gradient.color_space, color_space);
+        assert_eq!(gradient.hue_interpolation, hue_interpolation);
+    }
+
+    #[test_case("linear-gradient(red)" ; "single stop")]
+    #[test_case("linear-gradient(red, 50%)" ; "hint at the end")]
+    #[test_case("linear-gradient(red, 10%, 20%, blue)" ; "two hints")]
+    #[test_case("linear-gradient(to top bottom, red, blue)" ; "opposite sides")]
+    #[test_case("radial-gradient(circle 10px 20px, red, blue)" ; "circle with two radii")]
+    #[test_case("conic-gradient(red 10px, blue)" ; "conic length")]
+    #[test_case("stripes(red, blue)" ; "unknown function")]
+    fn test_gradient_parser_error(string: &str) {
+        assert!(gradient_result_parser(string).is_err());
+        assert_eq!(gradient_string_parser(string), None);
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests_serde {
+    use super::{Gradient, GradientKind, LinearDirection};
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    pub struct Foo {
+        #[serde(deserialize_with = "super::gradient_serde_parser")]
+        pub background: Gradient,
+    }
+
+    #[test]
+    fn test_gradient_serde_parser() {
+        let foo: Foo =
+            serde_json::from_str(r#"{"background": "linear-gradient(0rad, red, blue)"}"#).unwrap();
+        assert_eq!(
+            foo.background.kind,
+            GradientKind::Linear(LinearDirection::Angle(0.0))
+        );
+    }
+}
@@ -0,0 +1,303 @@
+use std::f32::consts::{SQRT_2, TAU};
+
+use bevy::{
+    math::Vec2,
+    render::{
+        color::Color,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+        texture::Image,
+    },
+    ui::Val,
+};
+
+use super::color_mix::interpolate_colors;
+use super::gradient::{Gradient, GradientKind, LinearDirection, RadialShape, RadialSize};
+use super::position::{resolve_length, resolve_position};
+
+/// The gradient line of a gradient resolved for a box size
+enum GradientLine {
+    Linear {
+        center: Vec2,
+        direction: Vec2,
+        length: f32,
+    },
+    Radial {
+        center: Vec2,
+        radii: Vec2,
+    },
+    Conic {
+        center: Vec2,
+        from: f32,
+    },
+}
+
+impl GradientLine {
+    fn new(kind: &GradientKind, size: Vec2) -> Self {
+        match *kind {
+            GradientKind::Linear(direction) => {
+                let angle = match direction {
+                    LinearDirection::Angle(angle) => angle,
+                    // the line perpendicular to the gradient line meets the two neighbouring corners
+                    LinearDirection::Corner { right, bottom } => {
+                        let angle = size.y.atan2(size.x);
+                        match (right, bottom) {
+                            (true, false) => angle,
+                            (true, true) => TAU / 2.0 - angle,
+                            (false, true) => TAU / 2.0 + angle,
+                            (false, false) => TAU - angle,
+                        }
+                    }
+                };
+                let direction = Vec2::new(angle.sin(), -angle.cos());
+                GradientLine::Linear {
+                    center: size / 2.0,
+                    direction,
+                    length: (size.x * direction.x).abs() + (size.y * direction.y).abs(),
+                }
+            }
+            GradientKind::Radial {
+                shape,
+                size: radial_size,
+                center,
+            } => {
+                let center = resolve_position(center, size);
+                let near = center.min(size - center).abs();
+                let far = center.max(size - center).abs();
+                let radii = match (shape, radial_size) {
+                    (RadialShape::Circle, RadialSize::ClosestSide) => {
+                        Vec2::splat(near.min_element())
+                    }
+                    (RadialShape::Circle, RadialSize::FarthestSide) => {
+                        Vec2::splat(far.max_element())
+                    }
+                    (RadialShape::Circle, RadialSize::ClosestCorner) => Vec2::splat(near.length()),
+                    (RadialShape::Circle, RadialSize::FarthestCorner) => Vec2::splat(far.length()),
+                    (RadialShape::Ellipse, RadialSize::ClosestSide) => near,
+                    (RadialShape::Ellipse, RadialSize::FarthestSide) => far,
+                    // the ellipse keeps the aspect ratio of the side ellipse and meets the corner
+                    (RadialShape::Ellipse, RadialSize::ClosestCorner) => near * SQRT_2,
+                    (RadialShape::Ellipse, RadialSize::FarthestCorner) => far * SQRT_2,
+                    (_, RadialSize::Explicit(x, y)) => Vec2::new(
+                        resolve_length(x, size.x).unwrap_or(0.0),
+                        resolve_length(y, size.y).unwrap_or(0.0),
+                    ),
+                };
+                GradientLine::Radial {
+                    center,
+                    radii: radii.max(Vec2::splat(f32::EPSILON)),
+                }
+            }
+            GradientKind::Conic { from, center } => GradientLine::Conic {
+                center: resolve_position(center, size),
+                from,
+            },
+        }
+    }
+
+    /// Length of the gradient line in pixels, that pixel positions of color stops refer to
+    fn length(&self) -> f32 {
+        match self {
+            GradientLine::Linear { length, .. } => *length,
+            GradientLine::Radial { radii, .. } => radii.x,
+            GradientLine::Conic { .. } => 1.0,
+        }
+    }
+
+    /// Returns the position of the point on the gradient line, `0.0` at its start and `1.0` at its end
+    fn offset(&self, point: Vec2) -> f32 {
+        match *self {
+            GradientLine::Linear {
+                center,
+                direction,
+                length,
+            } => (point - center).dot(direction) / length.max(f32::EPSILON) + 0.5,
+            GradientLine::Radial { center, radii } => ((point - center) / radii).length(),
+            GradientLine::Conic { center, from } => {
+                let delta = point - center;
+                (delta.x.atan2(-delta.y) - from).rem_euclid(TAU) / TAU
+            }
+        }
+    }
+}
+
+/// A color stop with its position and hint resolved to offsets on the gradient line
+struct ResolvedStop {
+    color: Color,
+    offset: f32,
+    hint: Option<f32>,
+}
+
+impl Gradient {
+    /// Resolves the positions of the color stops to offsets on a gradient line with the length
+    ///
+    /// Follows the css rules: the first and last stops default to the start and end,
+    /// positions smaller than a previous one are raised to it and stops without
+    /// position are spread evenly between their neighbours.
+    fn resolve_stops(&self, length: f32) -> Vec<ResolvedStop> {
+        let resolve =
+            |val: Val| resolve_length(val, length).map(|px| px / length.max(f32::EPSILON));
+        let last = self.stops.len().saturating_sub(1);
+        let mut offsets: Vec<Option<f32>> = self
+            .stops
+            .iter()
+            .enumerate()
+            .map(|(index, stop)| match stop.position.and_then(resolve) {
+                None if index == 0 => Some(0.0),
+                None if index == last => Some(1.0),
+                offset => offset,
+            })
+            .collect();
+        let mut max = f32::NEG_INFINITY;
+        for offset in offsets.iter_mut().flatten() {
+            max = max.max(*offset);
+            *offset = max;
+        }
+        let mut previous = 0;
+        for index in 1..offsets.len() {
+            if let (Some(start), Some(end)) = (offsets[previous], offsets[index]) {
+                let steps = (index - previous) as f32;
+                for (step, offset) in offsets[previous + 1..index].iter_mut().enumerate() {
+                    *offset = Some(start + (end - start) * (step + 1) as f32 / steps);
+                }
+                previous = index;
+            }
+        }
+        self.stops
+            .iter()
+            .zip(offsets)
+            .map(|(stop, offset)| ResolvedStop {
+                color: stop.color,
+                offset: offset.unwrap_or(0.0),
+                hint: stop.hint.and_then(resolve),
+            })
+            .collect()
+    }
+
+    /// Returns the color at an offset of the gradient line
+    fn color_at_offset(&self, stops: &[ResolvedStop], mut offset: f32) -> Color {
+        let (Some(first), Some(last)) = (stops.first(), stops.last()) else {
+            return Color::NONE;
+        };
+        let period = last.offset - first.offset;
+        if self.repeating && period > 0.0 {
+            offset = first.offset + (offset - first.offset).rem_euclid(period);
+        }
+        if offset <= first.offset {
+            return first.color;
+        }
+        let Some(index) = stops.iter().rposition(|stop| stop.offset <= offset) else {
+            return first.color;
+        };
+        let (start, Some(end)) = (&stops[index], stops.get(index + 1)) else {
+            return last.color;
+        };
+        let range = end.offset - start.offset;
+        let mut t = (offset - start.offset) / range;
+        if let Some(hint) = start.hint {
+            let hint = ((hint - start.offset) / range).clamp(0.0, 1.0);
+            t = match hint {
+                hint if hint <= 0.0 => 1.0,
+                hint if hint >= 1.0 => 0.0,
+                hint => t.powf(0.5_f32.ln() / hint.ln()),
+            };
+        }
+        interpolate_colors(
+            (self.color_space, self.hue_interpolation),
+            start.color,
+            end.color,
+            t,
+        )
+    }
+
+    /// Returns the color of the gradient at a point of a box with the size, in pixels
+    pub fn color_at(&self, point: Vec2, size: Vec2) -> Color {
+        let line = GradientLine::new(&self.kind, size);
+        let stops = self.resolve_stops(line.length());
+        self.color_at_offset(&stops, line.offset(point))
+    }
+
+    /// Renders the gradient into an sRGB image with the size, for example to use it as
+    /// background of a [`bevy::ui::node_bundles::ImageBundle`]
+    ///
+    /// Pixels are sampled at their centers, without anti-aliasing of hard color stops.
+    pub fn rasterize(&self, width: u32, height: u32) -> Image {
+        let size = Vec2::new(width as f32, height as f32);
+        let line = GradientLine::new(&self.kind, size);
+        let stops = self.resolve_stops(line.length());
+        let mut data = Vec::with_capacity(width as usize * height as usize * 4);
+        for y in 0..height {
+            for x in 0..width {
+                let point = Vec2::new(x as f32 + 0.5, y as f32 + 0.5);
+                let color = self.color_at_offset(&stops, line.offset(point));
+                let rgba = color.as_rgba_f32();
+                data.extend(rgba.map(|channel| (channel.clamp(0.0, 1.0) * 255.0).round() as u8));
+            }
+        }
+        Image::new(
+            Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8UnormSrgb,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::gradient_string_parser;
+    use bevy::{
+        math::{UVec2, Vec2},
+        render::color::Color,
+    };
+    use test_case::test_case;
+
+    fn assert_color_close(actual: Color, expected: Color) {
+        let (actual, expected) = (actual.as_rgba_f32(), expected.as_rgba_f32());
+        for (a, e) in actual.iter().zip(expected.iter()) {
+            assert!((a - e).abs() < 0.01, "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    const PURPLE: Color = Color::rgb(0.5, 0.0, 0.5);
+
+    #[test_case("linear-gradient(to right, red, blue)", (0.0, 5.0), Color::RED ; "linear start")]
+    #[test_case("linear-gradient(to right, red, blue)", (50.0, 5.0), PURPLE ; "linear middle")]
+    #[test_case("linear-gradient(to right, red, blue)", (100.0, 5.0), Color::BLUE ; "linear end")]
+    #[test_case("linear-gradient(red, blue)", (50.0, 2.5), Color::rgb(0.75, 0.0, 0.25) ; "linear to bottom")]
+    #[test_case("linear-gradient(90deg, red 50%, blue 50%)", (49.0, 5.0), Color::RED ; "hard stop before")]
+    #[test_case("linear-gradient(90deg, red 50%, blue 50%)", (51.0, 5.0), Color::BLUE ; "hard stop after")]
+    #[test_case("linear-gradient(to right, red, 20%, blue)", (20.0, 5.0), PURPLE ; "hint")]
+    #[test_case("linear-gradient(to right, red 40%, lime 20%, blue)", (30.0, 5.0), Color::RED ; "raised position")]
+    #[test_case("linear-gradient(to right, red 40%, lime 20%, blue)", (70.0, 5.0), Color::rgb(0.0, 0.5, 0.5) ; "after raised position")]
+    #[test_case("linear-gradient(to right, red, lime, blue 50%)", (25.0, 5.0), Color::GREEN ; "spread position")]
+    #[test_case("linear-gradient(to right, red, blue 20px)", (60.0, 5.0), Color::BLUE ; "pixel position")]
+    #[test_case("repeating-linear-gradient(to right, red, blue 20px)", (50.0, 5.0), PURPLE ; "repeating")]
+    #[test_case("linear-gradient(to bottom right, red, blue)", (100.0, 0.0), PURPLE ; "corner")]
+    #[test_case("linear-gradient(to right in hsl, red, blue)", (50.0, 5.0), Color::hsl(300.0, 1.0, 0.5) ; "hsl")]
+    #[test_case("radial-gradient(circle closest-side, red, blue)", (50.0, 5.0), Color::RED ; "radial center")]
+    #[test_case("radial-gradient(circle closest-side, red, blue)", (52.5, 5.0), PURPLE ; "radial middle")]
+    #[test_case("radial-gradient(closest-side at left, red, blue)", (50.0, 5.0), Color::BLUE ; "radial ellipse")]
+    #[test_case("radial-gradient(20px 10px, red, blue)", (50.0, 0.0), PURPLE ; "radial explicit")]
+    #[test_case("conic-gradient(red, blue)", (100.0, 5.0), Color::rgb(0.75, 0.0, 0.25) ; "conic quarter")]
+    #[test_case("conic-gradient(from 90deg, red, blue)", (100.0, 5.0), Color::RED ; "conic from")]
+    fn test_gradient_color_at(string: &str, (x, y): (f32, f32), expected: Color) {
+        let gradient = gradient_string_parser(string).unwrap();
+        let color = gradient.color_at(Vec2::new(x, y), Vec2::new(100.0, 10.0));
+        assert_color_close(color, expected);
+    }
+
+    #[test]
+    fn test_gradient_rasterize() {
+        let gradient = gradient_string_parser("linear-gradient(to right, red, blue)").unwrap();
+        let image = gradient.rasterize(4, 2);
+        assert_eq!(image.size(), UVec2::new(4, 2));
+        assert_eq!(image.data.len(), 4 * 2 * 4);
+        assert_eq!(&image.data[0..4], &[223, 0, 32, 255]);
+        assert_eq!(&image.data[16..20], &image.data[0..4]);
+        assert_eq!(&image.data[12..16], &[32, 0, 223, 255]);
+    }
+}
@@ -0,0 +1,582 @@
+use bevy::ui::{
+    GridPlacement, GridTrack, GridTrackRepetition, MaxTrackSizingFunction, MinTrackSizingFunction,
+    RepeatedGridTrack, Val,
+};
+use nom::{
+    branch::alt,
+    character::complete::{
+        i16 as signed_integer, multispace0 as multispace, multispace1, u16 as integer,
+    },
+    combinator::{cut, map, opt, verify},
+    error::context,
+    multi::separated_list1,
+    sequence::{delimited, pair, preceded, separated_pair, terminated, tuple},
+};
+
+use super::common::{comma_parser, function_parser, number_parser, token};
+use super::error::{parse_all, IResult, InputError, ParseError};
+use super::val::val_length_parser;
+
+/// Length of a grid track, which only supports px and %
+enum TrackLength {
+    Px(f32),
+    Percent(f32),
+}
+
+/// Parses a length in px or %, the only lengths supported by grid tracks
+fn track_length_parser(input: &str) -> IResult<&str, TrackLength> {
+    match val_length_parser(input)? {
+        (rest, Val::Px(value)) => Ok((rest, TrackLength::Px(value))),
+        (rest, Val::Percent(value)) => Ok((rest, TrackLength::Percent(value))),
+        _ => {
+            // report the unsupported unit, like `vw`
+            let (unit, _) = number_parser(input)?;
+            Err(nom::Err::Error(InputError {
+                input: unit,
+                expected: vec!["px", "%"],
+            }))
+        }
+    }
+}
+
+/// Parses the minimum of a `minmax()` track, like `100px` or `min-content`
+fn min_sizing_function_parser(input: &str) -> IResult<&str, MinTrackSizingFunction> {
+    alt((
+        map(track_length_parser, |length| match length {
+            TrackLength::Px(value) => MinTrackSizingFunction::Px(value),
+            TrackLength::Percent(value) => MinTrackSizingFunction::Percent(value),
+        }),
+        map(token("auto"), |_| MinTrackSizingFunction::Auto),
+        map(token("min-content"), |_| MinTrackSizingFunction::MinContent),
+        map(token("max-content"), |_| MinTrackSizingFunction::MaxContent),
+    ))(input)
+}
+
+/// Parses the maximum of a `minmax()` track, like `1fr` or `max-content`
+fn max_sizing_function_parser(input: &str) -> IResult<&str, MaxTrackSizingFunction> {
+    alt((
+        map(fraction_parser, MaxTrackSizingFunction::Fraction),
+        map(track_length_parser, |length| match length {
+            TrackLength::Px(value) => MaxTrackSizingFunction::Px(value),
+            TrackLength::Percent(value) => MaxTrackSizingFunction::Percent(value),
+        }),
+        map(token("auto"), |_| MaxTrackSizingFunction::Auto),
+        map(token("min-content"), |_| MaxTrackSizingFunction::MinContent),
+        map(token("max-content"), |_| MaxTrackSizingFunction::MaxContent),
+    ))(input)
+}
+
+/// Parses a flexible length, like `1fr`
+fn fraction_parser(input: &str) -> IResult<&str, f32> {
+    terminated(number_parser, token("fr"))(input)
+}
+
+/// Parses a single [`bevy::ui::GridTrack`], without surrounding whitespace
+///
+/// The syntax is inspired by CSS:
+///
+/// * `100px`, `25%` -> `GridTrack::px(100.0)`, `GridTrack::percent(25.0)`
+/// * `1fr` -> `GridTrack::fr(1.0)`
+/// * `auto`, `min-content`, `max-content` -> `GridTrack::auto()`, ...
+/// * `fit-content(200px)` -> `GridTrack::fit_content_px(200.0)`
+/// * `minmax(120px, 1fr)` -> `GridTrack::minmax(..)`
+///
+pub(crate) fn grid_track_parser(input: &str) -> IResult<&str, GridTrack> {
+    alt((
+        map(
+            function_parser(
+                "minmax",
+                separated_pair(
+                    min_sizing_function_parser,
+                    comma_parser,
+                    max_sizing_function_parser,
+                ),
+            ),
+            |(min, max)| GridTrack::minmax(min, max),
+        ),
+        map(
+            function_parser("fit-content", track_length_parser),
+            |length| match length {
+                TrackLength::Px(value) => GridTrack::fit_content_px(value),
+                TrackLength::Percent(value) => GridTrack::fit_content_percent(value),
+            },
+        ),
+        map(fraction_parser, GridTrack::fr),
+        map(track_length_parser, |length| match length {
+            TrackLength::Px(value) => GridTrack::px(value),
+            TrackLength::Percent(value) => GridTrack::percent(value),
+        }),
+        map(token("auto"), |_| GridTrack::auto()),
+        map(token("min-content"), |_| GridTrack::min_content()),
+        map(token("max-content"), |_| GridTrack::max_content()),
+    ))(input)
+}
+
+/// Parses a list of tracks separated by whitespace, without surrounding whitespace
+fn track_list_parser(input: &str) -> IResult<&str, Vec<GridTrack>> {
+    separated_list1(multispace1, grid_track_parser)(input)
+}
+
+/// Parses the repetition count of `repeat()`, like `3` or `auto-fill`
+fn repetition_parser(input: &str) -> IResult<&str, GridTrackRepetition> {
+    alt((
+        map(token("auto-fill"), |_| GridTrackRepetition::AutoFill),
+        map(token("auto-fit"), |_| GridTrackRepetition::AutoFit),
+        map(
+            context("<integer>", verify(integer, |count| *count > 0)),
+            GridTrackRepetition::Count,
+        ),
+    ))(input)
+}
+
+/// Parses a track or a `repeat()` of tracks, returning whether the repetition
+/// is automatic (`auto-fill` or `auto-fit`)
+fn repeated_track_parser(input: &str) -> IResult<&str, (bool, RepeatedGridTrack)> {
+    alt((
+        map(
+            function_parser(
+                "repeat",
+                separated_pair(repetition_parser, comma_parser, track_list_parser),
+            ),
+            |(repetition, tracks)| {
+                let is_auto = !matches!(repetition, GridTrackRepetition::Count(_));
+                (is_auto, RepeatedGridTrack::repeat_many(repetition, tracks))
+            },
+        ),
+        map(grid_track_parser, |track| (false, track.into())),
+    ))(input)
+}
+
+/// Parser for track lists like the css `grid-template-columns` and `grid-template-rows`
+///
+/// The syntax is inspired by CSS, tracks are separated by whitespace:
+///
+/// * `none` -> no explicit tracks
+/// * `200px 1fr 2fr` -> tracks as supported by [`grid_track_parser`]
+/// * `repeat(3, 1fr)`, `repeat(2, 100px auto)` -> tracks repeated a number of times
+/// * `repeat(auto-fill, minmax(120px, 1fr))`, `repeat(auto-fit, 100px)` -> tracks repeated
+///   to fill the available space, at most once per list
+///
+pub fn grid_template_parser(input: &str) -> IResult<&str, Vec<RepeatedGridTrack>> {
+    let (rest, tracks) = delimited(
+        multispace,
+        alt((
+            map(token("none"), |_| Vec::new()),
+            separated_list1(multispace1, repeated_track_parser),
+        )),
+        multispace,
+    )(input)?;
+    if tracks.iter().filter(|(is_auto, _)| *is_auto).count() > 1 {
+        return Err(nom::Err::Error(InputError::expected(
+            input.trim_start(),
+            "<track list with at most one auto-repeat>",
+        )));
+    }
+    Ok((rest, tracks.into_iter().map(|(_, track)| track).collect()))
+}
+
+/// Wrapper for [`grid_template_parser`] that returns an optional list of
+/// [`bevy::ui::RepeatedGridTrack`]
+pub fn grid_template_string_parser(input: &str) -> Option<Vec<RepeatedGridTrack>> {
+    grid_template_parser(input).map(|(_, value)| value).ok()
+}
+
+/// Wrapper for [`grid_template_parser`] that returns a [`ParseError`] on failure
+///
+/// Unlike [`grid_template_string_parser`] the whole input must be consumed.
+pub fn grid_template_result_parser(input: &str) -> Result<Vec<RepeatedGridTrack>, ParseError> {
+    parse_all(input, grid_template_parser)
+}
+
+/// Parser for track lists like the css `grid-auto-columns` and `grid-auto-rows`
+///
+/// Tracks as supported by [`grid_track_parser`] separated by whitespace, like `100px 1fr`.
+pub fn grid_auto_tracks_parser(input: &str) -> IResult<&str, Vec<GridTrack>> {
+    delimited(multispace, track_list_parser, multispace)(input)
+}
+
+/// Wrapper for [`grid_auto_tracks_parser`] that returns an optional list of
+/// [`bevy::ui::GridTrack`]
+pub fn grid_auto_tracks_string_parser(input: &str) -> Option<Vec<GridTrack>> {
+    grid_auto_tracks_parser(input).map(|(_, value)| value).ok()
+}
+
+/// Wrapper for [`grid_auto_tracks_parser`] that returns a [`ParseError`] on failure
+///
+/// Unlike [`grid_auto_tracks_string_parser`] the whole input must be consumed.
+pub fn grid_auto_tracks_result_parser(input: &str) -> Result<Vec<GridTrack>, ParseError> {
+    parse_all(input, grid_auto_tracks_parser)
+}
+
+/// Wrapper for [`grid_template_parser`] that implements a serde deserializer
+#[cfg(feature = "serde")]
+pub fn grid_template_serde_parser<'de, D>(
+    deserializer: D,
+) -> Result<Vec<RepeatedGridTrack>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::Error;
+    let s: &str = serde::Deserialize::deserialize(deserializer)?;
+    grid_template_result_parser(s)
+        .map_err(|error| D::Error::custom(format!("invalid grid template string: {}", error)))
+}
+
+/// Wrapper for [`grid_auto_tracks_parser`] that implements a serde deserializer
+#[cfg(feature = "serde")]
+pub fn grid_auto_tracks_serde_parser<'de, D>(deserializer: D) -> Result<Vec<GridTrack>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::Error;
+    let s: &str = serde::Deserialize::deserialize(deserializer)?;
+    grid_auto_tracks_result_parser(s)
+        .map_err(|error| D::Error::custom(format!("invalid grid tracks string: {}", error)))
+}
+
+/// A grid line of a placement, like the css `grid-row-start`
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum GridLine {
+    Auto,
+    Index(i16),
+    Span(u16),
+}
+
+/// Parses a grid line, like `2`, `-1`, `span 3` or `auto`
+fn grid_line_parser(input: &str) -> IResult<&str, GridLine> {
+    alt((
+        map(token("auto"), |_| GridLine::Auto),
+        map(
+            preceded(
+                pair(token("span"), multispace1),
+                context("<positive integer>", verify(integer, |span| *span > 0)),
+            ),
+            GridLine::Span,
+        ),
+        map(
+            context(
+                "<non-zero integer>",
+                verify(signed_integer, |index| *index != 0),
+            ),
+            GridLine::Index,
+        ),
+    ))(input)
+}
+
+/// Parses the `/` between grid lines
+fn grid_line_separator_parser(input: &str) -> IResult<&str, &str> {
+    delimited(multispace, token("/"), multispace)(input)
+}
+
+/// Creates the placement between the start and end lines, following the css placement rules
+fn placement_from_lines(start: GridLine, end: GridLine) -> GridPlacement {
+    match (start, end) {
+        (GridLine::Index(start), GridLine::Index(end)) => GridPlacement::start_end(start, end),
+        (GridLine::Index(start), GridLine::Span(span)) => GridPlacement::start_span(start, span),
+        (GridLine::Index(start), GridLine::Auto) => GridPlacement::start(start),
+        (GridLine::Span(span), GridLine::Index(end)) => GridPlacement::end_span(end, span),
+        (GridLine::Auto, GridLine::Index(end)) => GridPlacement::end(end),
+        // a span at the end is ignored if the start is a span as well
+        (GridLine::Span(span), _) | (GridLine::Auto, GridLine::Span(span)) => {
+            GridPlacement::span(span)
+        }
+        (GridLine::Auto, GridLine::Auto) => GridPlacement::auto(),
+    }
+}
+
+/// Parser for strings that represent a [`bevy::ui::GridPlacement`].
+///
+/// The syntax is inspired by the css `grid-row` and `grid-column` properties,
+/// a start line optionally followed by `/` and an end line:
+///
+/// * `auto` -> `GridPlacement::auto()`
+/// * `2` -> `GridPlacement::start(2)`
+/// * `-1` -> `GridPlacement::start(-1)`, negative lines count from the end
+/// * `span 2` -> `GridPlacement::span(2)`
+/// * `2 / 4` -> `GridPlacement::start_end(2, 4)`
+/// * `2 / span 3` -> `GridPlacement::start_span(2, 3)`
+/// * `span 3 / -1` -> `GridPlacement::end_span(-1, 3)`
+///
+/// Lines can't be zero, just like spans.
+pub fn grid_placement_parser(input: &str) -> IResult<&str, GridPlacement> {
+    map(
+        delimited(
+            multispace,
+            pair(
+                grid_line_parser,
+                opt(preceded(grid_line_separator_parser, cut(grid_line_parser))),
+            ),
+            multispace,
+        ),
+        |(start, end)| placement_from_lines(start, end.unwrap_or(GridLine::Auto)),
+    )(input)
+}
+
+/// Wrapper for [`grid_placement_parser`] that returns an optional [`bevy::ui::GridPlacement`]
+pub fn grid_placement_string_parser(input: &str) -> Option<GridPlacement> {
+    grid_placement_parser(input).map(|(_, value)| value).ok()
+}
+
+/// Wrapper for [`grid_placement_parser`] that returns a [`ParseError`] on failure
+///
+/// Unlike [`grid_placement_string_parser`] the whole input must be consumed.
+pub fn grid_placement_result_parser(input: &str) -> Result<GridPlacement, ParseError> {
+    parse_all(input, grid_placement_parser)
+}
+
+/// Formats a [`bevy::ui::GridPlacement`] as a string, the counterpart of [`grid_placement_parser`]
+///
+/// For example `GridPlacement::start_span(2, 3)` -> `2 / span 3`.
+pub fn grid_placement_formatter(placement: GridPlacement) -> String {
+    let span = placement.get_span().unwrap_or(1);
+    match (placement.get_start(), placement.get_end()) {
+        (Some(start), Some(end)) => format!("{} / {}", start, end),
+        (Some(start), None) if span == 1 => start.to_string(),
+        (Some(start), None) => format!("{} / span {}", start, span),
+        (None, Some(end)) if span == 1 => format!("auto / {}", end),
+        (None, Some(end)) => format!("span {} / {}", span, end),
+        (None, None) if span == 1 => "auto".to_string(),
+        (None, None) => format!("span {}", span),
+    }
+}
+
+/// Parser for the css `grid-area` shorthand, returns the row and column placements
+///
+/// Up to four grid lines separated by `/`, in the order
+/// `row-start / column-start / row-end / column-end`, like `1 / 2 / 3 / 4`.
+/// Omitted lines are `auto`.
+pub fn grid_area_parser(input: &str) -> IResult<&str, (GridPlacement, GridPlacement)> {
+    let line = || opt(preceded(grid_line_separator_parser, cut(grid_line_parser)));
+    map(
+        delimited(
+            multispace,
+            tuple((grid_line_parser, line(), line(), line())),
+            multispace,
+        ),
+        |(row_start, column_start, row_end, column_end)| {
+            let auto = |line: Option<GridLine>| line.unwrap_or(GridLine::Auto);
+            (
+                placement_from_lines(row_start, auto(row_end)),
+                placement_from_lines(auto(column_start), auto(column_end)),
+            )
+        },
+    )(input)
+}
+
+/// Wrapper for [`grid_area_parser`] that returns the optional row and column placements
+pub fn grid_area_string_parser(input: &str) -> Option<(GridPlacement, GridPlacement)> {
+    grid_area_parser(input).map(|(_, value)| value).ok()
+}
+
+/// Wrapper for [`grid_area_parser`] that returns a [`ParseError`] on failure
+///
+/// Unlike [`grid_area_string_parser`] the whole input must be consumed.
+pub fn grid_area_result_parser(input: &str) -> Result<(GridPlacement, GridPlacement), ParseError> {
+    parse_all(input, grid_area_parser)
+}
+
+/// Wrapper for [`grid_placement_parser`] that implements a serde deserializer
+#[cfg(feature = "serde")]
+pub fn grid_placement_serde_parser<'de, D>(deserializer: D) -> Result<GridPlacement, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::Error;
+    let s: &str = serde::Deserialize::deserialize(deserializer)?;
+    grid_placement_result_parser(s)
+        .map_err(|error| D::Error::custom(format!("invalid grid placement string: {}", error)))
+}
+
+/// Wrapper for [`grid_placement_formatter`] that implements a serde serializer
+#[cfg(feature = "serde")]
+pub fn grid_placement_serde_serializer<S>(
+    placement: &GridPlacement,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.serialize_str(&grid_placement_formatter(*placement))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("100px", GridTrack::px(100.0) ; "px")]
+    #[test_case("25%", GridTrack::percent(25.0) ; "percent")]
+    #[test_case("1.5fr", GridTrack::fr(1.5) ; "fr")]
+    #[test_case("auto", GridTrack::auto() ; "auto")]
+    #[test_case("min-content", GridTrack::min_content() ; "min content")]
+    #[test_case("max-content", GridTrack::max_content() ; "max content")]
+    #[test_case("fit-content(200px)", GridTrack::fit_content_px(200.0) ; "fit content px")]
+    #[test_case("fit-content( 50% )", GridTrack::fit_content_percent(50.0) ; "fit content percent")]
+    #[test_case(
+        "minmax(120px,1fr)",
+        GridTrack::minmax(MinTrackSizingFunction::Px(120.0), MaxTrackSizingFunction::Fraction(1.0)) ;
+        "minmax"
+    )]
+    #[test_case(
+        "minmax( min-content , 30% )",
+        GridTrack::minmax(MinTrackSizingFunction::MinContent, MaxTrackSizingFunction::Percent(30.0)) ;
+        "minmax keywords"
+    )]
+    fn test_grid_track_parser(string: &str, expected: GridTrack) {
+        assert_eq!(grid_track_parser(string), Ok(("", expected)));
+    }
+
+    #[test]
+    fn test_grid_template_parser() {
+        assert_eq!(
+            grid_template_result_parser("repeat(auto-fill, minmax(120px, 1fr)) 200px"),
+            Ok(vec![
+                RepeatedGridTrack::repeat_many(
+                    GridTrackRepetition::AutoFill,
+                    vec![GridTrack::minmax(
+                        MinTrackSizingFunction::Px(120.0),
+                        MaxTrackSizingFunction::Fraction(1.0)
+                    )]
+                ),
+                GridTrack::px(200.0),
+            ])
+        );
+        assert_eq!(
+            grid_template_result_parser(" repeat(2, 100px auto)  1fr "),
+            Ok(vec![
+                RepeatedGridTrack::repeat_many(2, vec![GridTrack::px(100.0), GridTrack::auto()]),
+                GridTrack::fr(1.0),
+            ])
+        );
+        assert_eq!(
+            grid_template_result_parser("repeat(auto-fit, 50%)"),
+            Ok(vec![RepeatedGridTrack::percent(
+                GridTrackRepetition::AutoFit,
+                50.0
+            )])
+        );
+        assert_eq!(grid_template_result_parser("none"), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_grid_auto_tracks_parser() {
+        assert_eq!(
+            grid_auto_tracks_string_parser("100px 1fr"),
+            Some(vec![GridTrack::px(100.0), GridTrack::fr(1.0)])
+        );
+        assert_eq!(grid_auto_tracks_string_parser("repeat(2, 1fr)"), None);
+    }
+
+    #[test_case("auto", GridPlacement::auto() ; "auto")]
+    #[test_case("2", GridPlacement::start(2) ; "start")]
+    #[test_case("-1", GridPlacement::start(-1) ; "negative start")]
+    #[test_case("span 2", GridPlacement::span(2) ; "span")]
+    #[test_case("2 / 4", GridPlacement::start_end(2, 4) ; "start end")]
+    #[test_case(" 2/span 3 ", GridPlacement::start_span(2, 3) ; "start span")]
+    #[test_case("span 3 / -1", GridPlacement::end_span(-1, 3) ; "end span")]
+    #[test_case("auto / 3", GridPlacement::end(3) ; "end")]
+    #[test_case("span 2 / span 3", GridPlacement::span(2) ; "two spans")]
+    fn test_grid_placement_parser(string: &str, expected: GridPlacement) {
+        assert_eq!(grid_placement_parser(string), Ok(("", expected)));
+    }
+
+    #[test_case(GridPlacement::auto(), "auto" ; "auto")]
+    #[test_case(GridPlacement::start(-2), "-2" ; "start")]
+    #[test_case(GridPlacement::span(4), "span 4" ; "span")]
+    #[test_case(GridPlacement::start_end(1, 3), "1 / 3" ; "start end")]
+    #[test_case(GridPlacement::start_span(2, 3), "2 / span 3" ; "start span")]
+    #[test_case(GridPlacement::end(3), "auto / 3" ; "end")]
+    #[test_case(GridPlacement::end_span(-1, 2), "span 2 / -1" ; "end span")]
+    fn test_grid_placement_formatter(placement: GridPlacement, expected: &str) {
+        assert_eq!(grid_placement_formatter(placement), expected);
+        assert_eq!(grid_placement_result_parser(expected), Ok(placement));
+    }
+
+    #[test]
+    fn test_grid_placement_result_parser_errors() {
+        let error = grid_placement_result_parser("0").unwrap_err();
+        assert_eq!(error.offset, 0);
+        assert_eq!(error.expected, vec!["auto", "span", "<non-zero integer>"]);
+
+        let error = grid_placement_result_parser("1 / span 0").unwrap_err();
+        assert_eq!(error.offset, 9);
+        assert_eq!(error.expected, vec!["<positive integer>"]);
+
+        let error = grid_placement_result_parser("1 / 2 / 3").unwrap_err();
+        assert_eq!(error.offset, 6);
+    }
+
+    #[test_case("1 / 2 / 3 / 4", GridPlacement::start_end(1, 3), GridPlacement::start_end(2, 4) ; "four lines")]
+    #[test_case("2 / 1 / span 2", GridPlacement::start_span(2, 2), GridPlacement::start(1) ; "three lines")]
+    #[test_case("1 / 3", GridPlacement::start(1), GridPlacement::start(3) ; "two lines")]
+    #[test_case("span 2", GridPlacement::span(2), GridPlacement::auto() ; "one line")]
+    fn test_grid_area_parser(string: &str, row: GridPlacement, column: GridPlacement) {
+        assert_eq!(grid_area_result_parser(string), Ok((row, column)));
+    }
+
+    #[test]
+    fn test_grid_template_result_parser_errors() {
+        let error = grid_template_result_parser("10vw").unwrap_err();
+        assert_eq!(error.offset, 2);
+        assert_eq!(error.expected, vec!["fr", "px", "%"]);
+
+        let error = grid_template_result_parser("repeat(0, 1fr)").unwrap_err();
+        assert_eq!(error.offset, 7);
+        assert_eq!(error.expected, vec!["auto-fill", "auto-fit", "<integer>"]);
+
+        // flexible lengths are not allowed as the minimum
+        let error = grid_template_result_parser("minmax(1fr, 1fr)").unwrap_err();
+        assert_eq!(error.offset, 8);
+        assert_eq!(error.fragment, "fr");
+        assert!(error.expected.contains(&"px"));
+
+        let error = grid_template_result_parser("repeat(auto-fill, 10px) repeat(auto-fit, 10px)")
+            .unwrap_err();
+        assert_eq!(error.offset, 0);
+        assert_eq!(
+            error.expected,
+            vec!["<track list with at most one auto-repeat>"]
+        );
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests_serde {
+    use bevy::ui::{GridPlacement, GridTrack, RepeatedGridTrack};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Deserialize)]
+    pub struct Foo {
+        #[serde(deserialize_with = "super::grid_template_serde_parser")]
+        pub columns: Vec<RepeatedGridTrack>,
+        #[serde(deserialize_with = "super::grid_auto_tracks_serde_parser")]
+        pub auto_rows: Vec<GridTrack>,
+    }
+
+    #[derive(Deserialize, Serialize)]
+    pub struct Bar {
+        #[serde(
+            deserialize_with = "super::grid_placement_serde_parser",
+            serialize_with = "super::grid_placement_serde_serializer"
+        )]
+        pub column: GridPlacement,
+    }
+
+    #[test]
+    fn test_grid_placement_serde_parser() {
+        let bar: Bar = serde_json::from_str(r#"{"column": "2 / span 3"}"#).unwrap();
+        assert_eq!(bar.column, GridPlacement::start_span(2, 3));
+        assert_eq!(
+            serde_json::to_string(&bar).unwrap(),
+            r#"{"column":"2 / span 3"}"#
+        );
+    }
+
+    #[test]
+    fn test_grid_serde_parsers() {
+        let foo: Foo =
+            serde_json::from_str(r#"{"columns": "repeat(3, 1fr)", "auto_rows": "100px"}"#).unwrap();
+        assert_eq!(foo.columns, vec![RepeatedGridTrack::fr(3, 1.0)]);
+        assert_eq!(foo.auto_rows, vec![GridTrack::px(100.0)]);
+    }
+}
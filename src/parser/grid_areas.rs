@@ -0,0 +1,400 @@
+use bevy::{ecs::component::Component, ui::GridPlacement, utils::HashMap};
+use nom::{
+    branch::alt,
+    bytes::complete::take_while1,
+    character::complete::{multispace0 as multispace, multispace1},
+    combinator::map,
+    error::context,
+    multi::{many1, separated_list1},
+    sequence::{delimited, terminated},
+};
+
+use super::common::{identifier_parser, token};
+use super::error::{parse_all, IResult, InputError, ParseError};
+use super::grid::grid_area_parser;
+
+/// Named areas of a grid, like the css `grid-template-areas`
+///
+/// Bevy has no named grid areas, instead the line indices of each area are computed
+/// from the area strings, see [`grid_template_areas_parser`]. The areas can be used
+/// as a plain value or as a bevy [`Component`] of the grid container, to place children
+/// with a [`crate::GridAreaName`] by name.
+#[derive(Component, Debug, Clone, Default, PartialEq)]
+pub struct GridTemplateAreas {
+    rows: usize,
+    columns: usize,
+    /// Row and column placements of the areas by name
+    areas: HashMap<String, (GridPlacement, GridPlacement)>,
+}
+
+impl GridTemplateAreas {
+    /// Returns the row and column placement of the named area
+    pub fn get(&self, name: &str) -> Option<(GridPlacement, GridPlacement)> {
+        self.areas.get(name).copied()
+    }
+
+    /// Returns true if an area with the name exists
+    pub fn contains(&self, name: &str) -> bool {
+        self.areas.contains_key(name)
+    }
+
+    /// Returns the names of all areas, in no particular order
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.areas.keys().map(String::as_str)
+    }
+
+    /// Returns the number of rows spanned by the area strings
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Returns the number of columns spanned by the area strings
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+}
+
+/// Parses a cell of an area string, an area name or one or more `.` for an empty cell
+fn area_cell_parser(input: &str) -> IResult<&str, &str> {
+    context(
+        "<area name>",
+        alt((identifier_parser, take_while1(|c| c == '.'))),
+    )(input)
+}
+
+/// Parses an area string with cells separated by whitespace, like `"header header"`
+///
+/// Returns the input at the start of the string along with the cells.
+fn area_string_parser(input: &str) -> IResult<&str, (&str, Vec<&str>)> {
+    let cells = || {
+        delimited(
+            multispace,
+            separated_list1(multispace1, area_cell_parser),
+            multispace,
+        )
+    };
+    map(
+        alt((
+            delimited(token("\""), cells(), token("\"")),
+            delimited(token("'"), cells(), token("'")),
+        )),
+        move |cells| (input, cells),
+    )(input)
+}
+
+/// Computes the placements of the areas, the rows must have the same number of cells
+fn template_areas_from_rows<'a>(
+    rows: &[(&'a str, Vec<&'a str>)],
+) -> Result<GridTemplateAreas, InputError<&'a str>> {
+    let columns = rows[0].1.len();
+    if let Some((row, _)) = rows.iter().find(|(_, cells)| cells.len() != columns) {
+        return Err(InputError::expected(
+            row,
+            "<string with the same number of cells as the first>",
+        ));
+    }
+    let cell = |row: usize, column: usize| rows[row].1[column];
+    let mut areas = HashMap::default();
+    for (row, (_, cells)) in rows.iter().enumerate() {
+        for (column, name) in cells.iter().enumerate() {
+            if name.starts_with('.') || areas.contains_key(*name) {
+                continue;
+            }
+            // the first cell in reading order is the top left corner of the area
+            let end_column = (column..columns)
+                .take_while(|column| cell(row, *column) == *name)
+                .last()
+                .unwrap_or(column);
+            let end_row = (row..rows.len())
+                .take_while(|row| cell(*row, column) == *name)
+                .last()
+                .unwrap_or(row);
+            // the area must cover exactly the rectangle between the corners
+            for (other_row, (_, cells)) in rows.iter().enumerate() {
+                for (other_column, other_name) in cells.iter().enumerate() {
+                    let inside = (row..=end_row).contains(&other_row)
+                        && (column..=end_column).contains(&other_column);
+                    if inside != (other_name == name) {
+                        return Err(InputError::expected(other_name, "<rectangular area>"));
+                    }
+                }
+            }
+            // grid lines are 1-based and stored as i16 by bevy
+            let line = |index: usize| i16::try_from(index + 1).ok();
+            let (Some(row_start), Some(row_end), Some(column_start), Some(column_end)) = (
+                line(row),
+                line(end_row + 1),
+                line(column),
+                line(end_column + 1),
+            ) else {
+                return Err(InputError::expected(name, "<area within 32767 grid lines>"));
+            };
+            areas.insert(
+                name.to_string(),
+                (
+                    GridPlacement::start_end(row_start, row_end),
+                    GridPlacement::start_end(column_start, column_end),
+                ),
+            );
+        }
+    }
+    Ok(GridTemplateAreas {
+        rows: rows.len(),
+        columns,
+        areas,
+    })
+}
+
+/// Parser for strings that represent [`GridTemplateAreas`].
+///
+/// The syntax is inspired by the css `grid-template-areas`, one string per row
+/// with the area names of the cells separated by whitespace:
+///
+/// * `"header header" "sidebar main"` -> `header` spans the first row, `sidebar`
+///   and `main` the cells of the second row
+/// * `"a . b"` -> one or more `.` mark an empty cell
+/// * `none` -> no areas
+///
+/// All strings must have the same number of cells and the cells of an area must form
+/// a rectangle.
+pub fn grid_template_areas_parser(input: &str) -> IResult<&str, GridTemplateAreas> {
+    let (input, _) = multispace(input)?;
+    if let Ok((rest, _)) = terminated(token("none"), multispace)(input) {
+        return Ok((rest, GridTemplateAreas::default()));
+    }
+    let (rest, rows) = many1(terminated(area_string_parser, multispace))(input).map_err(
+        |error: nom::Err<InputError<&str>>| {
+            error.map(|mut error| {
+                if error.input == input {
+                    error.expected.insert(0, "none");
+                }
+                error
+            })
+        },
+    )?;
+    let areas = template_areas_from_rows(&rows).map_err(nom::Err::Error)?;
+    Ok((rest, areas))
+}
+
+/// Wrapper for [`grid_template_areas_parser`] that returns optional [`GridTemplateAreas`]
+pub fn grid_template_areas_string_parser(input: &str) -> Option<GridTemplateAreas> {
+    grid_template_areas_parser(input)
+        .map(|(_, value)| value)
+        .ok()
+}
+
+/// Wrapper for [`grid_template_areas_parser`] that returns a [`ParseError`] on failure
+///
+/// Unlike [`grid_template_areas_string_parser`] the whole input must be consumed.
+pub fn grid_template_areas_result_parser(input: &str) -> Result<GridTemplateAreas, ParseError> {
+    parse_all(input, grid_template_areas_parser)
+}
+
+/// Parser for the css `grid-area` with area names, returns the row and column placements
+///
+/// Accepts the name of an area of the [`GridTemplateAreas`], like `main`, or grid lines
+/// as supported by [`super::grid_area_parser`], like `1 / 2 / 3 / 4`.
+pub fn grid_named_area_parser<'a>(
+    input: &'a str,
+    areas: &GridTemplateAreas,
+) -> IResult<&'a str, (GridPlacement, GridPlacement)> {
+    let (input, _) = multispace(input)?;
+    let named_area = |input: &'a str| {
+        let (rest, name) = identifier_parser(input)?;
+        match areas.get(name) {
+            Some(placements) => Ok((rest, placements)),
+            None => Err(nom::Err::Error(InputError::expected(input, "<area name>"))),
+        }
+    };
+    alt((grid_area_parser, terminated(named_area, multispace)))(input)
+}
+
+/// Wrapper for [`grid_named_area_parser`] that returns the optional row and column placements
+pub fn grid_named_area_string_parser(
+    input: &str,
+    areas: &GridTemplateAreas,
+) -> Option<(GridPlacement, GridPlacement)> {
+    grid_named_area_parser(input, areas)
+        .map(|(_, value)| value)
+        .ok()
+}
+
+/// Wrapper for [`grid_named_area_parser`] that returns a [`ParseError`] on failure
+///
+/// Unlike [`grid_named_area_string_parser`] the whole input must be consumed.
+pub fn grid_named_area_result_parser(
+    input: &str,
+    areas: &GridTemplateAreas,
+) -> Result<(GridPlacement, GridPlacement), ParseError> {
+    parse_all(input, |input| grid_named_area_parser(input, areas))
+}
+
+/// Wrapper for [`grid_template_areas_parser`] that implements a serde deserializer
+#[cfg(feature = "serde")]
+pub fn grid_template_areas_serde_parser<'de, D>(
+    deserializer: D,
+) -> Result<GridTemplateAreas, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::Error;
+    // area strings are quoted, so the string is usually escaped and can't be borrowed
+    let s: String = serde::Deserialize::deserialize(deserializer)?;
+    grid_template_areas_result_parser(&s)
+        .map_err(|error| D::Error::custom(format!("invalid grid template areas string: {}", error)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test]
+    fn test_grid_template_areas_parser() {
+        let areas = grid_template_areas_result_parser(
+            r#"
+            "header header header"
+            "sidebar main main"
+            "sidebar main main"
+            ". footer ..."
+            "#,
+        )
+        .unwrap();
+        assert_eq!((areas.rows(), areas.columns()), (4, 3));
+        assert_eq!(
+            areas.get("header"),
+            Some((
+                GridPlacement::start_end(1, 2),
+                GridPlacement::start_end(1, 4)
+            ))
+        );
+        assert_eq!(
+            areas.get("sidebar"),
+            Some((
+                GridPlacement::start_end(2, 4),
+                GridPlacement::start_end(1, 2)
+            ))
+        );
+        assert_eq!(
+            areas.get("main"),
+            Some((
+                GridPlacement::start_end(2, 4),
+                GridPlacement::start_end(2, 4)
+            ))
+        );
+        assert_eq!(
+            areas.get("footer"),
+            Some((
+                GridPlacement::start_end(4, 5),
+                GridPlacement::start_end(2, 3)
+            ))
+        );
+        let mut names: Vec<_> = areas.names().collect();
+        names.sort();
+        assert_eq!(names, vec!["footer", "header", "main", "sidebar"]);
+        assert!(!areas.contains("."));
+    }
+
+    #[test_case("'a b'", 1, 2 ; "single quotes")]
+    #[test_case(r#""a""b""#, 2, 1 ; "without whitespace")]
+    #[test_case("none", 0, 0 ; "none")]
+    fn test_grid_template_areas_parser_size(string: &str, rows: usize, columns: usize) {
+        let areas = grid_template_areas_result_parser(string).unwrap();
+        assert_eq!((areas.rows(), areas.columns()), (rows, columns));
+    }
+
+    #[test_case(r#""a a" "a b""#, 9, "b" ; "l shape")]
+    #[test_case(r#""a b a""#, 5, "a" ; "split row")]
+    #[test_case(r#""a b" "b a""#, 9, "a" ; "diagonal")]
+    #[test_case(r#""a b" "c d" "a e""#, 13, "a" ; "split column")]
+    fn test_grid_template_areas_parser_non_rectangular(
+        string: &str,
+        offset: usize,
+        fragment: &str,
+    ) {
+        let error = grid_template_areas_result_parser(string).unwrap_err();
+        assert_eq!(error.offset, offset);
+        assert_eq!(error.fragment, fragment);
+        assert_eq!(error.expected, vec!["<rectangular area>"]);
+    }
+
+    #[test]
+    fn test_grid_template_areas_result_parser_errors() {
+        let error = grid_template_areas_result_parser(r#""a b" "c""#).unwrap_err();
+        assert_eq!(error.offset, 6);
+        assert_eq!(
+            error.expected,
+            vec!["<string with the same number of cells as the first>"]
+        );
+
+        let error = grid_template_areas_result_parser(r#""a b"#).unwrap_err();
+        assert_eq!(error.offset, 4);
+        assert_eq!(error.expected, vec!["\""]);
+
+        let error = grid_template_areas_result_parser("header").unwrap_err();
+        assert_eq!(error.offset, 0);
+        assert_eq!(error.expected, vec!["none", "\"", "'"]);
+    }
+
+    #[test]
+    fn test_grid_template_areas_parser_line_overflow() {
+        let string = format!("\"{}a\"", ". ".repeat(32766));
+        let error = grid_template_areas_result_parser(&string).unwrap_err();
+        assert_eq!(error.offset, string.len() - 2);
+        assert_eq!(error.expected, vec!["<area within 32767 grid lines>"]);
+
+        let string = format!("\"{}a\"", ". ".repeat(32765));
+        let areas = grid_template_areas_result_parser(&string).unwrap();
+        assert_eq!(
+            areas.get("a").map(|(_, column)| column),
+            Some(GridPlacement::start_end(32766, 32767))
+        );
+    }
+
+    #[test]
+    fn test_grid_named_area_parser() {
+        let areas = grid_template_areas_string_parser(r#""header header" "sidebar main""#).unwrap();
+        assert_eq!(
+            grid_named_area_result_parser(" main ", &areas),
+            Ok((
+                GridPlacement::start_end(2, 3),
+                GridPlacement::start_end(2, 3)
+            ))
+        );
+        assert_eq!(
+            grid_named_area_result_parser("1 / 2", &areas),
+            Ok((GridPlacement::start(1), GridPlacement::start(2)))
+        );
+        let error = grid_named_area_result_parser("footer", &areas).unwrap_err();
+        assert_eq!(error.offset, 0);
+        assert_eq!(
+            error.expected,
+            vec!["auto", "span", "<non-zero integer>", "<area name>"]
+        );
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests_serde {
+    use super::GridTemplateAreas;
+    use bevy::ui::GridPlacement;
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    pub struct Foo {
+        #[serde(deserialize_with = "super::grid_template_areas_serde_parser")]
+        pub areas: GridTemplateAreas,
+    }
+
+    #[test]
+    fn test_grid_template_areas_serde_parser() {
+        let foo: Foo = serde_json::from_str(r#"{"areas": "\"a b\" \"a c\""}"#).unwrap();
+        assert_eq!(
+            foo.areas.get("a"),
+            Some((
+                GridPlacement::start_end(1, 3),
+                GridPlacement::start_end(1, 2)
+            ))
+        );
+    }
+}
@@ -0,0 +1,232 @@
+use bevy::{
+    render::color::Color,
+    ui::{UiRect, Val},
+};
+
+use std::f32::consts::{PI, TAU};
+
+use super::color_mix::interpolate_colors;
+use super::{CalcContext, CalcExpr, ColorSpace, HueInterpolation};
+
+/// Interpolates between two colors in the color space, `t` is the fraction of the second color
+///
+/// Hues of polar color spaces like [`ColorSpace::Hsl`] take the shorter way around the
+/// hue circle and the alpha is premultiplied, as in css `color-mix()`.
+pub fn interpolate_color(first: Color, second: Color, t: f32, space: ColorSpace) -> Color {
+    interpolate_colors((space, HueInterpolation::Shorter), first, second, t)
+}
+
+/// Interpolates between two [`Val`]s, `t` is the fraction of the second value
+///
+/// Values of the same unit result in a [`CalcExpr::Length`] of that unit, like
+/// `Val::Px(15.0)` halfway between `10px` and `20px`. Values of different units
+/// result in an expression like `calc(10px * 0.5 + 50% * 0.5)`, resolved with
+/// [`CalcExpr::resolve`] once the size percentages refer to is known. `auto` can't
+/// be interpolated and switches to the other value halfway.
+pub fn interpolate_val(first: Val, second: Val, t: f32) -> CalcExpr {
+    let lerp = |a: f32, b: f32| a + (b - a) * t;
+    let val = match (first, second) {
+        (Val::Px(a), Val::Px(b)) => Val::Px(lerp(a, b)),
+        (Val::Percent(a), Val::Percent(b)) => Val::Percent(lerp(a, b)),
+        (Val::Vw(a), Val::Vw(b)) => Val::Vw(lerp(a, b)),
+        (Val::Vh(a), Val::Vh(b)) => Val::Vh(lerp(a, b)),
+        (Val::VMin(a), Val::VMin(b)) => Val::VMin(lerp(a, b)),
+        (Val::VMax(a), Val::VMax(b)) => Val::VMax(lerp(a, b)),
+        (Val::Auto, _) | (_, Val::Auto) => {
+            if t < 0.5 {
+                first
+            } else {
+                second
+            }
+        }
+        _ => {
+            let weighted = |val: Val, weight: f32| {
+                CalcExpr::Mul(
+                    Box::new(CalcExpr::Length(val)),
+                    Box::new(CalcExpr::Number(weight)),
+                )
+            };
+            return CalcExpr::Add(
+                Box::new(weighted(first, 1.0 - t)),
+                Box::new(weighted(second, t)),
+            );
+        }
+    };
+    CalcExpr::Length(val)
+}
+
+/// A [`UiRect`] of [`CalcExpr`]s, the result of [`interpolate_rect`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalcRect {
+    pub left: CalcExpr,
+    pub right: CalcExpr,
+    pub top: CalcExpr,
+    pub bottom: CalcExpr,
+}
+
+impl CalcRect {
+    /// Resolves the expressions into a [`UiRect`] of [`Val::Px`]
+    ///
+    /// As for margins and paddings in css, percentages of all sides refer to
+    /// the same size, usually the width of the parent node. Returns `None` if
+    /// a side doesn't resolve to a finite length.
+    pub fn resolve(&self, context: &CalcContext) -> Option<UiRect> {
+        Some(UiRect {
+            left: self.left.resolve_val(context)?,
+            right: self.right.resolve_val(context)?,
+            top: self.top.resolve_val(context)?,
+            bottom: self.bottom.resolve_val(context)?,
+        })
+    }
+
+    /// Returns the [`UiRect`] if all sides are plain lengths, like `10px` or `50%`
+    pub fn to_rect(&self) -> Option<UiRect> {
+        match (&self.left, &self.right, &self.top, &self.bottom) {
+            (
+                CalcExpr::Length(left),
+                CalcExpr::Length(right),
+                CalcExpr::Length(top),
+                CalcExpr::Length(bottom),
+            ) => Some(UiRect::new(*left, *right, *top, *bottom)),
+            _ => None,
+        }
+    }
+}
+
+/// Interpolates between two [`UiRect`]s, each side as in [`interpolate_val`]
+pub fn interpolate_rect(first: UiRect, second: UiRect, t: f32) -> CalcRect {
+    CalcRect {
+        left: interpolate_val(first.left, second.left, t),
+        right: interpolate_val(first.right, second.right, t),
+        top: interpolate_val(first.top, second.top, t),
+        bottom: interpolate_val(first.bottom, second.bottom, t),
+    }
+}
+
+/// Interpolates between two angles in radians along the shorter arc, `t` is the
+/// fraction of the second angle
+///
+/// Like `350deg` to `10deg` passing `0deg` instead of `180deg`, also for angles of
+/// more than one turn. The result is not normalized.
+pub fn interpolate_angle(first: f32, second: f32, t: f32) -> f32 {
+    // the difference along the shorter arc, in (-π, π]
+    let mut difference = (second - first).rem_euclid(TAU);
+    if difference > PI {
+        difference -= TAU;
+    }
+    first + difference * t
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::PI;
+
+    use bevy::math::Vec2;
+
+    use super::*;
+    use test_case::test_case;
+
+    fn assert_color_eq(color: Color, expected: Color) {
+        let (color, expected) = (color.as_rgba_f32(), expected.as_rgba_f32());
+        for (a, b) in color.iter().zip(expected.iter()) {
+            assert!((a - b).abs() < 1e-3, "{:?} != {:?}", color, expected);
+        }
+    }
+
+    #[test_case(ColorSpace::Srgb, Color::rgb(0.5, 0.5, 0.5) ; "srgb")]
+    #[test_case(ColorSpace::SrgbLinear, Color::rgb_linear(0.5, 0.5, 0.5) ; "linear")]
+    #[test_case(ColorSpace::Oklab, Color::rgb(0.389, 0.389, 0.389) ; "oklab")]
+    fn test_interpolate_color(space: ColorSpace, expected: Color) {
+        let color = interpolate_color(Color::BLACK, Color::WHITE, 0.5, space);
+        assert_color_eq(color, expected);
+    }
+
+    #[test]
+    fn test_interpolate_color_hue() {
+        // red to blue passes magenta, not green
+        let color = interpolate_color(Color::RED, Color::BLUE, 0.5, ColorSpace::Hsl);
+        assert_color_eq(color, Color::rgb(1.0, 0.0, 1.0));
+        // the hue wraps around 360deg
+        let color = interpolate_color(
+            Color::hsl(350.0, 1.0, 0.5),
+            Color::hsl(30.0, 1.0, 0.5),
+            0.25,
+            ColorSpace::Hsl,
+        );
+        assert_color_eq(color, Color::hsl(0.0, 1.0, 0.5));
+        // transparent colors don't darken the result
+        let color = interpolate_color(Color::NONE, Color::WHITE, 0.5, ColorSpace::Srgb);
+        assert_color_eq(color, Color::rgba(1.0, 1.0, 1.0, 0.5));
+    }
+
+    #[test_case(Val::Px(10.0), Val::Px(20.0), 0.5, CalcExpr::Length(Val::Px(15.0)) ; "pixels")]
+    #[test_case(Val::Percent(0.0), Val::Percent(100.0), 0.25, CalcExpr::Length(Val::Percent(25.0)) ; "percentages")]
+    #[test_case(Val::Vw(10.0), Val::Vw(20.0), 1.0, CalcExpr::Length(Val::Vw(20.0)) ; "viewport units")]
+    #[test_case(Val::Auto, Val::Px(20.0), 0.4, CalcExpr::Length(Val::Auto) ; "auto before half")]
+    #[test_case(Val::Auto, Val::Px(20.0), 0.5, CalcExpr::Length(Val::Px(20.0)) ; "auto at half")]
+    fn test_interpolate_val(first: Val, second: Val, t: f32, expected: CalcExpr) {
+        assert_eq!(interpolate_val(first, second, t), expected);
+    }
+
+    #[test_case(0.0, 100.0 ; "start")]
+    #[test_case(0.5, 125.0 ; "half")]
+    #[test_case(1.0, 150.0 ; "end")]
+    fn test_interpolate_val_mixed_units(t: f32, expected: f32) {
+        let context = CalcContext {
+            percent_base: 300.0,
+            viewport: Vec2::ZERO,
+        };
+        let expr = interpolate_val(Val::Px(100.0), Val::Percent(50.0), t);
+        assert!(matches!(expr, CalcExpr::Add(..)));
+        assert_eq!(expr.resolve(&context), Some(expected));
+    }
+
+    #[test]
+    fn test_interpolate_rect() {
+        let rect = interpolate_rect(
+            UiRect::all(Val::Px(10.0)),
+            UiRect::new(
+                Val::Px(20.0),
+                Val::Px(30.0),
+                Val::Percent(10.0),
+                Val::Px(10.0),
+            ),
+            0.5,
+        );
+        assert_eq!(rect.left, CalcExpr::Length(Val::Px(15.0)));
+        assert_eq!(rect.right, CalcExpr::Length(Val::Px(20.0)));
+        assert_eq!(rect.to_rect(), None);
+        let context = CalcContext {
+            percent_base: 200.0,
+            viewport: Vec2::ZERO,
+        };
+        assert_eq!(
+            rect.resolve(&context),
+            Some(UiRect::new(
+                Val::Px(15.0),
+                Val::Px(20.0),
+                Val::Px(15.0),
+                Val::Px(10.0)
+            ))
+        );
+        let rect = interpolate_rect(UiRect::all(Val::Px(0.0)), UiRect::all(Val::Px(10.0)), 0.2);
+        assert_eq!(rect.to_rect(), Some(UiRect::all(Val::Px(2.0))));
+    }
+
+    #[test_case(350.0, 10.0, 0.5, 360.0 ; "wraps forward")]
+    #[test_case(10.0, 350.0, 0.5, 0.0 ; "wraps backward")]
+    #[test_case(0.0, 90.0, 0.5, 45.0 ; "short arc")]
+    #[test_case(0.0, 270.0, 0.5, -45.0 ; "long arc reversed")]
+    #[test_case(730.0, 0.0, 0.5, 5.0 ; "multiple turns")]
+    #[test_case(0.0, -1070.0, 0.5, 5.0 ; "multiple negative turns")]
+    fn test_interpolate_angle(first: f32, second: f32, t: f32, expected: f32) {
+        let angle = interpolate_angle(first.to_radians(), second.to_radians(), t);
+        let difference = (angle - expected.to_radians()).rem_euclid(2.0 * PI);
+        assert!(
+            difference < 1e-4 || 2.0 * PI - difference < 1e-4,
+            "{} != {}",
+            angle.to_degrees(),
+            expected
+        );
+    }
+}
@@ -0,0 +1,637 @@
+use bevy::{render::color::Color, ui::Val};
+use nom::{
+    branch::alt,
+    character::complete::multispace0 as multispace,
+    combinator::{map, opt, verify},
+    multi::separated_list1,
+    sequence::{delimited, preceded, terminated, tuple},
+};
+
+use super::common::{identifier_parser, number_parser, token};
+use super::error::{parse_all, IResult, InputError, ParseError};
+use super::val::val_length_or_zero_parser;
+use super::{angle_parser, color_parser, easing_parser};
+use super::{interpolate_color, interpolate_val, CalcExpr, ColorSpace, Easing};
+
+/// A property of a ui node that can be animated with [`Keyframes`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnimatedProperty {
+    /// `background-color`, the color of the `BackgroundColor`
+    BackgroundColor,
+    /// `border-color`, the color of the `BorderColor`
+    BorderColor,
+    /// `color`, the color of all sections of the `Text`
+    TextColor,
+    Width,
+    Height,
+    MinWidth,
+    MinHeight,
+    MaxWidth,
+    MaxHeight,
+    Left,
+    Right,
+    Top,
+    Bottom,
+    /// `rotate`, the rotation of the `Transform`, clockwise
+    Rotate,
+    /// `scale`, the uniform scale of the `Transform`
+    Scale,
+}
+
+/// Names of the properties supported by [`keyframes_parser`]
+const PROPERTIES: &[(&str, AnimatedProperty)] = &[
+    ("background-color", AnimatedProperty::BackgroundColor),
+    ("border-color", AnimatedProperty::BorderColor),
+    ("color", AnimatedProperty::TextColor),
+    ("width", AnimatedProperty::Width),
+    ("height", AnimatedProperty::Height),
+    ("min-width", AnimatedProperty::MinWidth),
+    ("min-height", AnimatedProperty::MinHeight),
+    ("max-width", AnimatedProperty::MaxWidth),
+    ("max-height", AnimatedProperty::MaxHeight),
+    ("left", AnimatedProperty::Left),
+    ("right", AnimatedProperty::Right),
+    ("top", AnimatedProperty::Top),
+    ("bottom", AnimatedProperty::Bottom),
+    ("rotate", AnimatedProperty::Rotate),
+    ("scale", AnimatedProperty::Scale),
+];
+
+/// The easing function of a keyframe, declared like a property
+const TIMING_FUNCTION: &str = "animation-timing-function";
+
+/// A value of an [`AnimatedProperty`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum KeyframeValue {
+    Color(Color),
+    Val(Val),
+    /// A length between two [`Val`]s of different units, like `10px` and `50%`, resolved
+    /// once the size percentages refer to is known
+    Calc(CalcExpr),
+    /// An angle in radians
+    Angle(f32),
+    Number(f32),
+}
+
+impl KeyframeValue {
+    /// Interpolates between two values, `t` is the fraction of the other value
+    ///
+    /// Colors are interpolated in srgb, `Val`s of different units result in a
+    /// [`KeyframeValue::Calc`], see [`interpolate_val`]. Values that can't be
+    /// interpolated, like `auto`, switch to the other value halfway.
+    pub fn interpolate(&self, other: &KeyframeValue, t: f32) -> KeyframeValue {
+        let lerp = |a: f32, b: f32| a + (b - a) * t;
+        match (self, other) {
+            (KeyframeValue::Color(a), KeyframeValue::Color(b)) => {
+                KeyframeValue::Color(interpolate_color(*a, *b, t, ColorSpace::Srgb))
+            }
+            (KeyframeValue::Val(a), KeyframeValue::Val(b)) => match interpolate_val(*a, *b, t) {
+                CalcExpr::Length(val) => KeyframeValue::Val(val),
+                expr => KeyframeValue::Calc(expr),
+            },
+            (KeyframeValue::Angle(a), KeyframeValue::Angle(b)) => {
+                KeyframeValue::Angle(lerp(*a, *b))
+            }
+            (KeyframeValue::Number(a), KeyframeValue::Number(b)) => {
+                KeyframeValue::Number(lerp(*a, *b))
+            }
+            (a, b) => {
+                if t < 0.5 {
+                    a.clone()
+                } else {
+                    b.clone()
+                }
+            }
+        }
+    }
+}
+
+/// A value of a property at an offset of the animation
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keyframe {
+    /// The offset between `0.0` and `1.0`, like `0.5` for `50%`
+    pub offset: f32,
+    pub value: KeyframeValue,
+    /// The easing function up to the next keyframe, overrides that of the animation
+    pub easing: Option<Easing>,
+}
+
+/// The keyframes of a single property, sorted by offset
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyframeTrack {
+    pub property: AnimatedProperty,
+    pub keyframes: Vec<Keyframe>,
+}
+
+impl KeyframeTrack {
+    /// Returns the value of the property at the progress of the animation
+    ///
+    /// Like in css, the base value, the value of the property without the animation,
+    /// serves as the implicit keyframe at `0%` and `100%` if the track has none there.
+    /// Without base value the first and last keyframes are held. The easing function
+    /// applies to each interval between two keyframes.
+    pub fn sample(
+        &self,
+        progress: f32,
+        easing: &Easing,
+        base: Option<&KeyframeValue>,
+    ) -> Option<KeyframeValue> {
+        let implicit = |offset: f32| {
+            base.map(|value| Keyframe {
+                offset,
+                value: value.clone(),
+                easing: None,
+            })
+        };
+        let start = implicit(0.0).filter(|_| {
+            !self
+                .keyframes
+                .first()
+                .is_some_and(|keyframe| keyframe.offset <= 0.0)
+        });
+        let end = implicit(1.0).filter(|_| {
+            !self
+                .keyframes
+                .last()
+                .is_some_and(|keyframe| keyframe.offset >= 1.0)
+        });
+        let keyframes: Vec<&Keyframe> = start
+            .iter()
+            .chain(self.keyframes.iter())
+            .chain(end.iter())
+            .collect();
+        let index = keyframes
+            .iter()
+            .rposition(|keyframe| keyframe.offset <= progress);
+        let Some(index) = index else {
+            return keyframes.first().map(|keyframe| keyframe.value.clone());
+        };
+        let (a, b) = match (keyframes.get(index), keyframes.get(index + 1)) {
+            (Some(a), Some(b)) => (a, b),
+            (a, _) => return a.map(|keyframe| keyframe.value.clone()),
+        };
+        let t = (progress - a.offset) / (b.offset - a.offset);
+        let t = a.easing.as_ref().unwrap_or(easing).sample(t);
+        Some(a.value.interpolate(&b.value, t))
+    }
+}
+
+/// An `@keyframes` animation definition, with a track for each animated property
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Keyframes {
+    pub name: String,
+    pub tracks: Vec<KeyframeTrack>,
+}
+
+impl Keyframes {
+    /// Returns the values of all properties at the progress of the animation
+    ///
+    /// `base` returns the value of a property without the animation, the implicit
+    /// start and end keyframe, see [`KeyframeTrack::sample`].
+    pub fn sample(
+        &self,
+        progress: f32,
+        easing: &Easing,
+        mut base: impl FnMut(AnimatedProperty) -> Option<KeyframeValue>,
+    ) -> Vec<(AnimatedProperty, KeyframeValue)> {
+        self.tracks
+            .iter()
+            .filter_map(|track| {
+                track
+                    .sample(progress, easing, base(track.property).as_ref())
+                    .map(|value| (track.property, value))
+            })
+            .collect()
+    }
+
+    /// Adds a keyframe to the track of the property, replacing one at the same offset
+    fn insert(&mut self, property: AnimatedProperty, keyframe: Keyframe) {
+        let index = match self
+            .tracks
+            .iter()
+            .position(|track| track.property == property)
+        {
+            Some(index) => index,
+            None => {
+                self.tracks.push(KeyframeTrack {
+                    property,
+                    keyframes: Vec::new(),
+                });
+                self.tracks.len() - 1
+            }
+        };
+        let keyframes = &mut self.tracks[index].keyframes;
+        match keyframes
+            .iter()
+            .position(|other| other.offset >= keyframe.offset)
+        {
+            Some(index) if keyframes[index].offset == keyframe.offset => {
+                keyframes[index] = keyframe
+            }
+            Some(index) => keyframes.insert(index, keyframe),
+            None => keyframes.push(keyframe),
+        }
+    }
+}
+
+/// A declaration within a keyframe block
+enum KeyframeDeclaration {
+    Value(AnimatedProperty, KeyframeValue),
+    Easing(Easing),
+}
+
+/// Parses the value of the property
+fn keyframe_value_parser(
+    property: AnimatedProperty,
+) -> impl FnMut(&str) -> IResult<&str, KeyframeValue> {
+    move |input| match property {
+        AnimatedProperty::BackgroundColor
+        | AnimatedProperty::BorderColor
+        | AnimatedProperty::TextColor => map(color_parser, KeyframeValue::Color)(input),
+        AnimatedProperty::Rotate => map(angle_parser, KeyframeValue::Angle)(input),
+        AnimatedProperty::Scale => map(number_parser, KeyframeValue::Number)(input),
+        _ => map(
+            alt((map(token("auto"), |_| Val::Auto), val_length_or_zero_parser)),
+            KeyframeValue::Val,
+        )(input),
+    }
+}
+
+/// Parses a single declaration, like `width: 50%`
+fn keyframe_declaration_parser(input: &str) -> IResult<&str, KeyframeDeclaration> {
+    let (rest, name) = identifier_parser(input).map_err(|error| {
+        error.map(|error| InputError {
+            expected: vec!["<property name>"],
+            ..error
+        })
+    })?;
+    let property = PROPERTIES
+        .iter()
+        .find(|(property_name, _)| *property_name == name)
+        .map(|(_, property)| *property);
+    if property.is_none() && name != TIMING_FUNCTION {
+        let mut expected: Vec<_> = PROPERTIES.iter().map(|(name, _)| *name).collect();
+        expected.push(TIMING_FUNCTION);
+        return Err(nom::Err::Error(InputError { input, expected }));
+    }
+    let (rest, _) = tuple((multispace, token(":"), multispace))(rest)?;
+    let (rest, declaration) = match property {
+        Some(property) => map(keyframe_value_parser(property), |value| {
+            KeyframeDeclaration::Value(property, value)
+        })(rest)?,
+        None => map(easing_parser, KeyframeDeclaration::Easing)(rest)?,
+    };
+    multispace(rest).map(|(rest, _)| (rest, declaration))
+}
+
+/// Parses the declarations of a keyframe block between braces, separated by `;`
+fn keyframe_block_parser(input: &str) -> IResult<&str, Vec<KeyframeDeclaration>> {
+    let (mut input, _) = tuple((token("{"), multispace))(input)?;
+    let mut declarations = Vec::new();
+    loop {
+        // empty declarations are allowed, like in `width: 10px;;`
+        while let (rest, Some(_)) = opt(preceded(token(";"), multispace))(input)? {
+            input = rest;
+        }
+        if let (rest, Some(_)) = opt(token("}"))(input)? {
+            return Ok((rest, declarations));
+        }
+        let (rest, declaration) = keyframe_declaration_parser(input)?;
+        declarations.push(declaration);
+        match opt(preceded(token(";"), multispace))(rest)? {
+            (rest, Some(_)) => input = rest,
+            (rest, None) => {
+                let (rest, _) = token("}")(rest)?;
+                return Ok((rest, declarations));
+            }
+        }
+    }
+}
+
+/// Parses a keyframe selector, like `from`, `to` or `50%`
+fn keyframe_selector_parser(input: &str) -> IResult<&str, f32> {
+    delimited(
+        multispace,
+        alt((
+            map(token("from"), |_| 0.0),
+            map(token("to"), |_| 1.0),
+            map(
+                verify(terminated(number_parser, token("%")), |value| {
+                    (0.0..=100.0).contains(value)
+                }),
+                |value| value / 100.0,
+            ),
+        )),
+        multispace,
+    )(input)
+}
+
+/// Parser for strings that represent an `@keyframes` rule into [`Keyframes`].
+///
+/// The syntax follows CSS, like `@keyframes pulse { 0% { width: 100px } 50%, 75% { width: 120px } to { width: 100px } }`:
+///
+/// * `from`, `to`, `50%` -> keyframe selectors, a block can have several separated by commas
+/// * `background-color`, `border-color`, `color` -> colors, as in [`super::color_parser`]
+/// * `width`, `height`, `min-width`, `min-height`, `max-width`, `max-height`, `left`,
+///   `right`, `top`, `bottom` -> lengths, as in [`super::val_parser`], a zero length may
+///   omit the unit
+/// * `rotate` -> an angle, as in [`super::angle_parser`]
+/// * `scale` -> a number
+/// * `animation-timing-function` -> the easing up to the next keyframe, as in [`super::easing_parser`]
+///
+/// Later keyframes override earlier ones with the same offset.
+pub fn keyframes_parser(input: &str) -> IResult<&str, Keyframes> {
+    let (input, (_, _, name, _, _)) = tuple((
+        multispace,
+        token("@keyframes"),
+        preceded(multispace, identifier_parser),
+        multispace,
+        token("{"),
+    ))(input)?;
+    let mut keyframes = Keyframes {
+        name: name.to_string(),
+        tracks: Vec::new(),
+    };
+    let (mut input, _) = multispace(input)?;
+    while let (rest, None) = opt(token("}"))(input)? {
+        let (rest, (offsets, declarations)) = tuple((
+            separated_list1(token(","), keyframe_selector_parser),
+            keyframe_block_parser,
+        ))(rest)?;
+        let easing = declarations
+            .iter()
+            .rev()
+            .find_map(|declaration| match declaration {
+                KeyframeDeclaration::Easing(easing) => Some(easing.clone()),
+                _ => None,
+            });
+        for offset in offsets {
+            for declaration in declarations.iter() {
+                if let KeyframeDeclaration::Value(property, value) = declaration {
+                    keyframes.insert(
+                        *property,
+                        Keyframe {
+                            offset,
+                            value: value.clone(),
+                            easing: easing.clone(),
+                        },
+                    );
+                }
+            }
+        }
+        (input, _) = multispace(rest)?;
+    }
+    let (input, _) = tuple((token("}"), multispace))(input)?;
+    Ok((input, keyframes))
+}
+
+/// Wrapper for [`keyframes_parser`] that returns optional [`Keyframes`]
+pub fn keyframes_string_parser(input: &str) -> Option<Keyframes> {
+    keyframes_parser(input).map(|(_, value)| value).ok()
+}
+
+/// Wrapper for [`keyframes_parser`] that returns a [`ParseError`] on failure
+///
+/// Unlike [`keyframes_string_parser`] the whole input must be consumed.
+pub fn keyframes_result_parser(input: &str) -> Result<Keyframes, ParseError> {
+    parse_all(input, keyframes_parser)
+}
+
+/// Wrapper for [`keyframes_parser`] that implements a serde deserializer
+#[cfg(feature = "serde")]
+pub fn keyframes_serde_parser<'de, D>(deserializer: D) -> Result<Keyframes, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::Error;
+    // keyframes usually span multiple lines, so the string is escaped and can't be borrowed
+    let s: String = serde::Deserialize::deserialize(deserializer)?;
+    keyframes_result_parser(&s)
+        .map_err(|error| D::Error::custom(format!("invalid keyframes string: {}", error)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CalcContext;
+    use test_case::test_case;
+
+    const PULSE: &str = "@keyframes pulse {
+        0% { background-color: red; width: 100px }
+        50% { width: 120px; animation-timing-function: ease-in; }
+        100% { background-color: blue; width: 100px }
+    }";
+
+    #[test]
+    fn test_keyframes_parser() {
+        let keyframes = keyframes_result_parser(PULSE).unwrap();
+        assert_eq!(keyframes.name, "pulse");
+        assert_eq!(keyframes.tracks.len(), 2);
+        let colors = &keyframes.tracks[0];
+        assert_eq!(colors.property, AnimatedProperty::BackgroundColor);
+        assert_eq!(
+            colors
+                .keyframes
+                .iter()
+                .map(|keyframe| keyframe.offset)
+                .collect::<Vec<_>>(),
+            vec![0.0, 1.0]
+        );
+        let widths = &keyframes.tracks[1];
+        assert_eq!(widths.property, AnimatedProperty::Width);
+        assert_eq!(widths.keyframes.len(), 3);
+        assert_eq!(
+            widths.keyframes[1].value,
+            KeyframeValue::Val(Val::Px(120.0))
+        );
+        assert_eq!(widths.keyframes[1].easing, Some(Easing::EASE_IN));
+        assert_eq!(widths.keyframes[0].easing, None);
+    }
+
+    #[test]
+    fn test_keyframes_parser_selectors() {
+        let keyframes = keyframes_result_parser(
+            "@keyframes spin { to { rotate: 1turn } from, 50% { rotate: 0deg; scale: 2 } 50% { rotate: 90deg } }",
+        )
+        .unwrap();
+        let rotations = &keyframes.tracks[0];
+        assert_eq!(rotations.property, AnimatedProperty::Rotate);
+        assert_eq!(
+            rotations
+                .keyframes
+                .iter()
+                .map(|keyframe| (keyframe.offset, keyframe.value.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                (0.0, KeyframeValue::Angle(0.0)),
+                (0.5, KeyframeValue::Angle(90f32.to_radians())),
+                (1.0, KeyframeValue::Angle(360f32.to_radians())),
+            ]
+        );
+        assert_eq!(keyframes.tracks[1].property, AnimatedProperty::Scale);
+        assert_eq!(keyframes.tracks[1].keyframes.len(), 2);
+        assert!(keyframes_result_parser("@keyframes empty {}").is_ok());
+
+        let keyframes =
+            keyframes_result_parser("@keyframes grow { from { width: 0 } to { width: auto } }")
+                .unwrap();
+        assert_eq!(
+            keyframes.tracks[0].keyframes[0].value,
+            KeyframeValue::Val(Val::Px(0.0))
+        );
+        assert_eq!(
+            keyframes.tracks[0].keyframes[1].value,
+            KeyframeValue::Val(Val::Auto)
+        );
+    }
+
+    #[test_case(0.0, Val::Px(100.0) ; "start")]
+    #[test_case(0.25, Val::Px(110.0) ; "first interval")]
+    #[test_case(0.75, Val::Px(120.0 - 20.0 * 0.3153) ; "eased interval")]
+    #[test_case(1.0, Val::Px(100.0) ; "end")]
+    fn test_keyframes_sample(progress: f32, expected: Val) {
+        let keyframes = keyframes_result_parser(PULSE).unwrap();
+        let values = keyframes.sample(progress, &Easing::Linear, |_| None);
+        let Some((_, KeyframeValue::Val(Val::Px(width)))) = values
+            .iter()
+            .find(|(property, _)| *property == AnimatedProperty::Width)
+        else {
+            panic!("no width in {:?}", values);
+        };
+        let Val::Px(expected) = expected else {
+            unreachable!()
+        };
+        assert!((width - expected).abs() < 1e-2, "{} != {}", width, expected);
+    }
+
+    #[test]
+    fn test_keyframes_sample_base() {
+        let keyframes =
+            keyframes_result_parser("@keyframes grow { 25% { height: 10% } 75% { height: 50% } }")
+                .unwrap();
+        let base = |_| Some(KeyframeValue::Val(Val::Percent(0.0)));
+        let height = |progress| {
+            keyframes
+                .sample(progress, &Easing::Linear, base)
+                .remove(0)
+                .1
+        };
+        assert_eq!(height(0.0), KeyframeValue::Val(Val::Percent(0.0)));
+        assert_eq!(height(0.125), KeyframeValue::Val(Val::Percent(5.0)));
+        assert_eq!(height(0.5), KeyframeValue::Val(Val::Percent(30.0)));
+        assert_eq!(height(0.875), KeyframeValue::Val(Val::Percent(25.0)));
+        assert_eq!(height(1.0), KeyframeValue::Val(Val::Percent(0.0)));
+
+        let keyframes = keyframes_result_parser("@keyframes grow { to { width: 100px } }").unwrap();
+        let base = |_| Some(KeyframeValue::Val(Val::Px(20.0)));
+        let width = |progress| {
+            keyframes
+                .sample(progress, &Easing::Linear, base)
+                .remove(0)
+                .1
+        };
+        assert_eq!(width(0.0), KeyframeValue::Val(Val::Px(20.0)));
+        assert_eq!(width(0.5), KeyframeValue::Val(Val::Px(60.0)));
+        assert_eq!(width(1.0), KeyframeValue::Val(Val::Px(100.0)));
+    }
+
+    #[test]
+    fn test_keyframes_sample_without_base() {
+        let keyframes =
+            keyframes_result_parser("@keyframes grow { 25% { height: 10% } 75% { height: 50% } }")
+                .unwrap();
+        let height = |progress| {
+            keyframes
+                .sample(progress, &Easing::Linear, |_| None)
+                .remove(0)
+                .1
+        };
+        assert_eq!(height(0.0), KeyframeValue::Val(Val::Percent(10.0)));
+        assert_eq!(height(0.5), KeyframeValue::Val(Val::Percent(30.0)));
+        assert_eq!(height(1.0), KeyframeValue::Val(Val::Percent(50.0)));
+    }
+
+    #[test]
+    fn test_keyframes_sample_units() {
+        let keyframes =
+            keyframes_result_parser("@keyframes grow { from { width: 100px } to { width: 50% } }")
+                .unwrap();
+        let context = CalcContext {
+            percent_base: 400.0,
+            viewport: Default::default(),
+        };
+        let width = |progress| match keyframes
+            .sample(progress, &Easing::Linear, |_| None)
+            .remove(0)
+            .1
+        {
+            KeyframeValue::Calc(expr) => expr.resolve(&context),
+            value => panic!("{:?} is not a calc expression", value),
+        };
+        assert_eq!(width(0.25), Some(125.0));
+        assert_eq!(width(0.75), Some(175.0));
+    }
+
+    #[test_case(KeyframeValue::Val(Val::Px(10.0)), KeyframeValue::Val(Val::Percent(50.0)), 0.4, KeyframeValue::Calc(interpolate_val(Val::Px(10.0), Val::Percent(50.0), 0.4)) ; "different units")]
+    #[test_case(KeyframeValue::Val(Val::Px(10.0)), KeyframeValue::Val(Val::Auto), 0.5, KeyframeValue::Val(Val::Auto) ; "auto at half")]
+    #[test_case(KeyframeValue::Number(1.0), KeyframeValue::Number(2.0), 0.25, KeyframeValue::Number(1.25) ; "numbers")]
+    #[test_case(KeyframeValue::Color(Color::BLACK), KeyframeValue::Color(Color::WHITE), 0.5, KeyframeValue::Color(Color::rgb(0.5, 0.5, 0.5)) ; "colors")]
+    fn test_keyframe_value_interpolate(
+        a: KeyframeValue,
+        b: KeyframeValue,
+        t: f32,
+        expected: KeyframeValue,
+    ) {
+        assert_eq!(a.interpolate(&b, t), expected);
+    }
+
+    #[test]
+    fn test_keyframes_parser_errors() {
+        let error = keyframes_result_parser("@keyframes a { 0% { widht: 10px } }").unwrap_err();
+        assert_eq!(error.fragment, "widht");
+        assert!(error.expected.contains(&"width"));
+        assert!(error.expected.contains(&"animation-timing-function"));
+
+        let input = "@keyframes a { 0% { rotate: 10px } }";
+        let error = keyframes_result_parser(input).unwrap_err();
+        assert_eq!(error.offset, input.find("px").unwrap());
+
+        assert!(keyframes_result_parser("@keyframes a { 120% { width: 10px } }").is_err());
+        assert!(keyframes_result_parser("@keyframes a { 0% { width: 10px }").is_err());
+        assert!(keyframes_result_parser("@keyframes { 0% { width: 10px } }").is_err());
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests_serde {
+    use super::{AnimatedProperty, Keyframes};
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    pub struct Foo {
+        #[serde(deserialize_with = "super::keyframes_serde_parser")]
+        pub keyframes: Keyframes,
+    }
+
+    #[test]
+    fn test_keyframes_serde_parser() {
+        let foo: Foo = serde_json::from_str(
+            r#"{"keyframes": "@keyframes fade { from { color: #fff } to { color: rgba(255, 255, 255, 0) } }"}"#,
+        )
+        .unwrap();
+        assert_eq!(foo.keyframes.name, "fade");
+        assert_eq!(
+            foo.keyframes.tracks[0].property,
+            AnimatedProperty::TextColor
+        );
+        assert!(serde_json::from_str::<Foo>(r#"{"keyframes": "@keyframes fade {"}"#).is_err());
+    }
+
+    #[test]
+    fn test_keyframes_serde_parser_escaped() {
+        let foo: Foo =
+            serde_json::from_str(r#"{"keyframes": "@keyframes grow {\n  to { width: 100px }\n}"}"#)
+                .unwrap();
+        assert_eq!(foo.keyframes.name, "grow");
+    }
+}
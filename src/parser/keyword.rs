@@ -0,0 +1,738 @@
+use bevy::{
+    render::view::Visibility,
+    ui::{
+        AlignContent, AlignItems, AlignSelf, Direction, Display, FlexDirection, FlexWrap,
+        GridAutoFlow, JustifyContent, JustifyItems, JustifySelf, Overflow, OverflowAxis,
+        PositionType,
+    },
+};
+use nom::{
+    character::complete::{multispace0 as multispace, multispace1},
+    combinator::opt,
+    sequence::{delimited, preceded},
+};
+
+use super::common::identifier_parser;
+use super::error::{parse_all, IResult, InputError, ParseError};
+
+/// Generates the `*_string_parser`, `*_result_parser` and serde wrappers of a keyword parser
+macro_rules! keyword_wrappers {
+    (
+        $type:ident,
+        $name:literal,
+        $parser:ident,
+        $string_parser:ident,
+        $result_parser:ident,
+        $formatter:ident,
+        $serde_parser:ident,
+        $serde_serializer:ident
+    ) => {
+        #[doc = concat!("Wrapper for [`", stringify!($parser), "`] that returns an optional [`", stringify!($type), "`]")]
+        pub fn $string_parser(input: &str) -> Option<$type> {
+            $parser(input).map(|(_, value)| value).ok()
+        }
+
+        #[doc = concat!("Wrapper for [`", stringify!($parser), "`] that returns a [`ParseError`] on failure")]
+        ///
+        #[doc = concat!("Unlike [`", stringify!($string_parser), "`] the whole input must be consumed.")]
+        pub fn $result_parser(input: &str) -> Result<$type, ParseError> {
+            parse_all(input, $parser)
+        }
+
+        #[doc = concat!("Wrapper for [`", stringify!($parser), "`] that implements a serde deserializer")]
+        #[cfg(feature = "serde")]
+        pub fn $serde_parser<'de, D>(deserializer: D) -> Result<$type, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            use serde::de::Error;
+            let s: &str = serde::Deserialize::deserialize(deserializer)?;
+            $result_parser(s).map_err(|error| {
+                D::Error::custom(format!(concat!("invalid ", $name, " string: {}"), error))
+            })
+        }
+
+        #[doc = concat!("Wrapper for [`", stringify!($formatter), "`] that implements a serde serializer")]
+        #[cfg(feature = "serde")]
+        pub fn $serde_serializer<S>(value: &$type, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            serializer.serialize_str(&$formatter(*value))
+        }
+    };
+}
+
+/// Parses one of the keywords of the table surrounded by optional whitespace
+fn keyword_parser<'a, T: Copy>(
+    input: &'a str,
+    keywords: &[(&'static str, T)],
+) -> IResult<&'a str, T> {
+    delimited(
+        multispace,
+        |input| keyword_token_parser(input, keywords),
+        multispace,
+    )(input)
+}
+
+/// Parses one of the keywords of the table, the keywords are reported as expected on failure
+fn keyword_token_parser<'a, T: Copy>(
+    input: &'a str,
+    keywords: &[(&'static str, T)],
+) -> IResult<&'a str, T> {
+    let error = || {
+        nom::Err::Error(InputError {
+            input,
+            expected: keywords.iter().map(|(keyword, _)| *keyword).collect(),
+        })
+    };
+    let (rest, name) = identifier_parser(input).map_err(|_| error())?;
+    keywords
+        .iter()
+        .find(|(keyword, _)| *keyword == name)
+        .map(|(_, value)| (rest, *value))
+        .ok_or_else(error)
+}
+
+/// Returns the first keyword of the table for the value
+fn keyword_formatter<T: PartialEq>(value: T, keywords: &[(&'static str, T)]) -> String {
+    keywords
+        .iter()
+        .find(|(_, keyword_value)| *keyword_value == value)
+        .map(|(keyword, _)| keyword.to_string())
+        .expect("every variant has a keyword")
+}
+
+/// Css keywords of [`Display`]
+const DISPLAY_KEYWORDS: &[(&str, Display)] = &[
+    ("flex", Display::Flex),
+    ("grid", Display::Grid),
+    ("none", Display::None),
+];
+
+/// Parses a [`Display`] keyword: `flex`, `grid` or `none`
+pub fn display_parser(input: &str) -> IResult<&str, Display> {
+    keyword_parser(input, DISPLAY_KEYWORDS)
+}
+
+/// Formats a [`Display`] as its css keyword, the counterpart of [`display_parser`]
+pub fn display_formatter(value: Display) -> String {
+    keyword_formatter(value, DISPLAY_KEYWORDS)
+}
+
+/// Css keywords of [`PositionType`]
+const POSITION_TYPE_KEYWORDS: &[(&str, PositionType)] = &[
+    ("relative", PositionType::Relative),
+    ("absolute", PositionType::Absolute),
+];
+
+/// Parses a [`PositionType`] keyword: `relative` or `absolute`
+pub fn position_type_parser(input: &str) -> IResult<&str, PositionType> {
+    keyword_parser(input, POSITION_TYPE_KEYWORDS)
+}
+
+/// Formats a [`PositionType`] as its css keyword, the counterpart of [`position_type_parser`]
+pub fn position_type_formatter(value: PositionType) -> String {
+    keyword_formatter(value, POSITION_TYPE_KEYWORDS)
+}
+
+/// Css keywords of [`FlexDirection`]
+const FLEX_DIRECTION_KEYWORDS: &[(&str, FlexDirection)] = &[
+    ("row", FlexDirection::Row),
+    ("column", FlexDirection::Column),
+    ("row-reverse", FlexDirection::RowReverse),
+    ("column-reverse", FlexDirection::ColumnReverse),
+];
+
+/// Parses a [`FlexDirection`] keyword, like `row` or `column-reverse`
+pub fn flex_direction_parser(input: &str) -> IResult<&str, FlexDirection> {
+    keyword_parser(input, FLEX_DIRECTION_KEYWORDS)
+}
+
+/// Formats a [`FlexDirection`] as its css keyword, the counterpart of [`flex_direction_parser`]
+pub fn flex_direction_formatter(value: FlexDirection) -> String {
+    keyword_formatter(value, FLEX_DIRECTION_KEYWORDS)
+}
+
+/// Css keywords of [`FlexWrap`]
+const FLEX_WRAP_KEYWORDS: &[(&str, FlexWrap)] = &[
+    ("nowrap", FlexWrap::NoWrap),
+    ("wrap", FlexWrap::Wrap),
+    ("wrap-reverse", FlexWrap::WrapReverse),
+];
+
+/// Parses a [`FlexWrap`] keyword: `nowrap`, `wrap` or `wrap-reverse`
+pub fn flex_wrap_parser(input: &str) -> IResult<&str, FlexWrap> {
+    keyword_parser(input, FLEX_WRAP_KEYWORDS)
+}
+
+/// Formats a [`FlexWrap`] as its css keyword, the counterpart of [`flex_wrap_parser`]
+pub fn flex_wrap_formatter(value: FlexWrap) -> String {
+    keyword_formatter(value, FLEX_WRAP_KEYWORDS)
+}
+
+/// Css keywords of [`AlignItems`]
+const ALIGN_ITEMS_KEYWORDS: &[(&str, AlignItems)] = &[
+    ("normal", AlignItems::Default),
+    ("start", AlignItems::Start),
+    ("end", AlignItems::End),
+    ("flex-start", AlignItems::FlexStart),
+    ("flex-end", AlignItems::FlexEnd),
+    ("center", AlignItems::Center),
+    ("baseline", AlignItems::Baseline),
+    ("stretch", AlignItems::Stretch),
+];
+
+/// Parses an [`AlignItems`] keyword, like `flex-start` or `normal` for the default
+pub fn align_items_parser(input: &str) -> IResult<&str, AlignItems> {
+    keyword_parser(input, ALIGN_ITEMS_KEYWORDS)
+}
+
+/// Formats a [`AlignItems`] as its css keyword, the counterpart of [`align_items_parser`]
+pub fn align_items_formatter(value: AlignItems) -> String {
+    keyword_formatter(value, ALIGN_ITEMS_KEYWORDS)
+}
+
+/// Css keywords of [`AlignSelf`]
+const ALIGN_SELF_KEYWORDS: &[(&str, AlignSelf)] = &[
+    ("auto", AlignSelf::Auto),
+    ("start", AlignSelf::Start),
+    ("end", AlignSelf::End),
+    ("flex-start", AlignSelf::FlexStart),
+    ("flex-end", AlignSelf::FlexEnd),
+    ("center", AlignSelf::Center),
+    ("baseline", AlignSelf::Baseline),
+    ("stretch", AlignSelf::Stretch),
+];
+
+/// Parses an [`AlignSelf`] keyword, like `flex-end` or `auto`
+pub fn align_self_parser(input: &str) -> IResult<&str, AlignSelf> {
+    keyword_parser(input, ALIGN_SELF_KEYWORDS)
+}
+
+/// Formats a [`AlignSelf`] as its css keyword, the counterpart of [`align_self_parser`]
+pub fn align_self_formatter(value: AlignSelf) -> String {
+    keyword_formatter(value, ALIGN_SELF_KEYWORDS)
+}
+
+/// Css keywords of [`AlignContent`]
+const ALIGN_CONTENT_KEYWORDS: &[(&str, AlignContent)] = &[
+    ("normal", AlignContent::Default),
+    ("start", AlignContent::Start),
+    ("end", AlignContent::End),
+    ("flex-start", AlignContent::FlexStart),
+    ("flex-end", AlignContent::FlexEnd),
+    ("center", AlignContent::Center),
+    ("stretch", AlignContent::Stretch),
+    ("space-between", AlignContent::SpaceBetween),
+    ("space-evenly", AlignContent::SpaceEvenly),
+    ("space-around", AlignContent::SpaceAround),
+];
+
+/// Parses an [`AlignContent`] keyword, like `space-between` or `normal` for the default
+pub fn align_content_parser(input: &str) -> IResult<&str, AlignContent> {
+    keyword_parser(input, ALIGN_CONTENT_KEYWORDS)
+}
+
+/// Formats a [`AlignContent`] as its css keyword, the counterpart of [`align_content_parser`]
+pub fn align_content_formatter(value: AlignContent) -> String {
+    keyword_formatter(value, ALIGN_CONTENT_KEYWORDS)
+}
+
+/// Css keywords of [`JustifyContent`]
+const JUSTIFY_CONTENT_KEYWORDS: &[(&str, JustifyContent)] = &[
+    ("normal", JustifyContent::Default),
+    ("start", JustifyContent::Start),
+    ("end", JustifyContent::End),
+    ("flex-start", JustifyContent::FlexStart),
+    ("flex-end", JustifyContent::FlexEnd),
+    ("center", JustifyContent::Center),
+    ("stretch", JustifyContent::Stretch),
+    ("space-between", JustifyContent::SpaceBetween),
+    ("space-evenly", JustifyContent::SpaceEvenly),
+    ("space-around", JustifyContent::SpaceAround),
+];
+
+/// Parses a [`JustifyContent`] keyword, like `space-between` or `normal` for the default
+pub fn justify_content_parser(input: &str) -> IResult<&str, JustifyContent> {
+    keyword_parser(input, JUSTIFY_CONTENT_KEYWORDS)
+}
+
+/// Formats a [`JustifyContent`] as its css keyword, the counterpart of [`justify_content_parser`]
+pub fn justify_content_formatter(value: JustifyContent) -> String {
+    keyword_formatter(value, JUSTIFY_CONTENT_KEYWORDS)
+}
+
+/// Css keywords of [`JustifyItems`]
+const JUSTIFY_ITEMS_KEYWORDS: &[(&str, JustifyItems)] = &[
+    ("normal", JustifyItems::Default),
+    ("start", JustifyItems::Start),
+    ("end", JustifyItems::End),
+    ("center", JustifyItems::Center),
+    ("baseline", JustifyItems::Baseline),
+    ("stretch", JustifyItems::Stretch),
+];
+
+/// Parses a [`JustifyItems`] keyword, like `center` or `normal` for the default
+pub fn justify_items_parser(input: &str) -> IResult<&str, JustifyItems> {
+    keyword_parser(input, JUSTIFY_ITEMS_KEYWORDS)
+}
+
+/// Formats a [`JustifyItems`] as its css keyword, the counterpart of [`justify_items_parser`]
+pub fn justify_items_formatter(value: JustifyItems) -> String {
+    keyword_formatter(value, JUSTIFY_ITEMS_KEYWORDS)
+}
+
+/// Css keywords of [`JustifySelf`]
+const JUSTIFY_SELF_KEYWORDS: &[(&str, JustifySelf)] = &[
+    ("auto", JustifySelf::Auto),
+    ("start", JustifySelf::Start),
+    ("end", JustifySelf::End),
+    ("center", JustifySelf::Center),
+    ("baseline", JustifySelf::Baseline),
+    ("stretch", JustifySelf::Stretch),
+];
+
+/// Parses a [`JustifySelf`] keyword, like `center` or `auto`
+pub fn justify_self_parser(input: &str) -> IResult<&str, JustifySelf> {
+    keyword_parser(input, JUSTIFY_SELF_KEYWORDS)
+}
+
+/// Formats a [`JustifySelf`] as its css keyword, the counterpart of [`justify_self_parser`]
+pub fn justify_self_formatter(value: JustifySelf) -> String {
+    keyword_formatter(value, JUSTIFY_SELF_KEYWORDS)
+}
+
+/// Css keywords of [`Direction`]
+const DIRECTION_KEYWORDS: &[(&str, Direction)] = &[
+    ("inherit", Direction::Inherit),
+    ("ltr", Direction::LeftToRight),
+    ("rtl", Direction::RightToLeft),
+];
+
+/// Parses a [`Direction`] keyword: `inherit`, `ltr` or `rtl`
+pub fn direction_parser(input: &str) -> IResult<&str, Direction> {
+    keyword_parser(input, DIRECTION_KEYWORDS)
+}
+
+/// Formats a [`Direction`] as its css keyword, the counterpart of [`direction_parser`]
+pub fn direction_formatter(value: Direction) -> String {
+    keyword_formatter(value, DIRECTION_KEYWORDS)
+}
+
+/// Css keywords of [`Visibility`]
+const VISIBILITY_KEYWORDS: &[(&str, Visibility)] = &[
+    ("inherit", Visibility::Inherited),
+    ("hidden", Visibility::Hidden),
+    ("visible", Visibility::Visible),
+];
+
+/// Parses a [`Visibility`] keyword: `inherit`, `hidden` or `visible`
+pub fn visibility_parser(input: &str) -> IResult<&str, Visibility> {
+    keyword_parser(input, VISIBILITY_KEYWORDS)
+}
+
+/// Formats a [`Visibility`] as its css keyword, the counterpart of [`visibility_parser`]
+pub fn visibility_formatter(value: Visibility) -> String {
+    keyword_formatter(value, VISIBILITY_KEYWORDS)
+}
+
+/// Css keywords of [`OverflowAxis`]
+const OVERFLOW_AXIS_KEYWORDS: &[(&str, OverflowAxis)] = &[
+    ("visible", OverflowAxis::Visible),
+    ("clip", OverflowAxis::Clip),
+];
+
+/// Parses an [`OverflowAxis`] keyword: `visible` or `clip`
+pub fn overflow_axis_parser(input: &str) -> IResult<&str, OverflowAxis> {
+    keyword_parser(input, OVERFLOW_AXIS_KEYWORDS)
+}
+
+/// Formats a [`OverflowAxis`] as its css keyword, the counterpart of [`overflow_axis_parser`]
+pub fn overflow_axis_formatter(value: OverflowAxis) -> String {
+    keyword_formatter(value, OVERFLOW_AXIS_KEYWORDS)
+}
+
+/// Parses an [`Overflow`] from one or two axis keywords, like `clip` or `clip visible`
+///
+/// As in css, a single keyword applies to both axes, otherwise the first keyword
+/// is used for the x axis and the second for the y axis.
+pub fn overflow_parser(input: &str) -> IResult<&str, Overflow> {
+    let axis = |input| keyword_token_parser(input, OVERFLOW_AXIS_KEYWORDS);
+    let (input, x) = preceded(multispace, axis)(input)?;
+    let (input, y) = opt(preceded(multispace1, axis))(input)?;
+    let (input, _) = multispace(input)?;
+    Ok((
+        input,
+        Overflow {
+            x,
+            y: y.unwrap_or(x),
+        },
+    ))
+}
+
+/// Formats an [`Overflow`] as one or two axis keywords, the counterpart of [`overflow_parser`]
+pub fn overflow_formatter(value: Overflow) -> String {
+    if value.x == value.y {
+        overflow_axis_formatter(value.x)
+    } else {
+        format!(
+            "{} {}",
+            overflow_axis_formatter(value.x),
+            overflow_axis_formatter(value.y)
+        )
+    }
+}
+
+/// Parses a [`GridAutoFlow`] from the keywords `row` or `column`, optionally followed
+/// or preceded by `dense`, or `dense` alone for `row dense`
+pub fn grid_auto_flow_parser(input: &str) -> IResult<&str, GridAutoFlow> {
+    delimited(multispace, grid_auto_flow_keywords_parser, multispace)(input)
+}
+
+/// Parses the keywords of a [`GridAutoFlow`] without surrounding whitespace
+fn grid_auto_flow_keywords_parser(input: &str) -> IResult<&str, GridAutoFlow> {
+    let direction = |input| {
+        keyword_token_parser(
+            input,
+            &[("row", GridAutoFlow::Row), ("column", GridAutoFlow::Column)],
+        )
+    };
+    let dense = |input| keyword_token_parser(input, &[("dense", ())]);
+    let make_dense = |flow| match flow {
+        GridAutoFlow::Column => GridAutoFlow::ColumnDense,
+        _ => GridAutoFlow::RowDense,
+    };
+    if let Ok((input, ())) = dense(input) {
+        let (input, flow) = opt(preceded(multispace1, direction))(input)?;
+        return Ok((input, make_dense(flow.unwrap_or(GridAutoFlow::Row))));
+    }
+    let (input, flow) = direction(input).map_err(|error| {
+        error.map(|mut error| {
+            error.expected.push("dense");
+            error
+        })
+    })?;
+    let (input, is_dense) = opt(preceded(multispace1, dense))(input)?;
+    Ok((
+        input,
+        if is_dense.is_some() {
+            make_dense(flow)
+        } else {
+            flow
+        },
+    ))
+}
+
+/// Formats a [`GridAutoFlow`] as css keywords, like `column dense`, the counterpart
+/// of [`grid_auto_flow_parser`]
+pub fn grid_auto_flow_formatter(value: GridAutoFlow) -> String {
+    match value {
+        GridAutoFlow::Row => "row",
+        GridAutoFlow::Column => "column",
+        GridAutoFlow::RowDense => "row dense",
+        GridAutoFlow::ColumnDense => "column dense",
+    }
+    .to_string()
+}
+
+keyword_wrappers!(
+    Display,
+    "display",
+    display_parser,
+    display_string_parser,
+    display_result_parser,
+    display_formatter,
+    display_serde_parser,
+    display_serde_serializer
+);
+keyword_wrappers!(
+    PositionType,
+    "position type",
+    position_type_parser,
+    position_type_string_parser,
+    position_type_result_parser,
+    position_type_formatter,
+    position_type_serde_parser,
+    position_type_serde_serializer
+);
+keyword_wrappers!(
+    FlexDirection,
+    "flex direction",
+    flex_direction_parser,
+    flex_direction_string_parser,
+    flex_direction_result_parser,
+    flex_direction_formatter,
+    flex_direction_serde_parser,
+    flex_direction_serde_serializer
+);
+keyword_wrappers!(
+    FlexWrap,
+    "flex wrap",
+    flex_wrap_parser,
+    flex_wrap_string_parser,
+    flex_wrap_result_parser,
+    flex_wrap_formatter,
+    flex_wrap_serde_parser,
+    flex_wrap_serde_serializer
+);
+keyword_wrappers!(
+    AlignItems,
+    "align items",
+    align_items_parser,
+    align_items_string_parser,
+    align_items_result_parser,
+    align_items_formatter,
+    align_items_serde_parser,
+    align_items_serde_serializer
+);
+keyword_wrappers!(
+    AlignSelf,
+    "align self",
+    align_self_parser,
+    align_self_string_parser,
+    align_self_result_parser,
+    align_self_formatter,
+    align_self_serde_parser,
+    align_self_serde_serializer
+);
+keyword_wrappers!(
+    AlignContent,
+    "align content",
+    align_content_parser,
+    align_content_string_parser,
+    align_content_result_parser,
+    align_content_formatter,
+    align_content_serde_parser,
+    align_content_serde_serializer
+);
+keyword_wrappers!(
+    JustifyContent,
+    "justify content",
+    justify_content_parser,
+    justify_content_string_parser,
+    justify_content_result_parser,
+    justify_content_formatter,
+    justify_content_serde_parser,
+    justify_content_serde_serializer
+);
+keyword_wrappers!(
+    JustifyItems,
+    "justify items",
+    justify_items_parser,
+    justify_items_string_parser,
+    justify_items_result_parser,
+    justify_items_formatter,
+    justify_items_serde_parser,
+    justify_items_serde_serializer
+);
+keyword_wrappers!(
+    JustifySelf,
+    "justify self",
+    justify_self_parser,
+    justify_self_string_parser,
+    justify_self_result_parser,
+    justify_self_formatter,
+    justify_self_serde_parser,
+    justify_self_serde_serializer
+);
+keyword_wrappers!(
+    Direction,
+    "direction",
+    direction_parser,
+    direction_string_parser,
+    direction_result_parser,
+    direction_formatter,
+    direction_serde_parser,
+    direction_serde_serializer
+);
+keyword_wrappers!(
+    Visibility,
+    "visibility",
+    visibility_parser,
+    visibility_string_parser,
+    visibility_result_parser,
+    visibility_formatter,
+    visibility_serde_parser,
+    visibility_serde_serializer
+);
+keyword_wrappers!(
+    OverflowAxis,
+    "overflow axis",
+    overflow_axis_parser,
+    overflow_axis_string_parser,
+    overflow_axis_result_parser,
+    overflow_axis_formatter,
+    overflow_axis_serde_parser,
+    overflow_axis_serde_serializer
+);
+keyword_wrappers!(
+    Overflow,
+    "overflow",
+    overflow_parser,
+    overflow_string_parser,
+    overflow_result_parser,
+    overflow_formatter,
+    overflow_serde_parser,
+    overflow_serde_serializer
+);
+keyword_wrappers!(
+    GridAutoFlow,
+    "grid auto flow",
+    grid_auto_flow_parser,
+    grid_auto_flow_string_parser,
+    grid_auto_flow_result_parser,
+    grid_auto_flow_formatter,
+    grid_auto_flow_serde_parser,
+    grid_auto_flow_serde_serializer
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("flex", Display::Flex ; "flex")]
+    #[test_case("grid", Display::Grid ; "grid")]
+    #[test_case("none", Display::None ; "none")]
+    fn test_display_parser(string: &str, expected: Display) {
+        assert_eq!(display_parser(string), Ok(("", expected)));
+    }
+
+    #[test_case(" flex", Display::Flex ; "leading whitespace")]
+    #[test_case("flex  ", Display::Flex ; "trailing whitespace")]
+    #[test_case(" \n grid ", Display::Grid ; "surrounding whitespace")]
+    fn test_keyword_parser_whitespace(string: &str, expected: Display) {
+        assert_eq!(display_parser(string), Ok(("", expected)));
+    }
+
+    #[test]
+    fn test_keyword_parser_errors() {
+        assert_eq!(
+            display_parser("block"),
+            Err(nom::Err::Error(InputError {
+                input: "block",
+                expected: vec!["flex", "grid", "none"],
+            }))
+        );
+        // keywords must not match the prefix of a longer identifier
+        assert!(flex_wrap_parser("wrapper").is_err());
+        assert_eq!(
+            flex_wrap_parser("wrap-reverse;"),
+            Ok((";", FlexWrap::WrapReverse))
+        );
+    }
+
+    #[test_case("clip", Overflow::clip() ; "one value")]
+    #[test_case("clip visible", Overflow::clip_x() ; "two values")]
+    #[test_case(" visible  clip ", Overflow::clip_y() ; "whitespace")]
+    fn test_overflow_parser(string: &str, expected: Overflow) {
+        assert_eq!(overflow_parser(string), Ok(("", expected)));
+    }
+
+    #[test_case("row", GridAutoFlow::Row ; "row")]
+    #[test_case("column", GridAutoFlow::Column ; "column")]
+    #[test_case("dense", GridAutoFlow::RowDense ; "dense")]
+    #[test_case("row dense", GridAutoFlow::RowDense ; "row dense")]
+    #[test_case("dense column", GridAutoFlow::ColumnDense ; "dense column")]
+    #[test_case("column dense", GridAutoFlow::ColumnDense ; "column dense")]
+    #[test_case(" row  dense ", GridAutoFlow::RowDense ; "whitespace")]
+    fn test_grid_auto_flow_parser(string: &str, expected: GridAutoFlow) {
+        assert_eq!(grid_auto_flow_parser(string), Ok(("", expected)));
+    }
+
+    #[test]
+    fn test_keyword_formatters() {
+        for (keyword, value) in ALIGN_CONTENT_KEYWORDS {
+            assert_eq!(align_content_formatter(*value), *keyword);
+            assert_eq!(align_content_result_parser(keyword), Ok(*value));
+        }
+        for (keyword, value) in VISIBILITY_KEYWORDS {
+            assert_eq!(visibility_formatter(*value), *keyword);
+            assert_eq!(visibility_result_parser(keyword), Ok(*value));
+        }
+        assert_eq!(overflow_formatter(Overflow::clip()), "clip");
+        assert_eq!(overflow_formatter(Overflow::clip_y()), "visible clip");
+        assert_eq!(
+            grid_auto_flow_formatter(GridAutoFlow::ColumnDense),
+            "column dense"
+        );
+    }
+
+    #[test]
+    fn test_keyword_string_parsers() {
+        assert_eq!(
+            justify_content_string_parser(" space-between"),
+            Some(JustifyContent::SpaceBetween)
+        );
+        assert_eq!(
+            flex_direction_string_parser("row-reverse"),
+            Some(FlexDirection::RowReverse)
+        );
+        assert_eq!(align_self_string_parser("middle"), None);
+        let error = flex_wrap_result_parser("wrap reverse").unwrap_err();
+        assert_eq!(error.offset, 5);
+        assert_eq!(error.expected, vec!["<end of input>"]);
+        let error = visibility_result_parser("hiden").unwrap_err();
+        assert_eq!(
+            crate::suggest_correction("hiden", &error).as_deref(),
+            Some("hidden")
+        );
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests_serde {
+    use bevy::{
+        render::view::Visibility,
+        ui::{FlexDirection, JustifyContent, Overflow},
+    };
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Deserialize, Serialize)]
+    pub struct Foo {
+        #[serde(
+            deserialize_with = "super::flex_direction_serde_parser",
+            serialize_with = "super::flex_direction_serde_serializer"
+        )]
+        pub flex_direction: FlexDirection,
+        #[serde(
+            deserialize_with = "super::justify_content_serde_parser",
+            serialize_with = "super::justify_content_serde_serializer"
+        )]
+        pub justify_content: JustifyContent,
+        #[serde(
+            deserialize_with = "super::overflow_serde_parser",
+            serialize_with = "super::overflow_serde_serializer"
+        )]
+        pub overflow: Overflow,
+        #[serde(
+            deserialize_with = "super::visibility_serde_parser",
+            serialize_with = "super::visibility_serde_serializer"
+        )]
+        pub visibility: Visibility,
+    }
+
+    #[test]
+    fn test_keyword_serde_parsers() {
+        let json = r#"{"flex_direction":"row-reverse","justify_content":"space-between","overflow":"clip visible","visibility":"hidden"}"#;
+        let foo: Foo = serde_json::from_str(json).unwrap();
+        assert_eq!(foo.flex_direction, FlexDirection::RowReverse);
+        assert_eq!(foo.justify_content, JustifyContent::SpaceBetween);
+        assert_eq!(foo.overflow, Overflow::clip_x());
+        assert_eq!(foo.visibility, Visibility::Hidden);
+        assert_eq!(serde_json::to_string(&foo).unwrap(), json);
+    }
+
+    #[test]
+    fn test_keyword_serde_parser_errors() {
+        let error = serde_json::from_str::<Foo>(
+            r#"{"flex_direction":"row","justify_content":"between","overflow":"clip","visibility":"hidden"}"#,
+        )
+        .err()
+        .unwrap();
+        assert!(error
+            .to_string()
+            .starts_with("invalid justify content string: unexpected \"between\" at offset 0"));
+    }
+}
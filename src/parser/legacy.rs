@@ -0,0 +1,207 @@
+//! Parsers with the signatures of version 0.1 of this crate.
+//!
+//! Since 0.2 the nom parsers return the crate's [`super::IResult`] with an
+//! [`super::InputError`] that lists the expected tokens, and the serde parsers
+//! reject trailing input. The comma separated `rgb()` takes channels in the
+//! range of 0-255 like css instead of 0.0-1.0. The functions of this module
+//! keep the previous behavior for code that has not been migrated yet, they
+//! will be removed in a future release.
+#![allow(deprecated)]
+
+use bevy::{render::color::Color, ui::UiRect, ui::Val};
+use nom::{
+    branch::alt,
+    character::complete::multispace0 as multispace,
+    combinator::map,
+    error::{Error, ErrorKind},
+    sequence::{delimited, preceded, tuple},
+};
+
+use super::common::{comma_parser, function_parser, number_parser};
+use super::error::{IResult, InputError};
+
+/// Converts the error of a parser of this crate into nom's default error
+fn nom_error(error: nom::Err<InputError<&str>>) -> nom::Err<Error<&str>> {
+    error.map(|error| Error::new(error.input, ErrorKind::Fail))
+}
+
+/// Parser for [`bevy::ui::Val`] returning nom's default error, see [`super::val_parser`]
+#[deprecated(
+    since = "0.2.0",
+    note = "use `val_parser`, which returns an `InputError`"
+)]
+pub fn val_parser(input: &str) -> nom::IResult<&str, Val> {
+    super::val_parser(input).map_err(nom_error)
+}
+
+/// Parser for [`bevy::ui::UiRect`] returning nom's default error, see [`super::rect_parser`]
+#[deprecated(
+    since = "0.2.0",
+    note = "use `rect_parser`, which returns an `InputError`"
+)]
+pub fn rect_parser(input: &str) -> nom::IResult<&str, UiRect> {
+    super::rect_parser(input).map_err(nom_error)
+}
+
+/// Parses a comma separated channel of [`legacy_rgb_parser`], like `, 1.0`
+fn legacy_channel_parser(input: &str) -> IResult<&str, f32> {
+    preceded(comma_parser, number_parser)(input)
+}
+
+/// Parses the comma separated `rgb()` and `rgba()` of version 0.1, with channels
+/// in the range of 0.0-1.0, like `rgb(1.0, 0.0, 0.0)`
+fn legacy_rgb_parser(input: &str) -> IResult<&str, Color> {
+    delimited(
+        multispace,
+        alt((
+            map(
+                function_parser(
+                    "rgb",
+                    tuple((number_parser, legacy_channel_parser, legacy_channel_parser)),
+                ),
+                |(r, g, b)| Color::rgb(r, g, b),
+            ),
+            map(
+                function_parser(
+                    "rgba",
+                    tuple((
+                        number_parser,
+                        legacy_channel_parser,
+                        legacy_channel_parser,
+                        legacy_channel_parser,
+                    )),
+                ),
+                |(r, g, b, a)| Color::rgba(r, g, b, a),
+            ),
+        )),
+        multispace,
+    )(input)
+}
+
+/// Parser for [`bevy::render::color::Color`] returning nom's default error,
+/// see [`super::color_parser`]
+///
+/// Unlike [`super::color_parser`], the comma separated `rgb()` and `rgba()` keep
+/// channels in the range of 0.0-1.0, like `rgb(1.0, 0.0, 0.0)`.
+#[deprecated(
+    since = "0.2.0",
+    note = "use `color_parser`, which returns an `InputError`"
+)]
+pub fn color_parser(input: &str) -> nom::IResult<&str, Color> {
+    alt((legacy_rgb_parser, super::color_parser))(input).map_err(nom_error)
+}
+
+/// Parser for angles in radians returning nom's default error, see [`super::angle_parser`]
+#[deprecated(
+    since = "0.2.0",
+    note = "use `angle_parser`, which returns an `InputError`"
+)]
+pub fn angle_parser(input: &str) -> nom::IResult<&str, f32> {
+    super::angle_parser(input).map_err(nom_error)
+}
+
+/// Serde deserializer for [`bevy::ui::Val`] that ignores trailing input
+#[cfg(feature = "serde")]
+#[deprecated(
+    since = "0.2.0",
+    note = "use `val_serde_parser`, which rejects trailing input"
+)]
+pub fn val_serde_parser<'de, D>(deserializer: D) -> Result<Val, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::Error;
+    let s: &str = serde::Deserialize::deserialize(deserializer)?;
+    super::val_string_parser(s).ok_or(D::Error::custom("invalid val string"))
+}
+
+/// Serde deserializer for [`bevy::ui::UiRect`] that ignores trailing input
+#[cfg(feature = "serde")]
+#[deprecated(
+    since = "0.2.0",
+    note = "use `rect_serde_parser`, which rejects trailing input"
+)]
+pub fn rect_serde_parser<'de, D>(deserializer: D) -> Result<UiRect, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::Error;
+    let s: &str = serde::Deserialize::deserialize(deserializer)?;
+    super::rect_string_parser(s).ok_or(D::Error::custom("invalid rect string"))
+}
+
+/// Serde deserializer for [`bevy::render::color::Color`] that ignores trailing input
+#[cfg(feature = "serde")]
+#[deprecated(
+    since = "0.2.0",
+    note = "use `color_serde_parser`, which rejects trailing input"
+)]
+pub fn color_serde_parser<'de, D>(deserializer: D) -> Result<Color, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::Error;
+    let s: &str = serde::Deserialize::deserialize(deserializer)?;
+    color_parser(s)
+        .map(|(_, value)| value)
+        .map_err(|_| D::Error::custom("invalid color string"))
+}
+
+/// Serde deserializer for angles in radians that ignores trailing input
+#[cfg(feature = "serde")]
+#[deprecated(
+    since = "0.2.0",
+    note = "use `angle_serde_parser`, which rejects trailing input"
+)]
+pub fn angle_serde_parser<'de, D>(deserializer: D) -> Result<f32, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::Error;
+    let s: &str = serde::Deserialize::deserialize(deserializer)?;
+    super::angle_string_parser(s).ok_or(D::Error::custom("invalid angle string"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_legacy_parsers() {
+        assert_eq!(val_parser("10px"), Ok(("", Val::Px(10.0))));
+        assert_eq!(rect_parser("1px"), Ok(("", UiRect::all(Val::Px(1.0)))));
+        assert_eq!(color_parser("red"), Ok(("", Color::RED)));
+        assert_eq!(color_parser("rgb(1.0, 0.0, 0.0)"), Ok(("", Color::RED)));
+        assert_eq!(
+            color_parser(" rgba(0, 0, 1, 0.5) "),
+            Ok(("", Color::rgba(0.0, 0.0, 1.0, 0.5)))
+        );
+        assert_eq!(
+            color_parser("rgb(255 0 0)"),
+            Ok(("", Color::rgb(1.0, 0.0, 0.0)))
+        );
+        assert_eq!(angle_parser("0rad"), Ok(("", 0.0)));
+        assert_eq!(
+            val_parser("10pc"),
+            Err(nom::Err::Error(Error::new("pc", ErrorKind::Fail)))
+        );
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests_serde {
+    use bevy::ui::Val;
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    pub struct Foo {
+        #[serde(deserialize_with = "super::val_serde_parser")]
+        pub value: Val,
+    }
+
+    #[test]
+    fn test_legacy_serde_parser_trailing_input() {
+        let foo: Foo = serde_json::from_str(r#"{"value": "42px trailing"}"#).unwrap();
+        assert_eq!(foo.value, Val::Px(42.0));
+    }
+}
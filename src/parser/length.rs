@@ -0,0 +1,398 @@
+use bevy::{
+    ecs::system::Resource,
+    ui::{UiRect, Val},
+};
+use nom::{
+    branch::alt,
+    character::complete::multispace0 as multispace,
+    combinator::map,
+    multi::many_m_n,
+    sequence::{delimited, terminated},
+};
+
+use super::common::{number_parser, token};
+use super::error::{parse_all, IResult, ParseError};
+use super::val::val_length_parser;
+use super::val_formatter;
+
+/// Sizes that the font-relative and absolute units of a [`Length`] refer to
+///
+/// Can also be inserted as a bevy [`Resource`], used by [`crate::resolve_length_styles`].
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct LengthContext {
+    /// Font size of the root element in logical pixels, used by `rem`
+    pub root_font_size: f32,
+    /// Font size of the current element in logical pixels, used by `em` and `ch`
+    pub font_size: f32,
+    /// Logical pixels per inch, used by `in`, `cm`, `mm`, `Q`, `pt` and `pc`
+    pub dpi: f32,
+}
+
+impl Default for LengthContext {
+    /// Font sizes of 16px and 96 dpi, the defaults of css
+    fn default() -> Self {
+        Self {
+            root_font_size: 16.0,
+            font_size: 16.0,
+            dpi: 96.0,
+        }
+    }
+}
+
+/// A length that is not resolved yet, because its unit depends on a [`LengthContext`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Length {
+    /// A length that [`bevy::ui::Val`] supports directly, like `12px`, `50%` or `auto`
+    Val(Val),
+    /// Relative to the font size of the element
+    Em(f32),
+    /// Relative to the font size of the root element
+    Rem(f32),
+    /// Relative to the width of the `0` glyph, which is approximated as `0.5em`
+    Ch(f32),
+    /// Inches
+    In(f32),
+    /// Centimeters
+    Cm(f32),
+    /// Millimeters
+    Mm(f32),
+    /// Quarter-millimeters
+    Q(f32),
+    /// Points, 1/72 of an inch
+    Pt(f32),
+    /// Picas, 1/6 of an inch
+    Pc(f32),
+}
+
+impl Default for Length {
+    fn default() -> Self {
+        Self::Val(Val::Auto)
+    }
+}
+
+impl Length {
+    /// Resolves the length into a [`bevy::ui::Val`]
+    ///
+    /// Lengths supported by `Val` are returned as is, all others are resolved to `Val::Px`.
+    pub fn resolve(&self, context: &LengthContext) -> Val {
+        let inch = context.dpi;
+        let px = match *self {
+            Length::Val(val) => return val,
+            Length::Em(value) => value * context.font_size,
+            Length::Rem(value) => value * context.root_font_size,
+            Length::Ch(value) => value * context.font_size * 0.5,
+            Length::In(value) => value * inch,
+            Length::Cm(value) => value * inch / 2.54,
+            Length::Mm(value) => value * inch / 25.4,
+            Length::Q(value) => value * inch / 101.6,
+            Length::Pt(value) => value * inch / 72.0,
+            Length::Pc(value) => value * inch / 6.0,
+        };
+        Val::Px(px)
+    }
+}
+
+/// Parses a number with one of the units that [`bevy::ui::Val`] doesn't support, like `1.5rem`
+fn unresolved_length_parser(input: &str) -> IResult<&str, Length> {
+    let unit =
+        |unit, length: fn(f32) -> Length| map(terminated(number_parser, token(unit)), length);
+    alt((
+        unit("em", Length::Em),
+        unit("rem", Length::Rem),
+        unit("ch", Length::Ch),
+        unit("in", Length::In),
+        unit("cm", Length::Cm),
+        unit("mm", Length::Mm),
+        unit("Q", Length::Q),
+        unit("pt", Length::Pt),
+        unit("pc", Length::Pc),
+    ))(input)
+}
+
+/// Parser for strings that represent a [`Length`].
+///
+/// Supports everything [`super::val_parser`] supports, plus the units:
+///
+/// * `1.5em` -> `Length::Em(1.5)`, relative to the font size
+/// * `2rem` -> `Length::Rem(2.0)`, relative to the root font size
+/// * `10ch` -> `Length::Ch(10.0)`, relative to the width of `0`
+/// * `1in`, `2.54cm`, `25.4mm`, `101.6Q` -> absolute lengths
+/// * `72pt`, `6pc` -> points and picas
+///
+pub fn length_parser(input: &str) -> IResult<&str, Length> {
+    delimited(
+        multispace,
+        alt((
+            map(token("auto"), |_| Length::Val(Val::Auto)),
+            map(val_length_parser, Length::Val),
+            unresolved_length_parser,
+        )),
+        multispace,
+    )(input)
+}
+
+/// Wrapper for [`length_parser`] that returns an optional [`Length`]
+pub fn length_string_parser(input: &str) -> Option<Length> {
+    length_parser(input).map(|(_, value)| value).ok()
+}
+
+/// Wrapper for [`length_parser`] that returns a [`ParseError`] on failure
+///
+/// Unlike [`length_string_parser`] the whole input must be consumed.
+pub fn length_result_parser(input: &str) -> Result<Length, ParseError> {
+    parse_all(input, length_parser)
+}
+
+/// Parses a [`Length`] and resolves it into a [`bevy::ui::Val`] with the context
+///
+/// For example `2rem` -> `Val::Px(32.0)` with the default context.
+pub fn val_context_parser<'a>(input: &'a str, context: &LengthContext) -> IResult<&'a str, Val> {
+    map(length_parser, |length| length.resolve(context))(input)
+}
+
+/// Wrapper for [`val_context_parser`] that returns an optional [`bevy::ui::Val`]
+pub fn val_context_string_parser(input: &str, context: &LengthContext) -> Option<Val> {
+    val_context_parser(input, context)
+        .map(|(_, value)| value)
+        .ok()
+}
+
+/// Wrapper for [`val_context_parser`] that returns a [`ParseError`] on failure
+///
+/// Unlike [`val_context_string_parser`] the whole input must be consumed.
+pub fn val_context_result_parser(input: &str, context: &LengthContext) -> Result<Val, ParseError> {
+    parse_all(input, |input| val_context_parser(input, context))
+}
+
+/// Formats a [`Length`] as a string, the counterpart of [`length_parser`]
+///
+/// For example `Length::Rem(1.5)` -> `1.5rem`.
+pub fn length_formatter(length: Length) -> String {
+    match length {
+        Length::Val(val) => val_formatter(val),
+        Length::Em(value) => format!("{}em", value),
+        Length::Rem(value) => format!("{}rem", value),
+        Length::Ch(value) => format!("{}ch", value),
+        Length::In(value) => format!("{}in", value),
+        Length::Cm(value) => format!("{}cm", value),
+        Length::Mm(value) => format!("{}mm", value),
+        Length::Q(value) => format!("{}Q", value),
+        Length::Pt(value) => format!("{}pt", value),
+        Length::Pc(value) => format!("{}pc", value),
+    }
+}
+
+/// Four [`Length`]s of the sides of a rect, the unresolved counterpart of [`bevy::ui::UiRect`]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct LengthRect {
+    pub left: Length,
+    pub right: Length,
+    pub top: Length,
+    pub bottom: Length,
+}
+
+impl LengthRect {
+    /// Resolves the lengths into a [`bevy::ui::UiRect`]
+    pub fn resolve(&self, context: &LengthContext) -> UiRect {
+        UiRect::new(
+            self.left.resolve(context),
+            self.right.resolve(context),
+            self.top.resolve(context),
+            self.bottom.resolve(context),
+        )
+    }
+}
+
+/// Parser for strings that represent a [`LengthRect`].
+///
+/// One to four lengths as supported by [`length_parser`], in the order of the css
+/// shorthands as described for [`super::rect_parser`], like `1rem 2rem`.
+pub fn length_rect_parser(input: &str) -> IResult<&str, LengthRect> {
+    map(many_m_n(1, 4, length_parser), |lengths| {
+        let (top, right, bottom, left) = match lengths[..] {
+            [all] => (all, all, all, all),
+            [vertical, horizontal] => (vertical, horizontal, vertical, horizontal),
+            [top, horizontal, bottom] => (top, horizontal, bottom, horizontal),
+            [top, right, bottom, left, ..] => (top, right, bottom, left),
+            [] => unreachable!("at least one length is parsed"),
+        };
+        LengthRect {
+            left,
+            right,
+            top,
+            bottom,
+        }
+    })(input)
+}
+
+/// Wrapper for [`length_rect_parser`] that returns an optional [`LengthRect`]
+pub fn length_rect_string_parser(input: &str) -> Option<LengthRect> {
+    length_rect_parser(input).map(|(_, value)| value).ok()
+}
+
+/// Wrapper for [`length_rect_parser`] that returns a [`ParseError`] on failure
+///
+/// Unlike [`length_rect_string_parser`] the whole input must be consumed.
+pub fn length_rect_result_parser(input: &str) -> Result<LengthRect, ParseError> {
+    parse_all(input, length_rect_parser)
+}
+
+/// Wrapper for [`length_parser`] that implements a serde deserializer
+#[cfg(feature = "serde")]
+pub fn length_serde_parser<'de, D>(deserializer: D) -> Result<Length, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::Error;
+    let s: &str = serde::Deserialize::deserialize(deserializer)?;
+    length_result_parser(s)
+        .map_err(|error| D::Error::custom(format!("invalid length string: {}", error)))
+}
+
+/// Wrapper for [`length_formatter`] that implements a serde serializer
+#[cfg(feature = "serde")]
+pub fn length_serde_serializer<S>(length: &Length, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.serialize_str(&length_formatter(*length))
+}
+
+/// Wrapper for [`length_rect_parser`] that implements a serde deserializer
+#[cfg(feature = "serde")]
+pub fn length_rect_serde_parser<'de, D>(deserializer: D) -> Result<LengthRect, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::Error;
+    let s: &str = serde::Deserialize::deserialize(deserializer)?;
+    length_rect_result_parser(s)
+        .map_err(|error| D::Error::custom(format!("invalid length rect string: {}", error)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("1.5em", Length::Em(1.5), 24.0 ; "em")]
+    #[test_case("2rem", Length::Rem(2.0), 20.0 ; "rem")]
+    #[test_case("4ch", Length::Ch(4.0), 32.0 ; "ch")]
+    #[test_case("1in", Length::In(1.0), 192.0 ; "inches")]
+    #[test_case("2.54cm", Length::Cm(2.54), 192.0 ; "cm")]
+    #[test_case("25.4mm", Length::Mm(25.4), 192.0 ; "mm")]
+    #[test_case("101.6Q", Length::Q(101.6), 192.0 ; "quarter millimeters")]
+    #[test_case("72pt", Length::Pt(72.0), 192.0 ; "pt")]
+    #[test_case("6pc", Length::Pc(6.0), 192.0 ; "pc")]
+    fn test_length_parser(string: &str, expected: Length, px: f32) {
+        let context = LengthContext {
+            root_font_size: 10.0,
+            font_size: 16.0,
+            dpi: 192.0,
+        };
+        assert_eq!(length_parser(string), Ok(("", expected)));
+        match expected.resolve(&context) {
+            Val::Px(value) => assert!((value - px).abs() < 1e-3, "{} != {}", value, px),
+            val => panic!("{:?} is not in px", val),
+        }
+        assert_eq!(length_formatter(expected), string);
+    }
+
+    #[test_case("1e1em", Length::Em(10.0) ; "exponent")]
+    #[test_case(".5rem", Length::Rem(0.5) ; "leading dot")]
+    #[test_case("-0in", Length::In(0.0) ; "negative zero")]
+    fn test_length_parser_numbers(string: &str, expected: Length) {
+        assert_eq!(length_parser(string), Ok(("", expected)));
+    }
+
+    #[test]
+    fn test_length_parser_val() {
+        assert_eq!(length_parser(" auto "), Ok(("", Length::Val(Val::Auto))));
+        assert_eq!(
+            length_parser("12vmin"),
+            Ok(("", Length::Val(Val::VMin(12.0))))
+        );
+        assert_eq!(
+            Length::Val(Val::Percent(50.0)).resolve(&LengthContext::default()),
+            Val::Percent(50.0)
+        );
+    }
+
+    #[test]
+    fn test_val_context_parser() {
+        let context = LengthContext::default();
+        assert_eq!(
+            val_context_string_parser("2rem", &context),
+            Some(Val::Px(32.0))
+        );
+        assert_eq!(
+            val_context_result_parser("12pt", &context),
+            Ok(Val::Px(16.0))
+        );
+        assert_eq!(
+            val_context_result_parser("5%", &context),
+            Ok(Val::Percent(5.0))
+        );
+        let error = val_context_result_parser("2rm", &context).unwrap_err();
+        assert_eq!(error.offset, 1);
+        assert_eq!(
+            error.expected,
+            vec![
+                "px", "%", "vw", "vh", "vmin", "vmax", "em", "rem", "ch", "in", "cm", "mm", "Q",
+                "pt", "pc"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_length_rect_parser() {
+        let rect = length_rect_result_parser("1rem 2px 0.5em").unwrap();
+        assert_eq!(
+            rect.resolve(&LengthContext::default()),
+            UiRect::new(Val::Px(2.0), Val::Px(2.0), Val::Px(16.0), Val::Px(8.0))
+        );
+        assert_eq!(
+            length_rect_string_parser("1rem"),
+            Some(LengthRect {
+                left: Length::Rem(1.0),
+                right: Length::Rem(1.0),
+                top: Length::Rem(1.0),
+                bottom: Length::Rem(1.0),
+            })
+        );
+        assert!(length_rect_result_parser("1px 2px 3px 4px 5px").is_err());
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests_serde {
+    use super::{Length, LengthRect};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Deserialize, Serialize)]
+    pub struct Foo {
+        #[serde(
+            deserialize_with = "super::length_serde_parser",
+            serialize_with = "super::length_serde_serializer"
+        )]
+        pub value: Length,
+    }
+
+    #[derive(Deserialize)]
+    pub struct Bar {
+        #[serde(deserialize_with = "super::length_rect_serde_parser")]
+        pub padding: LengthRect,
+    }
+
+    #[test]
+    fn test_length_serde_parser() {
+        let foo: Foo = serde_json::from_str(r#"{"value": "1.25rem"}"#).unwrap();
+        assert_eq!(foo.value, Length::Rem(1.25));
+        assert_eq!(
+            serde_json::to_string(&foo).unwrap(),
+            r#"{"value":"1.25rem"}"#
+        );
+        let bar: Bar = serde_json::from_str(r#"{"padding": "1em 2em"}"#).unwrap();
+        assert_eq!(bar.padding.left, Length::Em(2.0));
+    }
+}
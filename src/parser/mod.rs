@@ -1,18 +1,225 @@
 mod angle;
+mod animation;
+mod border;
+mod calc;
 mod color;
+mod color_mix;
+mod color_registry;
+mod color_space;
+mod common;
+mod diagnostics;
+mod duration;
+mod easing;
+mod error;
+mod gradient;
+mod gradient_image;
+mod grid;
+mod grid_areas;
+mod interpolate;
+mod keyframes;
+mod keyword;
+pub mod legacy;
+mod length;
+mod position;
+mod radius;
 mod rect;
+mod shadow;
+mod size;
+mod style;
+mod transform;
+mod transition;
 mod val;
 
 #[cfg(feature = "serde")]
-pub use angle::angle_serde_parser;
+pub use angle::{
+    angle_serde_parser, angle_serde_serializer, typed_angle_serde_parser,
+    typed_angle_serde_serializer,
+};
 #[cfg(feature = "serde")]
-pub use color::color_serde_parser;
+pub use animation::animation_serde_parser;
 #[cfg(feature = "serde")]
-pub use rect::rect_serde_parser;
+pub use border::border_serde_parser;
 #[cfg(feature = "serde")]
-pub use val::val_serde_parser;
-pub use angle::{angle_parser, angle_string_parser};
+pub use calc::calc_serde_parser;
+#[cfg(feature = "serde")]
+pub use color::{color_serde_parser, color_serde_serializer};
+#[cfg(feature = "serde")]
+pub use duration::{duration_serde_parser, duration_serde_serializer};
+#[cfg(feature = "serde")]
+pub use easing::{easing_serde_parser, easing_serde_serializer};
+#[cfg(feature = "serde")]
+pub use gradient::gradient_serde_parser;
+#[cfg(feature = "serde")]
+pub use grid::{
+    grid_auto_tracks_serde_parser, grid_placement_serde_parser, grid_placement_serde_serializer,
+    grid_template_serde_parser,
+};
+#[cfg(feature = "serde")]
+pub use grid_areas::grid_template_areas_serde_parser;
+#[cfg(feature = "serde")]
+pub use keyframes::keyframes_serde_parser;
+#[cfg(feature = "serde")]
+pub use keyword::{
+    align_content_serde_parser, align_content_serde_serializer, align_items_serde_parser,
+    align_items_serde_serializer, align_self_serde_parser, align_self_serde_serializer,
+    direction_serde_parser, direction_serde_serializer, display_serde_parser,
+    display_serde_serializer, flex_direction_serde_parser, flex_direction_serde_serializer,
+    flex_wrap_serde_parser, flex_wrap_serde_serializer, grid_auto_flow_serde_parser,
+    grid_auto_flow_serde_serializer, justify_content_serde_parser,
+    justify_content_serde_serializer, justify_items_serde_parser, justify_items_serde_serializer,
+    justify_self_serde_parser, justify_self_serde_serializer, overflow_axis_serde_parser,
+    overflow_axis_serde_serializer, overflow_serde_parser, overflow_serde_serializer,
+    position_type_serde_parser, position_type_serde_serializer, visibility_serde_parser,
+    visibility_serde_serializer,
+};
+#[cfg(feature = "serde")]
+pub use length::{length_rect_serde_parser, length_serde_parser, length_serde_serializer};
+#[cfg(feature = "serde")]
+pub use radius::{border_radius_serde_parser, border_radius_serde_serializer};
+#[cfg(feature = "serde")]
+pub use rect::{rect_serde_parser, rect_serde_serializer};
+#[cfg(feature = "serde")]
+pub use shadow::{box_shadow_serde_parser, box_shadow_serde_serializer, text_shadow_serde_parser};
+#[cfg(feature = "serde")]
+pub use size::{size_serde_parser, size_serde_serializer};
+#[cfg(feature = "serde")]
+pub use style::style_serde_parser;
+#[cfg(feature = "serde")]
+pub use transform::{
+    transform_origin_serde_parser, transform_serde_parser, transform_serde_serializer,
+};
+#[cfg(feature = "serde")]
+pub use transition::{transition_serde_parser, transition_serde_serializer};
+#[cfg(feature = "serde")]
+pub use val::{val_serde_parser, val_serde_serializer};
+pub use angle::{angle_formatter, angle_parser, angle_result_parser, angle_string_parser};
+pub use angle::{
+    angle_normalized_parser, angle_normalized_result_parser, angle_normalized_string_parser,
+};
+pub use angle::{angle_parser_f64, angle_result_parser_f64, angle_string_parser_f64};
+pub use angle::{
+    typed_angle_formatter, typed_angle_parser, typed_angle_result_parser,
+    typed_angle_string_parser, Angle, AngleNormalization, AngleUnit,
+};
+pub use animation::{
+    animation_parser, animation_result_parser, animation_string_parser, Animation,
+    AnimationDirection, FillMode, IterationCount,
+};
+pub use border::{
+    border_color_parser, border_color_result_parser, border_color_string_parser,
+};
+pub use border::{
+    border_line_parser, border_line_result_parser, border_line_string_parser, BorderLine,
+    BorderSide,
+};
+pub use border::{border_parser, border_result_parser, border_string_parser, Border};
+pub use border::{
+    border_width_rect_parser, border_width_rect_result_parser, border_width_rect_string_parser,
+};
+pub use calc::{calc_parser, calc_result_parser, calc_string_parser, CalcContext, CalcExpr};
 pub use color::CSS_COLOR_TABLE;
-pub use color::{color_parser, color_string_parser};
-pub use rect::{rect_parser, rect_string_parser};
-pub use val::{val_parser, val_string_parser};
+pub use color::{color_formatter, color_name_formatter, nearest_css_color_name};
+pub use color::{color_parser, color_result_parser, color_string_parser};
+pub use color::{
+    color_registry_parser, color_registry_result_parser, color_registry_string_parser,
+};
+pub use color_registry::ColorNameRegistry;
+pub use color_space::{ColorSpace, HueInterpolation};
+pub use diagnostics::{render_diagnostic, suggest_correction};
+pub use duration::{
+    duration_formatter, duration_parser, duration_result_parser, duration_string_parser,
+};
+pub use easing::{
+    easing_formatter, easing_parser, easing_result_parser, easing_string_parser, Easing,
+    LinearStop, StepPosition,
+};
+pub use error::{IResult, InputError, ParseError};
+pub use gradient::{gradient_parser, gradient_result_parser, gradient_string_parser};
+pub use gradient::{ColorStop, Gradient, GradientKind, LinearDirection, RadialShape, RadialSize};
+pub use grid::{
+    grid_auto_tracks_parser, grid_auto_tracks_result_parser, grid_auto_tracks_string_parser,
+};
+pub use grid::{grid_area_parser, grid_area_result_parser, grid_area_string_parser};
+pub use grid::{
+    grid_placement_formatter, grid_placement_parser, grid_placement_result_parser,
+    grid_placement_string_parser,
+};
+pub use grid::{grid_template_parser, grid_template_result_parser, grid_template_string_parser};
+pub use grid_areas::GridTemplateAreas;
+pub use grid_areas::{
+    grid_named_area_parser, grid_named_area_result_parser, grid_named_area_string_parser,
+};
+pub use grid_areas::{
+    grid_template_areas_parser, grid_template_areas_result_parser,
+    grid_template_areas_string_parser,
+};
+pub use interpolate::{
+    interpolate_angle, interpolate_color, interpolate_rect, interpolate_val, CalcRect,
+};
+pub use keyframes::{
+    keyframes_parser, keyframes_result_parser, keyframes_string_parser, AnimatedProperty,
+    Keyframe, KeyframeTrack, KeyframeValue, Keyframes,
+};
+pub use keyword::{
+    align_content_formatter, align_content_parser, align_content_result_parser,
+    align_content_string_parser, align_items_formatter, align_items_parser,
+    align_items_result_parser, align_items_string_parser, align_self_formatter, align_self_parser,
+    align_self_result_parser, align_self_string_parser, direction_formatter, direction_parser,
+    direction_result_parser, direction_string_parser, display_formatter, display_parser,
+    display_result_parser, display_string_parser, flex_direction_formatter, flex_direction_parser,
+    flex_direction_result_parser, flex_direction_string_parser, flex_wrap_formatter,
+    flex_wrap_parser, flex_wrap_result_parser, flex_wrap_string_parser, grid_auto_flow_formatter,
+    grid_auto_flow_parser, grid_auto_flow_result_parser, grid_auto_flow_string_parser,
+    justify_content_formatter, justify_content_parser, justify_content_result_parser,
+    justify_content_string_parser, justify_items_formatter, justify_items_parser,
+    justify_items_result_parser, justify_items_string_parser, justify_self_formatter,
+    justify_self_parser, justify_self_result_parser, justify_self_string_parser,
+    overflow_axis_formatter, overflow_axis_parser, overflow_axis_result_parser,
+    overflow_axis_string_parser, overflow_formatter, overflow_parser, overflow_result_parser,
+    overflow_string_parser, position_type_formatter, position_type_parser,
+    position_type_result_parser, position_type_string_parser, visibility_formatter,
+    visibility_parser, visibility_result_parser, visibility_string_parser,
+};
+pub use length::{
+    length_formatter, length_parser, length_result_parser, length_string_parser, Length,
+    LengthContext,
+};
+pub use length::{
+    length_rect_parser, length_rect_result_parser, length_rect_string_parser, LengthRect,
+};
+pub use length::{val_context_parser, val_context_result_parser, val_context_string_parser};
+pub use position::{
+    position_formatter, position_parser, position_result_parser, position_string_parser, Position,
+};
+pub use radius::{
+    border_radius_formatter, border_radius_parser, border_radius_result_parser,
+    border_radius_string_parser, CornerRadii,
+};
+pub use radius::{
+    corner_radius_parser, corner_radius_result_parser, corner_radius_string_parser,
+    CornerRadius,
+};
+pub use rect::{rect_formatter, rect_parser, rect_result_parser, rect_string_parser};
+pub use shadow::{
+    box_shadow_formatter, box_shadow_parser, box_shadow_result_parser, box_shadow_string_parser,
+    Shadow,
+};
+pub use shadow::{text_shadow_parser, text_shadow_result_parser, text_shadow_string_parser};
+pub use size::{calc_size_parser, calc_size_result_parser, calc_size_string_parser};
+pub use size::{size_formatter, size_parser, size_result_parser, size_string_parser};
+pub use style::{
+    style_block_parser, style_block_result_parser, style_block_string_parser, style_parser,
+    style_result_parser, style_string_parser, StyleBlock,
+};
+pub use transform::{
+    transform_formatter, transform_parser, transform_result_parser, transform_string_parser,
+    TransformFunction, TransformList,
+};
+pub use transform::{
+    transform_origin_parser, transform_origin_result_parser, transform_origin_string_parser,
+};
+pub use transition::{
+    transition_formatter, transition_parser, transition_result_parser, transition_string_parser,
+    Transition,
+};
+pub use val::{val_formatter, val_parser, val_result_parser, val_string_parser};
@@ -0,0 +1,198 @@
+use bevy::{math::Vec2, ui::Val};
+use nom::{
+    branch::alt, character::complete::multispace0 as multispace, combinator::map, multi::many_m_n,
+    sequence::delimited,
+};
+
+use super::common::token;
+use super::error::{parse_all, IResult, InputError, ParseError};
+use super::val::val_length_parser;
+use super::val_formatter;
+
+/// A position within a box, like the center of a gradient
+///
+/// The offsets are relative to the top left corner, percentages refer to the size of the box.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Position {
+    pub x: Val,
+    pub y: Val,
+}
+
+impl Default for Position {
+    /// The center of the box
+    fn default() -> Self {
+        Self {
+            x: Val::Percent(50.0),
+            y: Val::Percent(50.0),
+        }
+    }
+}
+
+/// A keyword or offset of a position
+#[derive(Clone, Copy, PartialEq)]
+enum PositionPart {
+    Left,
+    Right,
+    Top,
+    Bottom,
+    Center,
+    Offset(Val),
+}
+
+impl PositionPart {
+    fn is_vertical(self) -> bool {
+        matches!(self, PositionPart::Top | PositionPart::Bottom)
+    }
+
+    fn is_horizontal(self) -> bool {
+        matches!(self, PositionPart::Left | PositionPart::Right)
+    }
+
+    fn is_keyword(self) -> bool {
+        !matches!(self, PositionPart::Offset(_))
+    }
+
+    fn offset(self) -> Val {
+        match self {
+            PositionPart::Left | PositionPart::Top => Val::Percent(0.0),
+            PositionPart::Right | PositionPart::Bottom => Val::Percent(100.0),
+            PositionPart::Center => Val::Percent(50.0),
+            PositionPart::Offset(val) => val,
+        }
+    }
+}
+
+/// Resolves a length into pixels, percentages refer to the base
+///
+/// Viewport units and `auto` can't be resolved without a window and return `None`.
+pub(crate) fn resolve_length(val: Val, base: f32) -> Option<f32> {
+    match val {
+        Val::Px(px) => Some(px),
+        Val::Percent(percent) => Some(percent / 100.0 * base),
+        _ => None,
+    }
+}
+
+/// Resolves a position within a box into pixels, unresolvable offsets are centered
+pub(crate) fn resolve_position(position: Position, size: Vec2) -> Vec2 {
+    Vec2::new(
+        resolve_length(position.x, size.x).unwrap_or(size.x / 2.0),
+        resolve_length(position.y, size.y).unwrap_or(size.y / 2.0),
+    )
+}
+
+fn position_part_parser(input: &str) -> IResult<&str, PositionPart> {
+    delimited(
+        multispace,
+        alt((
+            map(token("left"), |_| PositionPart::Left),
+            map(token("right"), |_| PositionPart::Right),
+            map(token("top"), |_| PositionPart::Top),
+            map(token("bottom"), |_| PositionPart::Bottom),
+            map(token("center"), |_| PositionPart::Center),
+            map(val_length_parser, PositionPart::Offset),
+        )),
+        multispace,
+    )(input)
+}
+
+/// Parser for strings that represent a [`Position`].
+///
+/// One or two keywords or offsets as in CSS, like `center`, `30% 70%`,
+/// `right 10px` or `top left`:
+///
+/// * `left`, `right`, `top`, `bottom` -> the side, centered along the other axis
+/// * `10px` -> horizontal offset, vertically centered
+/// * `10px 20%`, `left top`, `top left` -> horizontal and vertical position
+pub fn position_parser(input: &str) -> IResult<&str, Position> {
+    let (rest, parts) = many_m_n(1, 2, position_part_parser)(input)?;
+    let (x, y) = match parts[..] {
+        [part] if part.is_vertical() => (PositionPart::Center, part),
+        [part] => (part, PositionPart::Center),
+        // keywords can be given in any order, like `top left`
+        [first, second]
+            if (first.is_vertical() || second.is_horizontal())
+                && first.is_keyword()
+                && second.is_keyword() =>
+        {
+            (second, first)
+        }
+        [first, second, ..] => (first, second),
+        [] => unreachable!("at least one part is parsed"),
+    };
+    if x.is_vertical() || y.is_horizontal() {
+        return Err(nom::Err::Error(InputError::expected(input, "<position>")));
+    }
+    Ok((
+        rest,
+        Position {
+            x: x.offset(),
+            y: y.offset(),
+        },
+    ))
+}
+
+/// Wrapper for [`position_parser`] that returns an optional [`Position`]
+pub fn position_string_parser(input: &str) -> Option<Position> {
+    position_parser(input).map(|(_, value)| value).ok()
+}
+
+/// Wrapper for [`position_parser`] that returns a [`ParseError`] on failure
+///
+/// Unlike [`position_string_parser`] the whole input must be consumed.
+pub fn position_result_parser(input: &str) -> Result<Position, ParseError> {
+    parse_all(input, position_parser)
+}
+
+/// Formats a [`Position`] as a string of two offsets, like `50% 10px`
+pub fn position_formatter(position: Position) -> String {
+    format!(
+        "{} {}",
+        val_formatter(position.x),
+        val_formatter(position.y)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("center", (50.0, 50.0) ; "center")]
+    #[test_case("left", (0.0, 50.0) ; "left")]
+    #[test_case("bottom", (50.0, 100.0) ; "bottom")]
+    #[test_case("30% 70%", (30.0, 70.0) ; "percentages")]
+    #[test_case("right top", (100.0, 0.0) ; "horizontal first")]
+    #[test_case("top right", (100.0, 0.0) ; "vertical first")]
+    #[test_case("center left", (0.0, 50.0) ; "center first")]
+    #[test_case("25% bottom", (25.0, 100.0) ; "offset and keyword")]
+    fn test_position_parser(string: &str, (x, y): (f32, f32)) {
+        assert_eq!(
+            position_result_parser(string),
+            Ok(Position {
+                x: Val::Percent(x),
+                y: Val::Percent(y),
+            })
+        );
+    }
+
+    #[test]
+    fn test_position_parser_offsets() {
+        let position = position_result_parser(" 10px 2vh ").unwrap();
+        assert_eq!(
+            position,
+            Position {
+                x: Val::Px(10.0),
+                y: Val::Vh(2.0),
+            }
+        );
+        assert_eq!(position_formatter(position), "10px 2vh");
+    }
+
+    #[test_case("left right" ; "two horizontal keywords")]
+    #[test_case("top 10px" ; "vertical keyword first")]
+    #[test_case("10px 20px 30px" ; "three values")]
+    fn test_position_parser_error(string: &str) {
+        assert!(position_result_parser(string).is_err());
+    }
+}
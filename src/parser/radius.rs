@@ -0,0 +1,336 @@
+use bevy::ui::Val;
+use nom::{
+    character::complete::multispace0 as multispace,
+    combinator::{cut, map, opt},
+    multi::many_m_n,
+    sequence::{delimited, pair, preceded},
+};
+
+use super::common::{pair_parser, token};
+use super::error::{parse_all, IResult, ParseError};
+use super::val::non_negative_length_parser;
+use super::val_formatter;
+
+/// Horizontal and vertical radius of a rounded corner, like `10px 20px`
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CornerRadius {
+    pub horizontal: Val,
+    pub vertical: Val,
+}
+
+impl CornerRadius {
+    /// Creates a circular corner with the same horizontal and vertical radius
+    pub fn circular(radius: Val) -> Self {
+        Self {
+            horizontal: radius,
+            vertical: radius,
+        }
+    }
+}
+
+/// Radii of the four corners of a ui node, like the value of `border-radius`
+///
+/// Bevy 0.12 doesn't render rounded corners, the radii can be passed on to a
+/// custom material or converted into the `BorderRadius` of later bevy versions.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CornerRadii {
+    pub top_left: CornerRadius,
+    pub top_right: CornerRadius,
+    pub bottom_right: CornerRadius,
+    pub bottom_left: CornerRadius,
+}
+
+impl CornerRadii {
+    /// Creates circular corners with the same radius
+    pub fn all(radius: Val) -> Self {
+        Self {
+            top_left: CornerRadius::circular(radius),
+            top_right: CornerRadius::circular(radius),
+            bottom_right: CornerRadius::circular(radius),
+            bottom_left: CornerRadius::circular(radius),
+        }
+    }
+}
+
+/// Parses a single radius, like `10px`, `50%` or `0`
+///
+/// Negative radii are invalid like in css, they fail without backtracking.
+fn radius_parser(input: &str) -> IResult<&str, Val> {
+    delimited(multispace, non_negative_length_parser, multispace)(input)
+}
+
+/// Parses one to four radii and expands them to the four corners
+///
+/// top-left | top-right | bottom-right | bottom-left
+fn corner_values_parser(input: &str) -> IResult<&str, [Val; 4]> {
+    map(many_m_n(1, 4, radius_parser), |values| match values[..] {
+        [all] => [all; 4],
+        [top_left_bottom_right, top_right_bottom_left] => [
+            top_left_bottom_right,
+            top_right_bottom_left,
+            top_left_bottom_right,
+            top_right_bottom_left,
+        ],
+        [top_left, top_right_bottom_left, bottom_right] => [
+            top_left,
+            top_right_bottom_left,
+            bottom_right,
+            top_right_bottom_left,
+        ],
+        [top_left, top_right, bottom_right, bottom_left, ..] => {
+            [top_left, top_right, bottom_right, bottom_left]
+        }
+        [] => unreachable!("at least one radius is parsed"),
+    })(input)
+}
+
+/// Parser for strings that represent [`CornerRadii`], like the value of `border-radius`.
+///
+/// Corresponds to the same order used in the CSS `border-radius` property, either
+/// one, two, three or four radii can be given:
+/// * top-left | top-right | bottom-right | bottom-left
+/// * top-left | top-right and bottom-left | bottom-right
+/// * top-left and bottom-right | top-right and bottom-left
+/// * all corners
+///
+/// Elliptical corners are given by a second list of vertical radii after a `/`,
+/// like `10px 20px / 5px`. A zero radius may omit the unit and negative radii are
+/// invalid, like in css.
+pub fn border_radius_parser(input: &str) -> IResult<&str, CornerRadii> {
+    map(
+        pair(
+            corner_values_parser,
+            opt(preceded(token("/"), cut(corner_values_parser))),
+        ),
+        |(horizontal, vertical)| {
+            let vertical = vertical.unwrap_or(horizontal);
+            let corner = |index: usize| CornerRadius {
+                horizontal: horizontal[index],
+                vertical: vertical[index],
+            };
+            CornerRadii {
+                top_left: corner(0),
+                top_right: corner(1),
+                bottom_right: corner(2),
+                bottom_left: corner(3),
+            }
+        },
+    )(input)
+}
+
+/// Wrapper for [`border_radius_parser`] that returns optional [`CornerRadii`]
+pub fn border_radius_string_parser(input: &str) -> Option<CornerRadii> {
+    border_radius_parser(input).map(|(_, value)| value).ok()
+}
+
+/// Wrapper for [`border_radius_parser`] that returns a [`ParseError`] on failure
+///
+/// Unlike [`border_radius_string_parser`] the whole input must be consumed.
+pub fn border_radius_result_parser(input: &str) -> Result<CornerRadii, ParseError> {
+    parse_all(input, border_radius_parser)
+}
+
+/// Formats the fewest radii that represent the four corners
+fn corner_values_formatter([top_left, top_right, bottom_right, bottom_left]: [Val; 4]) -> String {
+    let values = if top_right != bottom_left {
+        vec![top_left, top_right, bottom_right, bottom_left]
+    } else if top_left != bottom_right {
+        vec![top_left, top_right, bottom_right]
+    } else if top_left != top_right {
+        vec![top_left, top_right]
+    } else {
+        vec![top_left]
+    };
+    values
+        .into_iter()
+        .map(val_formatter)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Formats [`CornerRadii`] as a string, the counterpart of [`border_radius_parser`]
+///
+/// Uses the shortest form, like `10px` or `10px 20px / 5px`.
+pub fn border_radius_formatter(radii: CornerRadii) -> String {
+    let corners = [
+        radii.top_left,
+        radii.top_right,
+        radii.bottom_right,
+        radii.bottom_left,
+    ];
+    let horizontal = corners.map(|corner| corner.horizontal);
+    let vertical = corners.map(|corner| corner.vertical);
+    if horizontal == vertical {
+        corner_values_formatter(horizontal)
+    } else {
+        format!(
+            "{} / {}",
+            corner_values_formatter(horizontal),
+            corner_values_formatter(vertical)
+        )
+    }
+}
+
+/// Parser for strings that represent a [`CornerRadius`], like the value of `border-top-left-radius`.
+///
+/// Either a single radius for a circular corner or a horizontal and vertical radius,
+/// like `10px` or `10px 20%`.
+pub fn corner_radius_parser(input: &str) -> IResult<&str, CornerRadius> {
+    map(pair_parser(radius_parser), |(horizontal, vertical)| {
+        CornerRadius {
+            horizontal,
+            vertical,
+        }
+    })(input)
+}
+
+/// Wrapper for [`corner_radius_parser`] that returns an optional [`CornerRadius`]
+pub fn corner_radius_string_parser(input: &str) -> Option<CornerRadius> {
+    corner_radius_parser(input).map(|(_, value)| value).ok()
+}
+
+/// Wrapper for [`corner_radius_parser`] that returns a [`ParseError`] on failure
+///
+/// Unlike [`corner_radius_string_parser`] the whole input must be consumed.
+pub fn corner_radius_result_parser(input: &str) -> Result<CornerRadius, ParseError> {
+    parse_all(input, corner_radius_parser)
+}
+
+/// Wrapper for [`border_radius_parser`] that implements a serde deserializer
+#[cfg(feature = "serde")]
+pub fn border_radius_serde_parser<'de, D>(deserializer: D) -> Result<CornerRadii, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::Error;
+    let s: &str = serde::Deserialize::deserialize(deserializer)?;
+    border_radius_result_parser(s)
+        .map_err(|error| D::Error::custom(format!("invalid border radius string: {}", error)))
+}
+
+/// Wrapper for [`border_radius_formatter`] that implements a serde serializer
+#[cfg(feature = "serde")]
+pub fn border_radius_serde_serializer<S>(
+    radii: &CornerRadii,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.serialize_str(&border_radius_formatter(*radii))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const A: Val = Val::Px(1.0);
+    const B: Val = Val::Px(2.0);
+    const C: Val = Val::Px(3.0);
+    const D: Val = Val::Px(4.0);
+
+    #[test_case("1px", [A, A, A, A], "1px" ; "one value")]
+    #[test_case("1px 2px", [A, B, A, B], "1px 2px" ; "two values")]
+    #[test_case("1px 2px 3px", [A, B, C, B], "1px 2px 3px" ; "three values")]
+    #[test_case("1px 2px 3px 4px", [A, B, C, D], "1px 2px 3px 4px" ; "four values")]
+    #[test_case("0", [Val::Px(0.0); 4], "0px" ; "unitless zero")]
+    fn test_border_radius_parser(string: &str, expected: [Val; 4], formatted: &str) {
+        let radii = border_radius_result_parser(string).unwrap();
+        let [top_left, top_right, bottom_right, bottom_left] = expected.map(CornerRadius::circular);
+        assert_eq!(
+            radii,
+            CornerRadii {
+                top_left,
+                top_right,
+                bottom_right,
+                bottom_left,
+            }
+        );
+        assert_eq!(border_radius_formatter(radii), formatted);
+    }
+
+    #[test]
+    fn test_border_radius_parser_elliptical() {
+        let radii = border_radius_result_parser(" 10px 5% / 20px ").unwrap();
+        assert_eq!(
+            radii.top_left,
+            CornerRadius {
+                horizontal: Val::Px(10.0),
+                vertical: Val::Px(20.0),
+            }
+        );
+        assert_eq!(
+            radii.bottom_left,
+            CornerRadius {
+                horizontal: Val::Percent(5.0),
+                vertical: Val::Px(20.0),
+            }
+        );
+        assert_eq!(border_radius_formatter(radii), "10px 5% / 20px");
+    }
+
+    #[test_case("1px 2px 3px 4px 5px", 16 ; "too many values")]
+    #[test_case("1px /", 5 ; "missing vertical radii")]
+    #[test_case("auto", 0 ; "auto")]
+    #[test_case("-1px", 0 ; "negative")]
+    #[test_case("1px -2%", 4 ; "negative second value")]
+    #[test_case("1px / 2px -3vw", 10 ; "negative vertical radius")]
+    fn test_border_radius_parser_error(string: &str, offset: usize) {
+        assert_eq!(
+            border_radius_result_parser(string).unwrap_err().offset,
+            offset
+        );
+    }
+
+    #[test]
+    fn test_radius_parser_negative() {
+        let error = border_radius_result_parser("10px -5px").unwrap_err();
+        assert_eq!(error.expected, vec!["<non-negative length>"]);
+        assert_eq!(error.fragment, "-5px");
+        assert!(corner_radius_result_parser("10px -5px").is_err());
+        assert_eq!(
+            border_radius_result_parser("0px"),
+            Ok(CornerRadii::all(Val::Px(0.0)))
+        );
+    }
+
+    #[test]
+    fn test_corner_radius_parser() {
+        assert_eq!(
+            corner_radius_result_parser("10px 20%"),
+            Ok(CornerRadius {
+                horizontal: Val::Px(10.0),
+                vertical: Val::Percent(20.0),
+            })
+        );
+        assert_eq!(
+            corner_radius_string_parser("50%"),
+            Some(CornerRadius::circular(Val::Percent(50.0)))
+        );
+        assert!(corner_radius_result_parser("1px 2px 3px").is_err());
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests_serde {
+    use super::CornerRadii;
+    use bevy::ui::Val;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Deserialize, Serialize)]
+    pub struct Foo {
+        #[serde(
+            deserialize_with = "super::border_radius_serde_parser",
+            serialize_with = "super::border_radius_serde_serializer"
+        )]
+        pub radius: CornerRadii,
+    }
+
+    #[test]
+    fn test_border_radius_serde() {
+        let foo: Foo = serde_json::from_str(r#"{"radius": "8px"}"#).unwrap();
+        assert_eq!(foo.radius, CornerRadii::all(Val::Px(8.0)));
+        assert_eq!(serde_json::to_string(&foo).unwrap(), r#"{"radius":"8px"}"#);
+    }
+}
@@ -4,10 +4,10 @@ use nom::{
     character::complete::multispace0 as multispace,
     combinator::{complete, map},
     sequence::{preceded, tuple},
-    IResult,
 };
 
-use super::val_parser;
+use super::error::{parse_all, IResult, ParseError};
+use super::{val_formatter, val_parser};
 
 /// Parse rect from a string of four val strings
 ///
@@ -84,6 +84,40 @@ pub fn rect_string_parser(input: &str) -> Option<ui::UiRect> {
     rect_parser(input).map(|(_, value)| value).ok()
 }
 
+/// Wrapper for [`rect_parser`] that returns a [`ParseError`] on failure
+///
+/// Unlike [`rect_string_parser`] the whole input must be consumed.
+pub fn rect_result_parser(input: &str) -> Result<ui::UiRect, ParseError> {
+    parse_all(input, rect_parser)
+}
+
+/// Formats a [`bevy::ui::UiRect`] as a string, the counterpart of [`rect_parser`]
+///
+/// Uses the shortest of the CSS shorthands, for example `10px 20px` instead of
+/// `10px 20px 10px 20px`.
+pub fn rect_formatter(rect: ui::UiRect) -> String {
+    let ui::UiRect {
+        left,
+        right,
+        top,
+        bottom,
+    } = rect;
+    let values = if left != right {
+        vec![top, right, bottom, left]
+    } else if top != bottom {
+        vec![top, right, bottom]
+    } else if top != right {
+        vec![top, right]
+    } else {
+        vec![top]
+    };
+    values
+        .into_iter()
+        .map(val_formatter)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 /// Wrapper for [`rect_parser`] that implements a serde deserializer
 #[cfg(feature = "serde")]
 pub fn rect_serde_parser<'de, D>(deserializer: D) -> Result<ui::UiRect, D::Error>
@@ -92,7 +126,17 @@ where
 {
     use serde::de::Error;
     let s: &str = serde::Deserialize::deserialize(deserializer)?;
-    rect_string_parser(s).ok_or(D::Error::custom("invalid rect string"))
+    rect_result_parser(s)
+        .map_err(|error| D::Error::custom(format!("invalid rect string: {}", error)))
+}
+
+/// Wrapper for [`rect_formatter`] that implements a serde serializer
+#[cfg(feature = "serde")]
+pub fn rect_serde_serializer<S>(rect: &ui::UiRect, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.serialize_str(&rect_formatter(*rect))
 }
 
 #[cfg(test)]
@@ -137,16 +181,63 @@ mod tests {
             ))
         );
     }
+
+    #[test]
+    fn test_rect_formatter() {
+        use ui::Val::{Auto, Percent, Px};
+        let cases = [
+            (ui::UiRect::all(Px(10.0)), "10px"),
+            (
+                ui::UiRect::new(Px(20.0), Px(20.0), Px(10.0), Px(10.0)),
+                "10px 20px",
+            ),
+            (
+                ui::UiRect::new(Auto, Auto, Px(10.0), Percent(5.0)),
+                "10px auto 5%",
+            ),
+            (
+                ui::UiRect::new(Px(4.0), Px(2.0), Px(1.0), Px(3.0)),
+                "1px 2px 3px 4px",
+            ),
+            (
+                ui::UiRect::new(Px(4.0), Px(2.0), Px(1.0), Px(1.0)),
+                "1px 2px 1px 4px",
+            ),
+        ];
+        for (rect, expected) in cases {
+            assert_eq!(rect_formatter(rect), expected);
+            assert_eq!(rect_result_parser(expected), Ok(rect));
+        }
+    }
+
+    #[test]
+    fn test_rect_result_parser() {
+        assert_eq!(
+            rect_result_parser("1px 2px"),
+            Ok(ui::UiRect::new(
+                ui::Val::Px(2.0),
+                ui::Val::Px(2.0),
+                ui::Val::Px(1.0),
+                ui::Val::Px(1.0)
+            ))
+        );
+        let error = rect_result_parser("1px 2pt").unwrap_err();
+        assert_eq!(error.offset, 4);
+        assert_eq!(error.fragment, "2pt");
+    }
 }
 
 #[cfg(all(test, feature = "serde"))]
 mod tests_serde {
     use bevy::ui::{UiRect, Val};
-    use serde::Deserialize;
+    use serde::{Deserialize, Serialize};
 
-    #[derive(Deserialize)]
+    #[derive(Deserialize, Serialize)]
     pub struct Foo {
-        #[serde(deserialize_with = "super::rect_serde_parser")]
+        #[serde(
+            deserialize_with = "super::rect_serde_parser",
+            serialize_with = "super::rect_serde_serializer"
+        )]
         pub rect: UiRect,
     }
 
@@ -155,4 +246,15 @@ mod tests_serde {
         let foo: Foo = serde_json::from_str(r#"{"rect": "42px"}"#).unwrap();
         assert_eq!(foo.rect, UiRect::all(Val::Px(42.0)));
     }
+
+    #[test]
+    fn test_rect_serde_serializer() {
+        let foo = Foo {
+            rect: UiRect::axes(Val::Px(20.0), Val::Px(10.0)),
+        };
+        assert_eq!(
+            serde_json::to_string(&foo).unwrap(),
+            r#"{"rect":"10px 20px"}"#
+        );
+    }
 }
\ No newline at end of file
@@ -0,0 +1,345 @@
+use bevy::{render::color::Color, ui::Val};
+use nom::{
+    branch::alt,
+    character::complete::multispace0 as multispace,
+    combinator::{map, verify},
+    multi::separated_list1,
+    sequence::delimited,
+};
+
+use super::common::{comma_parser, token};
+use super::error::{parse_all, IResult, InputError, ParseError};
+use super::val::{val_is_negative, val_length_or_zero_parser};
+use super::{color_formatter, color_parser, val_formatter};
+
+/// A shadow of a `box-shadow` or `text-shadow` list
+///
+/// The color is `None` if the shadow didn't specify one, css uses the text color then.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Shadow {
+    pub offset_x: Val,
+    pub offset_y: Val,
+    pub blur: Val,
+    pub spread: Val,
+    pub color: Option<Color>,
+    /// Whether the shadow is drawn inside the border box, only for `box-shadow`
+    pub inset: bool,
+}
+
+/// A part of a shadow
+enum ShadowPart {
+    Inset,
+    Lengths(Vec<Val>),
+    Color(Color),
+}
+
+/// Parses a shadow length, like `2px`, also accepting `0` without unit
+fn shadow_length_parser(input: &str) -> IResult<&str, Val> {
+    delimited(multispace, val_length_or_zero_parser, multispace)(input)
+}
+
+/// Parses two to `max_lengths` shadow lengths, the offsets, blur radius and spread distance
+///
+/// A negative blur radius is invalid like in css, it fails without backtracking.
+fn shadow_lengths_parser(input: &str, max_lengths: usize) -> IResult<&str, Vec<Val>> {
+    let mut lengths = Vec::new();
+    let mut rest = input;
+    while lengths.len() < max_lengths {
+        let (start, _) = multispace(rest)?;
+        let (next, length) = match shadow_length_parser(start) {
+            Err(nom::Err::Error(_)) if lengths.len() >= 2 => break,
+            result => result?,
+        };
+        if lengths.len() == 2 && val_is_negative(length) {
+            return Err(nom::Err::Failure(InputError::expected(
+                start,
+                "<non-negative blur radius>",
+            )));
+        }
+        lengths.push(length);
+        rest = next;
+    }
+    Ok((rest, lengths))
+}
+
+/// Parses a single shadow, with up to three lengths for text shadows and four for box shadows
+fn shadow_parser(input: &str, text: bool) -> IResult<&str, Shadow> {
+    let max_lengths = if text { 3 } else { 4 };
+    let (mut rest, _) = multispace(input)?;
+    let (mut inset, mut lengths, mut color) = (None, None, None);
+    let mut parts = 0;
+    loop {
+        let inset_parser = verify(
+            delimited(multispace, token("inset"), multispace),
+            |_: &str| !text,
+        );
+        let (next, part) = match alt((
+            map(inset_parser, |_| ShadowPart::Inset),
+            map(
+                |input| shadow_lengths_parser(input, max_lengths),
+                ShadowPart::Lengths,
+            ),
+            map(color_parser, ShadowPart::Color),
+        ))(rest)
+        {
+            Err(nom::Err::Error(_)) if parts > 0 => break,
+            result => result?,
+        };
+        let taken = match part {
+            ShadowPart::Inset => inset.replace(true).is_some(),
+            ShadowPart::Lengths(values) => lengths.replace(values).is_some(),
+            ShadowPart::Color(value) => color.replace(value).is_some(),
+        };
+        if taken {
+            break;
+        }
+        rest = next;
+        parts += 1;
+    }
+    let Some(lengths) = lengths else {
+        return Err(nom::Err::Error(InputError::expected(
+            rest,
+            "<shadow offset>",
+        )));
+    };
+    let length = |index| lengths.get(index).copied().unwrap_or(Val::Px(0.0));
+    Ok((
+        rest,
+        Shadow {
+            offset_x: length(0),
+            offset_y: length(1),
+            blur: length(2),
+            spread: length(3),
+            color,
+            inset: inset.unwrap_or(false),
+        },
+    ))
+}
+
+/// Parses a comma separated list of shadows or `none`
+fn shadow_list_parser(input: &str, text: bool) -> IResult<&str, Vec<Shadow>> {
+    alt((
+        map(delimited(multispace, token("none"), multispace), |_| {
+            Vec::new()
+        }),
+        separated_list1(comma_parser, |input| shadow_parser(input, text)),
+    ))(input)
+}
+
+/// Parser for strings that represent a list of [`Shadow`]s, like the value of `box-shadow`.
+///
+/// The syntax follows CSS, shadows are separated by commas:
+///
+/// * `2px 4px` -> horizontal and vertical offset
+/// * `0 2px 4px 1px` -> offsets, blur radius and spread distance, `0` needs no unit
+/// * `0 2px 4px rgba(0, 0, 0, 0.3)`, `red 1px 1px` -> with a color before or after the lengths
+/// * `inset 0 0 0 1px #fff` -> drawn inside the border box
+/// * `none` -> no shadows
+///
+pub fn box_shadow_parser(input: &str) -> IResult<&str, Vec<Shadow>> {
+    shadow_list_parser(input, false)
+}
+
+/// Wrapper for [`box_shadow_parser`] that returns an optional list of [`Shadow`]s
+pub fn box_shadow_string_parser(input: &str) -> Option<Vec<Shadow>> {
+    box_shadow_parser(input).map(|(_, value)| value).ok()
+}
+
+/// Wrapper for [`box_shadow_parser`] that returns a [`ParseError`] on failure
+///
+/// Unlike [`box_shadow_string_parser`] the whole input must be consumed.
+pub fn box_shadow_result_parser(input: &str) -> Result<Vec<Shadow>, ParseError> {
+    parse_all(input, box_shadow_parser)
+}
+
+/// Parser for strings that represent a list of [`Shadow`]s, like the value of `text-shadow`.
+///
+/// Like [`box_shadow_parser`], but without spread distance and `inset`, like `1px 1px 2px black`.
+pub fn text_shadow_parser(input: &str) -> IResult<&str, Vec<Shadow>> {
+    shadow_list_parser(input, true)
+}
+
+/// Wrapper for [`text_shadow_parser`] that returns an optional list of [`Shadow`]s
+pub fn text_shadow_string_parser(input: &str) -> Option<Vec<Shadow>> {
+    text_shadow_parser(input).map(|(_, value)| value).ok()
+}
+
+/// Wrapper for [`text_shadow_parser`] that returns a [`ParseError`] on failure
+///
+/// Unlike [`text_shadow_string_parser`] the whole input must be consumed.
+pub fn text_shadow_result_parser(input: &str) -> Result<Vec<Shadow>, ParseError> {
+    parse_all(input, text_shadow_parser)
+}
+
+/// Formats a list of [`Shadow`]s as a string, the counterpart of [`box_shadow_parser`]
+///
+/// Trailing zero lengths are omitted, like `inset 0px 2px #000000`, colors are formatted
+/// with [`super::color_formatter`].
+pub fn box_shadow_formatter(shadows: &[Shadow]) -> String {
+    if shadows.is_empty() {
+        return "none".to_string();
+    }
+    shadows
+        .iter()
+        .map(|shadow| {
+            let mut lengths = vec![shadow.offset_x, shadow.offset_y, shadow.blur, shadow.spread];
+            while lengths.len() > 2 && lengths.last() == Some(&Val::Px(0.0)) {
+                lengths.pop();
+            }
+            let mut parts = Vec::new();
+            if shadow.inset {
+                parts.push("inset".to_string());
+            }
+            parts.extend(lengths.into_iter().map(val_formatter));
+            parts.extend(shadow.color.map(color_formatter));
+            parts.join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Wrapper for [`box_shadow_parser`] that implements a serde deserializer
+#[cfg(feature = "serde")]
+pub fn box_shadow_serde_parser<'de, D>(deserializer: D) -> Result<Vec<Shadow>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::Error;
+    let s: &str = serde::Deserialize::deserialize(deserializer)?;
+    box_shadow_result_parser(s)
+        .map_err(|error| D::Error::custom(format!("invalid box shadow string: {}", error)))
+}
+
+/// Wrapper for [`text_shadow_parser`] that implements a serde deserializer
+#[cfg(feature = "serde")]
+pub fn text_shadow_serde_parser<'de, D>(deserializer: D) -> Result<Vec<Shadow>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::Error;
+    let s: &str = serde::Deserialize::deserialize(deserializer)?;
+    text_shadow_result_parser(s)
+        .map_err(|error| D::Error::custom(format!("invalid text shadow string: {}", error)))
+}
+
+/// Wrapper for [`box_shadow_formatter`] that implements a serde serializer
+#[cfg(feature = "serde")]
+pub fn box_shadow_serde_serializer<S>(shadows: &[Shadow], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.serialize_str(&box_shadow_formatter(shadows))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    fn shadow(lengths: [f32; 4], color: Option<Color>, inset: bool) -> Shadow {
+        let [offset_x, offset_y, blur, spread] = lengths.map(Val::Px);
+        Shadow {
+            offset_x,
+            offset_y,
+            blur,
+            spread,
+            color,
+            inset,
+        }
+    }
+
+    #[test_case("2px 4px", shadow([2.0, 4.0, 0.0, 0.0], None, false) ; "offsets")]
+    #[test_case("0 2px 4px rgba(0, 0, 0, 0.3)", shadow([0.0, 2.0, 4.0, 0.0], Some(Color::rgba(0.0, 0.0, 0.0, 0.3)), false) ; "unitless zero and color")]
+    #[test_case("red 1px 1px 2px -1px", shadow([1.0, 1.0, 2.0, -1.0], Some(Color::RED), false) ; "color first with spread")]
+    #[test_case("inset 0 0 0 1px #fff", shadow([0.0, 0.0, 0.0, 1.0], Some(Color::WHITE), true) ; "inset first")]
+    #[test_case("1px 2px blue inset", shadow([1.0, 2.0, 0.0, 0.0], Some(Color::BLUE), true) ; "inset last")]
+    fn test_box_shadow_parser(string: &str, expected: Shadow) {
+        assert_eq!(box_shadow_result_parser(string), Ok(vec![expected]));
+    }
+
+    #[test]
+    fn test_box_shadow_parser_list() {
+        let shadows =
+            box_shadow_result_parser("0 2px 4px rgba(0,0,0,0.3), inset 0 0 0 1px #fff").unwrap();
+        assert_eq!(shadows.len(), 2);
+        assert!(!shadows[0].inset);
+        assert!(shadows[1].inset);
+        assert_eq!(box_shadow_string_parser(" none "), Some(vec![]));
+        assert_eq!(
+            box_shadow_formatter(&shadows),
+            "0px 2px 4px rgb(0 0 0 / 0.3), inset 0px 0px 0px 1px #ffffff"
+        );
+        assert_eq!(box_shadow_formatter(&[]), "none");
+    }
+
+    #[test_case("1px" ; "single length")]
+    #[test_case("1px 2px 3px 4px 5px" ; "too many lengths")]
+    #[test_case("red" ; "color only")]
+    #[test_case("1px 2px -3px" ; "negative blur")]
+    #[test_case("1px 2px red blue" ; "two colors")]
+    #[test_case("1px 2px, " ; "trailing comma")]
+    fn test_box_shadow_parser_error(string: &str) {
+        assert!(box_shadow_result_parser(string).is_err());
+    }
+
+    #[test_case("1px 2px -3px", 8 ; "negative px blur")]
+    #[test_case("red 1px 2px -2vw", 12 ; "negative vw blur")]
+    #[test_case("1px 2px  -1vmax 4px", 9 ; "negative vmax blur with spread")]
+    fn test_box_shadow_parser_negative_blur(string: &str, offset: usize) {
+        let error = box_shadow_result_parser(string).unwrap_err();
+        assert_eq!(error.offset, offset);
+        assert_eq!(error.expected, vec!["<non-negative blur radius>"]);
+    }
+
+    #[test]
+    fn test_text_shadow_parser() {
+        assert_eq!(
+            text_shadow_result_parser("1px 1px 2px black"),
+            Ok(vec![shadow(
+                [1.0, 1.0, 2.0, 0.0],
+                Some(Color::BLACK),
+                false
+            )])
+        );
+        assert_eq!(
+            text_shadow_string_parser("1px 1px, red 0 0 1px").map(|shadows| shadows.len()),
+            Some(2)
+        );
+        assert!(text_shadow_result_parser("inset 1px 1px").is_err());
+        assert!(text_shadow_result_parser("1px 1px 2px 3px").is_err());
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests_serde {
+    use super::Shadow;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Deserialize, Serialize)]
+    pub struct Foo {
+        #[serde(
+            deserialize_with = "super::box_shadow_serde_parser",
+            serialize_with = "super::box_shadow_serde_serializer"
+        )]
+        pub box_shadow: Vec<Shadow>,
+        #[serde(
+            deserialize_with = "super::text_shadow_serde_parser",
+            serialize_with = "super::box_shadow_serde_serializer"
+        )]
+        pub text_shadow: Vec<Shadow>,
+    }
+
+    #[test]
+    fn test_shadow_serde() {
+        let foo: Foo = serde_json::from_str(
+            r#"{"box_shadow": "0 2px 4px black", "text_shadow": "1px 1px red"}"#,
+        )
+        .unwrap();
+        assert_eq!(foo.box_shadow.len(), 1);
+        assert_eq!(foo.text_shadow[0].offset_x, bevy::ui::Val::Px(1.0));
+        assert_eq!(
+            serde_json::to_string(&foo).unwrap(),
+            r#"{"box_shadow":"0px 2px 4px #000000","text_shadow":"1px 1px #ff0000"}"#
+        );
+    }
+}
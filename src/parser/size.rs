@@ -0,0 +1,145 @@
+use bevy::ui::Val;
+
+use super::calc::{calc_parser, CalcExpr};
+use super::common::pair_parser;
+use super::error::{parse_all, IResult, ParseError};
+use super::{val_formatter, val_parser};
+
+/// Parser for strings that represent a pair of [`bevy::ui::Val`]s, like a size or a gap.
+///
+/// Either one or two val strings can be given, a single value is used for both:
+///
+/// * `100px 50%` -> `(Val::Px(100.0), Val::Percent(50.0))`
+/// * `auto` -> `(Val::Auto, Val::Auto)`
+///
+/// The order is `width height` for sizes and `row column` for the `gap` shorthand.
+pub fn size_parser(input: &str) -> IResult<&str, (Val, Val)> {
+    pair_parser(val_parser)(input)
+}
+
+/// Wrapper for [`size_parser`] that returns an optional pair of [`bevy::ui::Val`]s
+pub fn size_string_parser(input: &str) -> Option<(Val, Val)> {
+    size_parser(input).map(|(_, value)| value).ok()
+}
+
+/// Wrapper for [`size_parser`] that returns a [`ParseError`] on failure
+///
+/// Unlike [`size_string_parser`] the whole input must be consumed.
+pub fn size_result_parser(input: &str) -> Result<(Val, Val), ParseError> {
+    parse_all(input, size_parser)
+}
+
+/// Formats a pair of [`bevy::ui::Val`]s as a string, the counterpart of [`size_parser`]
+///
+/// Equal values are formatted only once, like `auto`.
+pub fn size_formatter((first, second): (Val, Val)) -> String {
+    if first == second {
+        val_formatter(first)
+    } else {
+        format!("{} {}", val_formatter(first), val_formatter(second))
+    }
+}
+
+/// Parser for strings that represent a pair of [`CalcExpr`]s.
+///
+/// Like [`size_parser`], but each value may be a math function as supported by
+/// [`super::calc_parser`], like `calc(100% - 20px) 50%`.
+pub fn calc_size_parser(input: &str) -> IResult<&str, (CalcExpr, CalcExpr)> {
+    pair_parser(calc_parser)(input)
+}
+
+/// Wrapper for [`calc_size_parser`] that returns an optional pair of [`CalcExpr`]s
+pub fn calc_size_string_parser(input: &str) -> Option<(CalcExpr, CalcExpr)> {
+    calc_size_parser(input).map(|(_, value)| value).ok()
+}
+
+/// Wrapper for [`calc_size_parser`] that returns a [`ParseError`] on failure
+///
+/// Unlike [`calc_size_string_parser`] the whole input must be consumed.
+pub fn calc_size_result_parser(input: &str) -> Result<(CalcExpr, CalcExpr), ParseError> {
+    parse_all(input, calc_size_parser)
+}
+
+/// Wrapper for [`size_parser`] that implements a serde deserializer
+#[cfg(feature = "serde")]
+pub fn size_serde_parser<'de, D>(deserializer: D) -> Result<(Val, Val), D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::Error;
+    let s: &str = serde::Deserialize::deserialize(deserializer)?;
+    size_result_parser(s)
+        .map_err(|error| D::Error::custom(format!("invalid size string: {}", error)))
+}
+
+/// Wrapper for [`size_formatter`] that implements a serde serializer
+#[cfg(feature = "serde")]
+pub fn size_serde_serializer<S>(size: &(Val, Val), serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.serialize_str(&size_formatter(*size))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("100px 50%", (Val::Px(100.0), Val::Percent(50.0)) ; "two values")]
+    #[test_case("auto", (Val::Auto, Val::Auto) ; "auto")]
+    #[test_case("50vw", (Val::Vw(50.0), Val::Vw(50.0)) ; "single value")]
+    #[test_case(" 1px  auto ", (Val::Px(1.0), Val::Auto) ; "with whitespace")]
+    fn test_size_parser(string: &str, expected: (Val, Val)) {
+        assert_eq!(size_result_parser(string), Ok(expected));
+        assert_eq!(size_result_parser(&size_formatter(expected)), Ok(expected));
+    }
+
+    #[test]
+    fn test_size_result_parser_error() {
+        let error = size_result_parser("10px 20px 30px").unwrap_err();
+        assert_eq!(error.offset, 10);
+        assert_eq!(error.fragment, "30px");
+        assert_eq!(size_string_parser("10"), None);
+
+        let error = size_result_parser("10px20px").unwrap_err();
+        assert_eq!(error.offset, 4);
+        assert_eq!(error.fragment, "20px");
+        assert!(calc_size_result_parser("calc(1px + 2px)calc(3px)").is_err());
+    }
+
+    #[test]
+    fn test_calc_size_parser() {
+        let (width, height) = calc_size_result_parser("calc(100% - 20px) min(50%, 10vh)").unwrap();
+        assert_eq!(Some(width), crate::calc_string_parser("calc(100% - 20px)"));
+        assert_eq!(Some(height), crate::calc_string_parser("min(50%, 10vh)"));
+        let (width, height) = calc_size_string_parser("calc(1px + 2px)").unwrap();
+        assert_eq!(width, height);
+        assert!(calc_size_result_parser("calc(1px + 2px) 3").is_err());
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests_serde {
+    use bevy::ui::Val;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Deserialize, Serialize)]
+    pub struct Foo {
+        #[serde(
+            deserialize_with = "super::size_serde_parser",
+            serialize_with = "super::size_serde_serializer"
+        )]
+        pub size: (Val, Val),
+    }
+
+    #[test]
+    fn test_size_serde() {
+        let foo: Foo = serde_json::from_str(r#"{"size": "100px 50%"}"#).unwrap();
+        assert_eq!(foo.size, (Val::Px(100.0), Val::Percent(50.0)));
+        assert_eq!(
+            serde_json::to_string(&foo).unwrap(),
+            r#"{"size":"100px 50%"}"#
+        );
+    }
+}
@@ -0,0 +1,671 @@
+use bevy::{
+    render::color::Color,
+    ui::{Style, UiRect},
+};
+use nom::{
+    branch::alt,
+    character::complete::multispace0 as multispace,
+    combinator::{map, opt},
+    sequence::{delimited, preceded, tuple},
+};
+
+use super::border::{
+    border_color_parser, border_line_parser, border_parser, border_width_rect_parser, BorderSide,
+};
+use super::common::{identifier_parser, number_parser, token};
+use super::error::{parse_all, IResult, InputError, ParseError};
+use super::grid::{
+    grid_area_parser, grid_auto_tracks_parser, grid_placement_parser, grid_template_parser,
+};
+use super::grid_areas::{grid_template_areas_parser, GridTemplateAreas};
+use super::keyword::{
+    align_content_parser, align_items_parser, align_self_parser, direction_parser, display_parser,
+    flex_direction_parser, flex_wrap_parser, grid_auto_flow_parser, justify_content_parser,
+    justify_items_parser, justify_self_parser, overflow_axis_parser, overflow_parser,
+    position_type_parser,
+};
+use super::{rect_parser, size_parser, val_parser};
+
+/// The declarations of an inline style block, see [`style_block_parser`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StyleBlock {
+    pub style: Style,
+    /// The color of `border` or `border-color` for the [`bevy::ui::BorderColor`] of the
+    /// node, `None` if no color was declared
+    pub border_color: Option<Color>,
+    /// The areas of `grid-template-areas`, empty if not declared, they can be added as
+    /// a component to the grid container, see [`crate::GridAreasPlugin`]
+    pub grid_template_areas: GridTemplateAreas,
+}
+
+/// Parses the value with the parser and writes it into the field
+fn set<'a, T>(
+    input: &'a str,
+    mut parser: impl FnMut(&'a str) -> IResult<&'a str, T>,
+    field: &mut T,
+) -> IResult<&'a str, ()> {
+    let (input, value) = parser(input)?;
+    *field = value;
+    Ok((input, ()))
+}
+
+/// Parses a single side border, like `1px red`, and writes it into the edge of the side
+fn border_side<'a>(
+    input: &'a str,
+    block: &mut StyleBlock,
+    side: BorderSide,
+) -> IResult<&'a str, ()> {
+    let (input, line) = border_line_parser(input)?;
+    *side.edge_mut(&mut block.style.border) = line.width;
+    if line.color.is_some() {
+        block.border_color = line.color;
+    }
+    Ok((input, ()))
+}
+
+/// Parses an aspect ratio, like `16 / 9`, `1.5` or `auto`
+///
+/// Ratios that are not finite and positive, like `1 / 0`, fail without backtracking.
+fn aspect_ratio_parser(input: &str) -> IResult<&str, Option<f32>> {
+    let (rest, ratio) = alt((
+        map(token("auto"), |_| None),
+        map(
+            tuple((
+                number_parser,
+                opt(preceded(
+                    delimited(multispace, token("/"), multispace),
+                    number_parser,
+                )),
+            )),
+            |(width, height)| Some(width / height.unwrap_or(1.0)),
+        ),
+    ))(input)?;
+    if ratio.is_some_and(|ratio| !ratio.is_finite() || ratio <= 0.0) {
+        return Err(nom::Err::Failure(InputError::expected(
+            input,
+            "<positive ratio>",
+        )));
+    }
+    Ok((rest, ratio))
+}
+
+/// Signature of the functions that parse the value of a property into the block
+type PropertyParser = for<'a> fn(&'a str, &mut StyleBlock) -> IResult<&'a str, ()>;
+
+/// The properties supported by [`style_parser`] with the parsers of their values
+const PROPERTIES: &[(&str, PropertyParser)] = &[
+    ("display", |input, block| {
+        set(input, display_parser, &mut block.style.display)
+    }),
+    ("position", |input, block| {
+        set(input, position_type_parser, &mut block.style.position_type)
+    }),
+    ("overflow", |input, block| {
+        set(input, overflow_parser, &mut block.style.overflow)
+    }),
+    ("overflow-x", |input, block| {
+        set(input, overflow_axis_parser, &mut block.style.overflow.x)
+    }),
+    ("overflow-y", |input, block| {
+        set(input, overflow_axis_parser, &mut block.style.overflow.y)
+    }),
+    ("direction", |input, block| {
+        set(input, direction_parser, &mut block.style.direction)
+    }),
+    ("left", |input, block| {
+        set(input, val_parser, &mut block.style.left)
+    }),
+    ("right", |input, block| {
+        set(input, val_parser, &mut block.style.right)
+    }),
+    ("top", |input, block| {
+        set(input, val_parser, &mut block.style.top)
+    }),
+    ("bottom", |input, block| {
+        set(input, val_parser, &mut block.style.bottom)
+    }),
+    ("inset", |input, block| {
+        let (input, rect) = rect_parser(input)?;
+        let UiRect {
+            left,
+            right,
+            top,
+            bottom,
+        } = rect;
+        (
+            block.style.left,
+            block.style.right,
+            block.style.top,
+            block.style.bottom,
+        ) = (left, right, top, bottom);
+        Ok((input, ()))
+    }),
+    ("width", |input, block| {
+        set(input, val_parser, &mut block.style.width)
+    }),
+    ("height", |input, block| {
+        set(input, val_parser, &mut block.style.height)
+    }),
+    ("min-width", |input, block| {
+        set(input, val_parser, &mut block.style.min_width)
+    }),
+    ("min-height", |input, block| {
+        set(input, val_parser, &mut block.style.min_height)
+    }),
+    ("max-width", |input, block| {
+        set(input, val_parser, &mut block.style.max_width)
+    }),
+    ("max-height", |input, block| {
+        set(input, val_parser, &mut block.style.max_height)
+    }),
+    // not css, shorthands for the `width height` pairs like the former `Size` of bevy
+    ("size", |input, block| {
+        let (input, (width, height)) = size_parser(input)?;
+        (block.style.width, block.style.height) = (width, height);
+        Ok((input, ()))
+    }),
+    ("min-size", |input, block| {
+        let (input, (width, height)) = size_parser(input)?;
+        (block.style.min_width, block.style.min_height) = (width, height);
+        Ok((input, ()))
+    }),
+    ("max-size", |input, block| {
+        let (input, (width, height)) = size_parser(input)?;
+        (block.style.max_width, block.style.max_height) = (width, height);
+        Ok((input, ()))
+    }),
+    ("aspect-ratio", |input, block| {
+        set(input, aspect_ratio_parser, &mut block.style.aspect_ratio)
+    }),
+    ("align-items", |input, block| {
+        set(input, align_items_parser, &mut block.style.align_items)
+    }),
+    ("justify-items", |input, block| {
+        set(input, justify_items_parser, &mut block.style.justify_items)
+    }),
+    ("align-self", |input, block| {
+        set(input, align_self_parser, &mut block.style.align_self)
+    }),
+    ("justify-self", |input, block| {
+        set(input, justify_self_parser, &mut block.style.justify_self)
+    }),
+    ("align-content", |input, block| {
+        set(input, align_content_parser, &mut block.style.align_content)
+    }),
+    ("justify-content", |input, block| {
+        set(
+            input,
+            justify_content_parser,
+            &mut block.style.justify_content,
+        )
+    }),
+    ("margin", |input, block| {
+        set(input, rect_parser, &mut block.style.margin)
+    }),
+    ("padding", |input, block| {
+        set(input, rect_parser, &mut block.style.padding)
+    }),
+    ("border-width", |input, block| {
+        set(input, border_width_rect_parser, &mut block.style.border)
+    }),
+    ("flex-direction", |input, block| {
+        set(
+            input,
+            flex_direction_parser,
+            &mut block.style.flex_direction,
+        )
+    }),
+    ("flex-wrap", |input, block| {
+        set(input, flex_wrap_parser, &mut block.style.flex_wrap)
+    }),
+    ("flex-grow", |input, block| {
+        set(input, number_parser, &mut block.style.flex_grow)
+    }),
+    ("flex-shrink", |input, block| {
+        set(input, number_parser, &mut block.style.flex_shrink)
+    }),
+    ("flex-basis", |input, block| {
+        set(input, val_parser, &mut block.style.flex_basis)
+    }),
+    ("gap", |input, block| {
+        let (input, (row, column)) = size_parser(input)?;
+        (block.style.row_gap, block.style.column_gap) = (row, column);
+        Ok((input, ()))
+    }),
+    ("row-gap", |input, block| {
+        set(input, val_parser, &mut block.style.row_gap)
+    }),
+    ("column-gap", |input, block| {
+        set(input, val_parser, &mut block.style.column_gap)
+    }),
+    ("grid-auto-flow", |input, block| {
+        set(
+            input,
+            grid_auto_flow_parser,
+            &mut block.style.grid_auto_flow,
+        )
+    }),
+    ("grid-template-rows", |input, block| {
+        set(
+            input,
+            grid_template_parser,
+            &mut block.style.grid_template_rows,
+        )
+    }),
+    ("grid-template-columns", |input, block| {
+        set(
+            input,
+            grid_template_parser,
+            &mut block.style.grid_template_columns,
+        )
+    }),
+    ("grid-auto-rows", |input, block| {
+        set(
+            input,
+            grid_auto_tracks_parser,
+            &mut block.style.grid_auto_rows,
+        )
+    }),
+    ("grid-auto-columns", |input, block| {
+        set(
+            input,
+            grid_auto_tracks_parser,
+            &mut block.style.grid_auto_columns,
+        )
+    }),
+    ("grid-row", |input, block| {
+        set(input, grid_placement_parser, &mut block.style.grid_row)
+    }),
+    ("grid-column", |input, block| {
+        set(input, grid_placement_parser, &mut block.style.grid_column)
+    }),
+    ("grid-area", |input, block| {
+        let (input, (row, column)) = grid_area_parser(input)?;
+        (block.style.grid_row, block.style.grid_column) = (row, column);
+        Ok((input, ()))
+    }),
+    ("border", |input, block| {
+        let (input, border) = border_parser(input)?;
+        block.style.border = border.width;
+        if border.color.is_some() {
+            block.border_color = border.color;
+        }
+        Ok((input, ()))
+    }),
+    ("border-top", |input, block| {
+        border_side(input, block, BorderSide::Top)
+    }),
+    ("border-right", |input, block| {
+        border_side(input, block, BorderSide::Right)
+    }),
+    ("border-bottom", |input, block| {
+        border_side(input, block, BorderSide::Bottom)
+    }),
+    ("border-left", |input, block| {
+        border_side(input, block, BorderSide::Left)
+    }),
+    ("border-color", |input, block| {
+        let (input, border_color) = border_color_parser(input)?;
+        block.border_color = Some(border_color.0);
+        Ok((input, ()))
+    }),
+    ("grid-template-areas", |input, block| {
+        set(
+            input,
+            grid_template_areas_parser,
+            &mut block.grid_template_areas,
+        )
+    }),
+];
+
+/// Parses a single declaration, like `width: 50%`, and writes it into the block
+fn declaration_parser<'a>(input: &'a str, block: &mut StyleBlock) -> IResult<&'a str, ()> {
+    let (rest, name) = identifier_parser(input).map_err(|error| {
+        error.map(|error| InputError {
+            expected: vec!["<property name>"],
+            ..error
+        })
+    })?;
+    let Some((_, property_parser)) = PROPERTIES.iter().find(|(property, _)| *property == name)
+    else {
+        return Err(nom::Err::Error(InputError {
+            input,
+            expected: PROPERTIES.iter().map(|(property, _)| *property).collect(),
+        }));
+    };
+    let (rest, _) = tuple((multispace, token(":"), multispace))(rest)?;
+    let (rest, _) = property_parser(rest, block)?;
+    multispace(rest).map(|(rest, _)| (rest, ()))
+}
+
+/// Parser for inline style declaration blocks into a [`bevy::ui::Style`].
+///
+/// The syntax is inspired by CSS, declarations are separated by `;`:
+///
+/// * `display: flex; width: 50%; padding: 10px 20px; flex-direction: column`
+///
+/// Values are parsed with [`super::val_parser`], [`super::rect_parser`] and the css
+/// keywords of the layout enums, properties that are not declared keep their default
+/// value and later declarations override earlier ones.
+///
+/// Supported properties: `display`, `position`, `overflow`, `overflow-x`, `overflow-y`,
+/// `direction`, `left`, `right`, `top`, `bottom`, `inset`, `width`, `height`,
+/// `min-width`, `min-height`, `max-width`, `max-height`, `aspect-ratio`, `align-items`,
+/// `justify-items`, `align-self`, `justify-self`, `align-content`, `justify-content`,
+/// `margin`, `padding`, `border`, `border-top`, `border-right`, `border-bottom`,
+/// `border-left`, `border-width`, `border-color`, `flex-direction`,
+/// `flex-wrap`, `flex-grow`, `flex-shrink`, `flex-basis`, `gap`, `row-gap`, `column-gap`,
+/// `grid-auto-flow`, `grid-template-rows`, `grid-template-columns`, `grid-auto-rows`,
+/// `grid-auto-columns`, `grid-row`, `grid-column`, `grid-area` and `grid-template-areas`.
+///
+/// Unlike css, `size`, `min-size` and `max-size` set the width and height at once, with
+/// the syntax of [`super::size_parser`], like `size: 100px 50%`.
+///
+/// [`Style`] has no field for the color of the borders and `grid-template-areas`,
+/// they are validated but only returned by [`style_block_parser`].
+pub fn style_parser(input: &str) -> IResult<&str, Style> {
+    map(style_block_parser, |block| block.style)(input)
+}
+
+/// Parser for inline style declaration blocks like [`style_parser`] into a [`StyleBlock`],
+/// that also keeps the declarations which have no field in the [`Style`]
+pub fn style_block_parser(input: &str) -> IResult<&str, StyleBlock> {
+    let mut block = StyleBlock::default();
+    let (mut input, _) = multispace(input)?;
+    loop {
+        // empty declarations are allowed, like in `width: 10px;;`
+        while let (rest, Some(_)) = opt(preceded(token(";"), multispace))(input)? {
+            input = rest;
+        }
+        if input.is_empty() {
+            return Ok((input, block));
+        }
+        let (rest, _) = declaration_parser(input, &mut block)?;
+        match opt(preceded(token(";"), multispace))(rest)? {
+            (rest, Some(_)) => input = rest,
+            (rest, None) => return Ok((rest, block)),
+        }
+    }
+}
+
+/// Wrapper for [`style_block_parser`] that returns an optional [`StyleBlock`]
+pub fn style_block_string_parser(input: &str) -> Option<StyleBlock> {
+    style_block_parser(input).map(|(_, value)| value).ok()
+}
+
+/// Wrapper for [`style_block_parser`] that returns a [`ParseError`] on failure
+///
+/// Unlike [`style_block_string_parser`] the whole input must be consumed.
+pub fn style_block_result_parser(input: &str) -> Result<StyleBlock, ParseError> {
+    parse_all(input, style_block_parser)
+}
+
+/// Wrapper for [`style_parser`] that returns an optional [`bevy::ui::Style`]
+pub fn style_string_parser(input: &str) -> Option<Style> {
+    style_parser(input).map(|(_, value)| value).ok()
+}
+
+/// Wrapper for [`style_parser`] that returns a [`ParseError`] on failure
+///
+/// Unlike [`style_string_parser`] the whole input must be consumed.
+pub fn style_result_parser(input: &str) -> Result<Style, ParseError> {
+    parse_all(input, style_parser)
+}
+
+/// Wrapper for [`style_parser`] that implements a serde deserializer
+#[cfg(feature = "serde")]
+pub fn style_serde_parser<'de, D>(deserializer: D) -> Result<Style, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::Error;
+    let s: &str = serde::Deserialize::deserialize(deserializer)?;
+    style_result_parser(s)
+        .map_err(|error| D::Error::custom(format!("invalid style string: {}", error)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ui::{
+        AlignItems, Display, FlexDirection, GridAutoFlow, GridPlacement, GridTrack,
+        GridTrackRepetition, JustifyContent, MaxTrackSizingFunction, MinTrackSizingFunction,
+        Overflow, OverflowAxis, PositionType, RepeatedGridTrack, Val,
+    };
+    use test_case::test_case;
+
+    #[test]
+    fn test_style_parser() {
+        let style = style_result_parser(
+            "display: flex; width: 50%; padding: 10px 20px; flex-direction: column",
+        )
+        .unwrap();
+        assert_eq!(
+            style,
+            Style {
+                display: Display::Flex,
+                width: Val::Percent(50.0),
+                padding: UiRect::axes(Val::Px(20.0), Val::Px(10.0)),
+                flex_direction: FlexDirection::Column,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_style_parser_properties() {
+        let style = style_result_parser(concat!(
+            " position:absolute ; inset: 0px 10px; top: 5%;",
+            "overflow: clip visible; overflow-y: clip;",
+            "aspect-ratio: 16 / 9; flex-grow: 2; flex-shrink: 0.5; flex-basis: auto;",
+            "align-items: flex-end; justify-content: space-between; ",
+            "row-gap: 1vh; column-gap: 2vw; border-width: 1px; ",
+            "grid-auto-flow: column dense;",
+        ))
+        .unwrap();
+        assert_eq!(
+            style,
+            Style {
+                position_type: PositionType::Absolute,
+                left: Val::Px(10.0),
+                right: Val::Px(10.0),
+                top: Val::Percent(5.0),
+                bottom: Val::Px(0.0),
+                overflow: Overflow {
+                    x: OverflowAxis::Clip,
+                    y: OverflowAxis::Clip,
+                },
+                aspect_ratio: Some(16.0 / 9.0),
+                flex_grow: 2.0,
+                flex_shrink: 0.5,
+                flex_basis: Val::Auto,
+                align_items: AlignItems::FlexEnd,
+                justify_content: JustifyContent::SpaceBetween,
+                row_gap: Val::Vh(1.0),
+                column_gap: Val::Vw(2.0),
+                border: UiRect::all(Val::Px(1.0)),
+                grid_auto_flow: GridAutoFlow::ColumnDense,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_style_parser_gap() {
+        let style = style_result_parser("gap: 10px 5%").unwrap();
+        assert_eq!(
+            (style.row_gap, style.column_gap),
+            (Val::Px(10.0), Val::Percent(5.0))
+        );
+        let style = style_result_parser("gap: 1vw; column-gap: auto").unwrap();
+        assert_eq!((style.row_gap, style.column_gap), (Val::Vw(1.0), Val::Auto));
+    }
+
+    #[test]
+    fn test_style_parser_grid() {
+        let style = style_result_parser(concat!(
+            "display: grid; grid-template-columns: repeat(auto-fill, minmax(120px, 1fr)) 200px;",
+            "grid-template-rows: 1fr auto; grid-auto-rows: 100px; grid-auto-columns: 1fr 2fr",
+        ))
+        .unwrap();
+        assert_eq!(
+            style.grid_template_columns,
+            vec![
+                RepeatedGridTrack::repeat_many(
+                    GridTrackRepetition::AutoFill,
+                    vec![GridTrack::minmax(
+                        MinTrackSizingFunction::Px(120.0),
+                        MaxTrackSizingFunction::Fraction(1.0)
+                    )]
+                ),
+                GridTrack::px(200.0),
+            ]
+        );
+        assert_eq!(
+            style.grid_template_rows,
+            vec![GridTrack::fr(1.0), GridTrack::auto()]
+        );
+        assert_eq!(style.grid_auto_rows, vec![GridTrack::px(100.0)]);
+        assert_eq!(
+            style.grid_auto_columns,
+            vec![GridTrack::fr(1.0), GridTrack::fr(2.0)]
+        );
+
+        let style = style_result_parser("grid-area: 1 / 2 / 3; grid-column: span 2").unwrap();
+        assert_eq!(style.grid_row, GridPlacement::start_end(1, 3));
+        assert_eq!(style.grid_column, GridPlacement::span(2));
+    }
+
+    #[test]
+    fn test_style_block_parser_border() {
+        let block = style_block_result_parser("border: 2px solid red").unwrap();
+        assert_eq!(block.style.border, UiRect::all(Val::Px(2.0)));
+        assert_eq!(block.border_color, Some(Color::RED));
+
+        let block = style_block_result_parser("border-color: blue; border: 0").unwrap();
+        assert_eq!(block.style.border, UiRect::all(Val::Px(0.0)));
+        assert_eq!(block.border_color, Some(Color::BLUE));
+        assert_eq!(
+            style_block_result_parser("border: 1px")
+                .unwrap()
+                .border_color,
+            None
+        );
+
+        let error = style_result_parser("border: 1px dashed red").unwrap_err();
+        assert_eq!(error.offset, 12);
+        assert_eq!(error.fragment, "dashed");
+    }
+
+    #[test]
+    fn test_style_block_parser_border_sides() {
+        let block =
+            style_block_result_parser("border: 2px; border-top: 1px red; border-left: none")
+                .unwrap();
+        assert_eq!(
+            block.style.border,
+            UiRect::new(Val::Px(0.0), Val::Px(2.0), Val::Px(1.0), Val::Px(2.0))
+        );
+        assert_eq!(block.border_color, Some(Color::RED));
+
+        let error = style_result_parser("border-width: -1px").unwrap_err();
+        assert_eq!(error.offset, 14);
+        assert_eq!(error.expected, vec!["<non-negative length>"]);
+    }
+
+    #[test_case("size", |style| (style.width, style.height) ; "size")]
+    #[test_case("min-size", |style| (style.min_width, style.min_height) ; "min size")]
+    #[test_case("max-size", |style| (style.max_width, style.max_height) ; "max size")]
+    fn test_style_parser_sizes(property: &str, get: fn(&Style) -> (Val, Val)) {
+        let style = style_result_parser(&format!("{}: 100px 50%", property)).unwrap();
+        assert_eq!(get(&style), (Val::Px(100.0), Val::Percent(50.0)));
+        let style = style_result_parser(&format!("{}: auto", property)).unwrap();
+        assert_eq!(get(&style), (Val::Auto, Val::Auto));
+    }
+
+    #[test]
+    fn test_style_parser_all_properties() {
+        for (property, _) in PROPERTIES {
+            let error = style_result_parser(&format!("{}: ?", property)).unwrap_err();
+            assert_eq!(error.offset, property.len() + 2, "{}", property);
+        }
+    }
+
+    #[test]
+    fn test_style_block_parser_areas() {
+        let block = style_block_result_parser(
+            r#"display: grid; grid-template-areas: "head head" "side main"; row-gap: 4px"#,
+        )
+        .unwrap();
+        assert_eq!(block.style.display, Display::Grid);
+        assert_eq!(block.style.row_gap, Val::Px(4.0));
+        let areas = block.grid_template_areas;
+        assert_eq!((areas.rows(), areas.columns()), (2, 2));
+        assert_eq!(
+            areas.get("main"),
+            Some((
+                GridPlacement::start_end(2, 3),
+                GridPlacement::start_end(2, 3)
+            ))
+        );
+
+        let block = style_block_result_parser("display: grid").unwrap();
+        assert_eq!(block.grid_template_areas, GridTemplateAreas::default());
+        assert!(style_result_parser(r#"grid-template-areas: "a b""#).is_ok());
+        assert!(style_result_parser(r#"grid-template-areas: "a b" "c""#).is_err());
+    }
+
+    #[test_case("" ; "empty")]
+    #[test_case(" ; " ; "empty declaration")]
+    #[test_case("width: 10px;" ; "trailing semicolon")]
+    fn test_style_parser_empty(string: &str) {
+        assert!(style_result_parser(string).is_ok());
+    }
+
+    #[test]
+    fn test_style_result_parser_errors() {
+        let error = style_result_parser("display: flex; witdh: 10px").unwrap_err();
+        assert_eq!(error.offset, 15);
+        assert_eq!(error.fragment, "witdh");
+        assert!(error.expected.contains(&"width"));
+        assert_eq!(
+            crate::suggest_correction("display: flex; witdh: 10px", &error).as_deref(),
+            Some("display: flex; width: 10px")
+        );
+
+        let error = style_result_parser("display: block").unwrap_err();
+        assert_eq!(error.offset, 9);
+        assert_eq!(error.expected, vec!["flex", "grid", "none"]);
+
+        let error = style_result_parser("width: 10px height: 20px").unwrap_err();
+        assert_eq!(error.offset, 12);
+        assert_eq!(error.expected, vec!["<end of input>"]);
+    }
+
+    #[test_case("aspect-ratio: 1 / 0" ; "zero denominator")]
+    #[test_case("aspect-ratio: 0 / 0" ; "zero ratio")]
+    #[test_case("aspect-ratio: -1.5" ; "negative ratio")]
+    fn test_style_result_parser_aspect_ratio_error(string: &str) {
+        let error = style_result_parser(string).unwrap_err();
+        assert_eq!(error.offset, 14);
+        assert_eq!(error.expected, vec!["<positive ratio>"]);
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests_serde {
+    use bevy::ui::{Display, Style};
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    pub struct Foo {
+        #[serde(deserialize_with = "super::style_serde_parser")]
+        pub style: Style,
+    }
+
+    #[test]
+    fn test_style_serde_parser() {
+        let foo: Foo = serde_json::from_str(r#"{"style": "display: grid"}"#).unwrap();
+        assert_eq!(foo.style.display, Display::Grid);
+    }
+}
@@ -0,0 +1,495 @@
+use bevy::{
+    math::{Affine2, Mat2, Mat4, Vec2, Vec4},
+    transform::components::Transform,
+    ui::Val,
+};
+use nom::{
+    branch::alt,
+    character::complete::multispace0 as multispace,
+    combinator::{map, opt},
+    multi::many1,
+    sequence::{delimited, pair, preceded, tuple},
+};
+
+use super::common::{comma_parser, function_parser, number_argument_parser, number_parser, token};
+use super::error::{parse_all, IResult, ParseError};
+use super::position::{resolve_length, resolve_position};
+use super::val::val_length_or_zero_parser;
+use super::{angle_formatter, angle_parser, position_parser, val_formatter, Position};
+
+/// A single function of a css `transform` list
+///
+/// The single axis functions like `translateX(...)` and `skewY(...)` are stored
+/// as their two axis variant, angles are in radians.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TransformFunction {
+    /// `translate(x, y)`, percentages refer to the size of the node
+    Translate(Val, Val),
+    /// `scale(x, y)`
+    Scale(f32, f32),
+    /// `rotate(angle)`, clockwise like in css
+    Rotate(f32),
+    /// `skew(x, y)`
+    Skew(f32, f32),
+    /// `matrix(a, b, c, d, e, f)`
+    Matrix([f32; 6]),
+}
+
+impl TransformFunction {
+    /// The 2d matrix of the function for a node of the given size
+    pub fn to_affine2(&self, size: Vec2) -> Affine2 {
+        match *self {
+            TransformFunction::Translate(x, y) => Affine2::from_translation(Vec2::new(
+                resolve_length(x, size.x).unwrap_or(0.0),
+                resolve_length(y, size.y).unwrap_or(0.0),
+            )),
+            TransformFunction::Scale(x, y) => Affine2::from_scale(Vec2::new(x, y)),
+            TransformFunction::Rotate(angle) => Affine2::from_angle(angle),
+            TransformFunction::Skew(x, y) => Affine2::from_mat2(Mat2::from_cols(
+                Vec2::new(1.0, y.tan()),
+                Vec2::new(x.tan(), 1.0),
+            )),
+            TransformFunction::Matrix(values) => Affine2::from_cols_array(&values),
+        }
+    }
+}
+
+/// A list of [`TransformFunction`]s, like the value of the css `transform` property
+///
+/// The functions are applied like in css, the last function of the list is
+/// applied to the node first. Bevy ui uses a y-down coordinate system,
+/// so the matrices are the same as in css.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TransformList(pub Vec<TransformFunction>);
+
+impl TransformList {
+    /// Composes the functions into a 2d matrix for a node of the given size
+    ///
+    /// The matrix transforms around the `origin`, relative to the center of the node.
+    pub fn to_affine2(&self, size: Vec2, origin: Position) -> Affine2 {
+        let origin = resolve_position(origin, size) - size / 2.0;
+        let matrix = self.0.iter().fold(Affine2::IDENTITY, |matrix, function| {
+            matrix * function.to_affine2(size)
+        });
+        Affine2::from_translation(origin) * matrix * Affine2::from_translation(-origin)
+    }
+
+    /// Composes the functions into a 3d matrix, see [`TransformList::to_affine2`]
+    pub fn to_mat4(&self, size: Vec2, origin: Position) -> Mat4 {
+        let matrix = self.to_affine2(size, origin);
+        Mat4::from_cols(
+            matrix.matrix2.x_axis.extend(0.0).extend(0.0),
+            matrix.matrix2.y_axis.extend(0.0).extend(0.0),
+            Vec4::Z,
+            matrix.translation.extend(0.0).extend(1.0),
+        )
+    }
+
+    /// Composes the functions into a [`Transform`], see [`TransformList::to_affine2`]
+    ///
+    /// A [`Transform`] can't represent skew, use [`TransformList::to_mat4`] if
+    /// [`TransformList::has_skew`] is true.
+    ///
+    /// The ui layout of bevy overwrites the translation of the [`Transform`] of ui nodes
+    /// whenever it positions them, only the rotation and scale are kept. Translations
+    /// have to be added to the position of the node instead, like with `Style::left`.
+    pub fn to_transform(&self, size: Vec2, origin: Position) -> Transform {
+        Transform::from_matrix(self.to_mat4(size, origin))
+    }
+
+    /// Whether the composed matrix skews, like `skewX(10deg)` or `scale(2, 1) rotate(45deg)`
+    pub fn has_skew(&self) -> bool {
+        let matrix = self.to_affine2(Vec2::ZERO, Position::default()).matrix2;
+        matrix.x_axis.dot(matrix.y_axis).abs() > 1e-6
+    }
+}
+
+/// Parses a translation, like `10px`, `50%` or `0`
+fn translation_parser(input: &str) -> IResult<&str, Val> {
+    delimited(multispace, val_length_or_zero_parser, multispace)(input)
+}
+
+/// Parses a scale factor, like `1.5` or `150%`
+fn factor_parser(input: &str) -> IResult<&str, f32> {
+    delimited(
+        multispace,
+        map(pair(number_parser, opt(token("%"))), |(value, percent)| {
+            if percent.is_some() {
+                value / 100.0
+            } else {
+                value
+            }
+        }),
+        multispace,
+    )(input)
+}
+
+/// Parses a single transform function, like `rotate(45deg)` or `translateX(50%)`
+fn transform_function_parser(input: &str) -> IResult<&str, TransformFunction> {
+    delimited(
+        multispace,
+        alt((
+            map(
+                function_parser(
+                    "translate",
+                    pair(
+                        translation_parser,
+                        opt(preceded(comma_parser, translation_parser)),
+                    ),
+                ),
+                |(x, y)| TransformFunction::Translate(x, y.unwrap_or(Val::Px(0.0))),
+            ),
+            map(function_parser("translateX", translation_parser), |x| {
+                TransformFunction::Translate(x, Val::Px(0.0))
+            }),
+            map(function_parser("translateY", translation_parser), |y| {
+                TransformFunction::Translate(Val::Px(0.0), y)
+            }),
+            map(
+                function_parser(
+                    "scale",
+                    pair(factor_parser, opt(preceded(comma_parser, factor_parser))),
+                ),
+                |(x, y)| TransformFunction::Scale(x, y.unwrap_or(x)),
+            ),
+            map(function_parser("scaleX", factor_parser), |x| {
+                TransformFunction::Scale(x, 1.0)
+            }),
+            map(function_parser("scaleY", factor_parser), |y| {
+                TransformFunction::Scale(1.0, y)
+            }),
+            map(
+                function_parser("rotate", angle_parser),
+                TransformFunction::Rotate,
+            ),
+            map(
+                function_parser(
+                    "skew",
+                    pair(angle_parser, opt(preceded(comma_parser, angle_parser))),
+                ),
+                |(x, y)| TransformFunction::Skew(x, y.unwrap_or(0.0)),
+            ),
+            map(function_parser("skewX", angle_parser), |x| {
+                TransformFunction::Skew(x, 0.0)
+            }),
+            map(function_parser("skewY", angle_parser), |y| {
+                TransformFunction::Skew(0.0, y)
+            }),
+            map(
+                function_parser(
+                    "matrix",
+                    tuple((
+                        number_argument_parser,
+                        preceded(comma_parser, number_argument_parser),
+                        preceded(comma_parser, number_argument_parser),
+                        preceded(comma_parser, number_argument_parser),
+                        preceded(comma_parser, number_argument_parser),
+                        preceded(comma_parser, number_argument_parser),
+                    )),
+                ),
+                |(a, b, c, d, e, f)| TransformFunction::Matrix([a, b, c, d, e, f]),
+            ),
+        )),
+        multispace,
+    )(input)
+}
+
+/// Parser for strings that represent a [`TransformList`], like the value of `transform`.
+///
+/// A whitespace separated list of css transform functions, or `none`:
+///
+/// * `translate(10px, -5px)`, `translateX(50%)`, `translateY(0)`
+/// * `scale(1.2, 0.8)`, `scale(2)`, `scaleX(150%)`, `scaleY(0.5)`
+/// * `rotate(45deg)`, `rotate(0.25turn)`
+/// * `skew(10deg, 5deg)`, `skewX(10deg)`, `skewY(-0.1rad)`
+/// * `matrix(1, 0, 0, 1, 10, 20)`
+///
+pub fn transform_parser(input: &str) -> IResult<&str, TransformList> {
+    alt((
+        map(delimited(multispace, token("none"), multispace), |_| {
+            TransformList::default()
+        }),
+        map(many1(transform_function_parser), TransformList),
+    ))(input)
+}
+
+/// Wrapper for [`transform_parser`] that returns an optional [`TransformList`]
+pub fn transform_string_parser(input: &str) -> Option<TransformList> {
+    transform_parser(input).map(|(_, value)| value).ok()
+}
+
+/// Wrapper for [`transform_parser`] that returns a [`ParseError`] on failure
+///
+/// Unlike [`transform_string_parser`] the whole input must be consumed.
+pub fn transform_result_parser(input: &str) -> Result<TransformList, ParseError> {
+    parse_all(input, transform_parser)
+}
+
+/// Formats a [`TransformList`] as a string, the counterpart of [`transform_parser`]
+///
+/// Single axis functions are formatted with both axes, like `translate(10px, 0px)`.
+pub fn transform_formatter(transform: &TransformList) -> String {
+    if transform.0.is_empty() {
+        return "none".to_string();
+    }
+    transform
+        .0
+        .iter()
+        .map(|function| match *function {
+            TransformFunction::Translate(x, y) => {
+                format!("translate({}, {})", val_formatter(x), val_formatter(y))
+            }
+            TransformFunction::Scale(x, y) => format!("scale({}, {})", x, y),
+            TransformFunction::Rotate(angle) => format!("rotate({})", angle_formatter(angle)),
+            TransformFunction::Skew(x, y) => {
+                format!("skew({}, {})", angle_formatter(x), angle_formatter(y))
+            }
+            TransformFunction::Matrix(values) => format!(
+                "matrix({})",
+                values.map(|value| value.to_string()).join(", ")
+            ),
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Parser for strings that represent the origin of a transform, like the value of `transform-origin`.
+///
+/// Uses the syntax of [`position_parser`], like `center`, `top left` or `10px 50%`.
+pub fn transform_origin_parser(input: &str) -> IResult<&str, Position> {
+    position_parser(input)
+}
+
+/// Wrapper for [`transform_origin_parser`] that returns an optional [`Position`]
+pub fn transform_origin_string_parser(input: &str) -> Option<Position> {
+    transform_origin_parser(input).map(|(_, value)| value).ok()
+}
+
+/// Wrapper for [`transform_origin_parser`] that returns a [`ParseError`] on failure
+///
+/// Unlike [`transform_origin_string_parser`] the whole input must be consumed.
+pub fn transform_origin_result_parser(input: &str) -> Result<Position, ParseError> {
+    parse_all(input, transform_origin_parser)
+}
+
+/// Wrapper for [`transform_parser`] that implements a serde deserializer
+#[cfg(feature = "serde")]
+pub fn transform_serde_parser<'de, D>(deserializer: D) -> Result<TransformList, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::Error;
+    let s: &str = serde::Deserialize::deserialize(deserializer)?;
+    transform_result_parser(s)
+        .map_err(|error| D::Error::custom(format!("invalid transform string: {}", error)))
+}
+
+/// Wrapper for [`transform_formatter`] that implements a serde serializer
+#[cfg(feature = "serde")]
+pub fn transform_serde_serializer<S>(
+    transform: &TransformList,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.serialize_str(&transform_formatter(transform))
+}
+
+/// Wrapper for [`transform_origin_parser`] that implements a serde deserializer
+#[cfg(feature = "serde")]
+pub fn transform_origin_serde_parser<'de, D>(deserializer: D) -> Result<Position, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::Error;
+    let s: &str = serde::Deserialize::deserialize(deserializer)?;
+    transform_origin_result_parser(s)
+        .map_err(|error| D::Error::custom(format!("invalid transform origin string: {}", error)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::FRAC_PI_2;
+
+    use bevy::math::Quat;
+
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("translate(10px, -5px)", TransformFunction::Translate(Val::Px(10.0), Val::Px(-5.0)) ; "translate")]
+    #[test_case("translate(50%)", TransformFunction::Translate(Val::Percent(50.0), Val::Px(0.0)) ; "translate single")]
+    #[test_case("translateX( 0 )", TransformFunction::Translate(Val::Px(0.0), Val::Px(0.0)) ; "translate x unitless zero")]
+    #[test_case("translateY(2vh)", TransformFunction::Translate(Val::Px(0.0), Val::Vh(2.0)) ; "translate y")]
+    #[test_case("scale(1.2, 0.8)", TransformFunction::Scale(1.2, 0.8) ; "scale")]
+    #[test_case("scale(2)", TransformFunction::Scale(2.0, 2.0) ; "scale single")]
+    #[test_case("scaleX(150%)", TransformFunction::Scale(1.5, 1.0) ; "scale x percentage")]
+    #[test_case("scaleY(0.5)", TransformFunction::Scale(1.0, 0.5) ; "scale y")]
+    #[test_case("rotate(90deg)", TransformFunction::Rotate(FRAC_PI_2) ; "rotate")]
+    #[test_case("rotate(0.5)", TransformFunction::Rotate(0.5) ; "rotate without unit")]
+    #[test_case("skew(0.5rad, 0.25rad)", TransformFunction::Skew(0.5, 0.25) ; "skew")]
+    #[test_case("skewX(0.5rad)", TransformFunction::Skew(0.5, 0.0) ; "skew x")]
+    #[test_case("skewY(0.5rad)", TransformFunction::Skew(0.0, 0.5) ; "skew y")]
+    #[test_case("matrix(1, 0, 0, 1, 10, 20)", TransformFunction::Matrix([1.0, 0.0, 0.0, 1.0, 10.0, 20.0]) ; "matrix")]
+    fn test_transform_function_parser(string: &str, expected: TransformFunction) {
+        assert_eq!(
+            transform_result_parser(string),
+            Ok(TransformList(vec![expected]))
+        );
+    }
+
+    #[test]
+    fn test_transform_parser_list() {
+        let transform = transform_result_parser(
+            "rotate(45deg) scale(1.2, 0.8) translate(10px, -5px) skewX(10deg)",
+        )
+        .unwrap();
+        assert_eq!(transform.0.len(), 4);
+        assert!(transform.has_skew());
+        assert_eq!(
+            transform_string_parser(" none "),
+            Some(TransformList::default())
+        );
+        assert_eq!(
+            transform_formatter(&transform),
+            "rotate(45deg) scale(1.2, 0.8) translate(10px, -5px) skew(10deg, 0deg)"
+        );
+        assert_eq!(transform_formatter(&TransformList::default()), "none");
+    }
+
+    #[test_case("rotate(45px)" ; "invalid angle unit")]
+    #[test_case("scale()" ; "missing argument")]
+    #[test_case("translate(1px, 2px, 3px)" ; "too many arguments")]
+    #[test_case("matrix(1, 0, 0, 1)" ; "incomplete matrix")]
+    #[test_case("rotate3d(1, 0, 0, 45deg)" ; "unsupported function")]
+    fn test_transform_parser_invalid(string: &str) {
+        assert!(transform_result_parser(string).is_err());
+    }
+
+    #[test]
+    fn test_transform_list_to_affine2() {
+        let size = Vec2::new(100.0, 50.0);
+        let transform = transform_result_parser("translate(50%, 10px) rotate(90deg)").unwrap();
+        let matrix = transform.to_affine2(size, Position::default());
+        // rotates clockwise in the y-down ui space, then translates
+        let point = matrix.transform_point2(Vec2::new(1.0, 0.0));
+        assert!(point.abs_diff_eq(Vec2::new(50.0, 11.0), 1e-4), "{}", point);
+        assert!(!transform.has_skew());
+    }
+
+    #[test]
+    fn test_transform_list_origin() {
+        let size = Vec2::new(100.0, 100.0);
+        let transform = transform_result_parser("scale(2)").unwrap();
+        let origin = transform_origin_result_parser("top left").unwrap();
+        let matrix = transform.to_affine2(size, origin);
+        // the top left corner of the node stays in place
+        let corner = matrix.transform_point2(Vec2::new(-50.0, -50.0));
+        assert!(
+            corner.abs_diff_eq(Vec2::new(-50.0, -50.0), 1e-4),
+            "{}",
+            corner
+        );
+        let center = matrix.transform_point2(Vec2::ZERO);
+        assert!(
+            center.abs_diff_eq(Vec2::new(50.0, 50.0), 1e-4),
+            "{}",
+            center
+        );
+    }
+
+    #[test]
+    fn test_transform_list_to_transform() {
+        let transform = transform_result_parser("translate(10px, 20px) rotate(90deg) scale(2, 3)")
+            .unwrap()
+            .to_transform(Vec2::ZERO, Position::default());
+        assert!(transform
+            .translation
+            .abs_diff_eq(bevy::math::Vec3::new(10.0, 20.0, 0.0), 1e-4));
+        assert!(transform
+            .rotation
+            .abs_diff_eq(Quat::from_rotation_z(FRAC_PI_2), 1e-4));
+        assert!(transform
+            .scale
+            .abs_diff_eq(bevy::math::Vec3::new(2.0, 3.0, 1.0), 1e-4));
+    }
+
+    #[test_case("scale(2, 1) rotate(45deg)", true ; "non uniform scale before rotation")]
+    #[test_case("rotate(45deg) scale(2, 1)", false ; "non uniform scale after rotation")]
+    #[test_case("skewY(10deg)", true ; "skew")]
+    #[test_case("none", false ; "empty")]
+    fn test_transform_list_has_skew(string: &str, expected: bool) {
+        assert_eq!(
+            transform_result_parser(string).unwrap().has_skew(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_transform_origin_parser() {
+        assert_eq!(
+            transform_origin_result_parser("right 10px"),
+            Ok(Position {
+                x: Val::Percent(100.0),
+                y: Val::Px(10.0),
+            })
+        );
+        assert!(transform_origin_result_parser("left right").is_err());
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests_serde {
+    use super::{Position, TransformFunction, TransformList};
+    use bevy::ui::Val;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Deserialize, Serialize)]
+    pub struct Foo {
+        #[serde(
+            deserialize_with = "super::transform_serde_parser",
+            serialize_with = "super::transform_serde_serializer"
+        )]
+        pub transform: TransformList,
+    }
+
+    #[derive(Deserialize)]
+    pub struct Bar {
+        #[serde(deserialize_with = "super::transform_origin_serde_parser")]
+        pub origin: Position,
+    }
+
+    #[test]
+    fn test_transform_serde_parser() {
+        let foo: Foo = serde_json::from_str(r#"{"transform": "rotate(90deg) scale(2)"}"#).unwrap();
+        assert_eq!(
+            foo.transform,
+            TransformList(vec![
+                TransformFunction::Rotate(90f32.to_radians()),
+                TransformFunction::Scale(2.0, 2.0),
+            ])
+        );
+        assert!(serde_json::from_str::<Foo>(r#"{"transform": "rotate(90px)"}"#).is_err());
+    }
+
+    #[test]
+    fn test_transform_serde_serializer() {
+        let foo = Foo {
+            transform: TransformList(vec![TransformFunction::Scale(2.0, 1.0)]),
+        };
+        let json = serde_json::to_string(&foo).unwrap();
+        assert_eq!(json, r#"{"transform":"scale(2, 1)"}"#);
+    }
+
+    #[test]
+    fn test_transform_origin_serde_parser() {
+        let bar: Bar = serde_json::from_str(r#"{"origin": "top left"}"#).unwrap();
+        assert_eq!(
+            bar.origin,
+            Position {
+                x: Val::Percent(0.0),
+                y: Val::Percent(0.0),
+            }
+        );
+    }
+}
@@ -0,0 +1,304 @@
+use std::time::Duration;
+
+use nom::{
+    branch::alt,
+    character::complete::multispace0 as multispace,
+    combinator::{map, peek},
+    multi::separated_list1,
+    sequence::{delimited, preceded, tuple},
+};
+
+use super::common::{comma_parser, identifier_parser, token};
+use super::duration::{delay_formatter, delay_parser};
+use super::error::{parse_all, IResult, InputError, ParseError};
+use super::{
+    duration_formatter, duration_parser, easing_formatter, easing_parser, easing_result_parser,
+    Easing,
+};
+
+/// A transition of a property, like an entry of the css `transition` shorthand
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transition {
+    /// The name of the transitioned property, like `background-color` or `all`
+    pub property: String,
+    pub duration: Duration,
+    pub easing: Easing,
+    /// The delay in seconds, a negative delay starts the transition partway through
+    pub delay: f32,
+}
+
+impl Default for Transition {
+    /// Transitions `all` properties immediately with `ease`, like in css
+    fn default() -> Self {
+        Self {
+            property: "all".to_string(),
+            duration: Duration::ZERO,
+            easing: Easing::default(),
+            delay: 0.0,
+        }
+    }
+}
+
+/// A part of a transition
+enum TransitionPart {
+    Time(Duration),
+    /// A negative time, which is only valid as delay
+    Delay(f32),
+    Easing(Easing),
+    Property(String),
+}
+
+/// Parses an easing keyword or the name of a property
+///
+/// The keywords are matched against whole identifiers, so that properties like
+/// `linear-fade` are not mistaken for easing functions.
+fn transition_keyword_parser(input: &str) -> IResult<&str, TransitionPart> {
+    map(
+        delimited(multispace, identifier_parser, multispace),
+        |name: &str| match easing_result_parser(name) {
+            Ok(easing) => TransitionPart::Easing(easing),
+            Err(_) => TransitionPart::Property(name.to_string()),
+        },
+    )(input)
+}
+
+fn transition_part_parser(input: &str) -> IResult<&str, TransitionPart> {
+    alt((
+        map(duration_parser, TransitionPart::Time),
+        map(delay_parser, TransitionPart::Delay),
+        // easing functions with arguments, like `steps(4)`
+        map(
+            preceded(
+                peek(tuple((multispace, identifier_parser, token("(")))),
+                easing_parser,
+            ),
+            TransitionPart::Easing,
+        ),
+        transition_keyword_parser,
+    ))(input)
+}
+
+/// Parses a single transition, the parts can be given in any order
+fn transition_single_parser(input: &str) -> IResult<&str, Transition> {
+    let (mut rest, _) = multispace(input)?;
+    let (mut property, mut easing) = (None, None);
+    let (mut duration, mut delay) = (None, None);
+    let mut parts = 0;
+    loop {
+        let (next, part) = match transition_part_parser(rest) {
+            Err(nom::Err::Error(_)) if parts > 0 => break,
+            result => result?,
+        };
+        let taken = match part {
+            TransitionPart::Time(value) if duration.is_none() => {
+                duration = Some(value);
+                false
+            }
+            TransitionPart::Time(value) => delay.replace(value.as_secs_f32()).is_some(),
+            TransitionPart::Delay(_) if duration.is_none() => {
+                return Err(nom::Err::Failure(InputError::expected(
+                    rest,
+                    "<non-negative duration>",
+                )));
+            }
+            TransitionPart::Delay(value) => delay.replace(value).is_some(),
+            TransitionPart::Easing(value) => easing.replace(value).is_some(),
+            TransitionPart::Property(value) => property.replace(value).is_some(),
+        };
+        if taken {
+            break;
+        }
+        rest = next;
+        parts += 1;
+    }
+    if property.as_deref() == Some("none") {
+        return Err(nom::Err::Error(InputError::expected(input, "<property>")));
+    }
+    let default = Transition::default();
+    Ok((
+        rest,
+        Transition {
+            property: property.unwrap_or(default.property),
+            duration: duration.unwrap_or(default.duration),
+            easing: easing.unwrap_or(default.easing),
+            delay: delay.unwrap_or(default.delay),
+        },
+    ))
+}
+
+/// Parser for strings that represent a list of [`Transition`]s, like the value of `transition`.
+///
+/// The syntax follows CSS, transitions are separated by commas:
+///
+/// * `background-color 200ms` -> property and duration
+/// * `background-color 200ms ease-out 50ms` -> with easing function and delay
+/// * `300ms steps(4)`, `ease-in 1s width` -> parts in any order, the property defaults to `all`
+/// * `none` -> no transitions
+///
+/// The first time is the duration and the second the delay, only the delay may be
+/// negative. The easing function supports the formats of [`easing_parser`].
+pub fn transition_parser(input: &str) -> IResult<&str, Vec<Transition>> {
+    alt((
+        map(delimited(multispace, token("none"), multispace), |_| {
+            Vec::new()
+        }),
+        separated_list1(comma_parser, transition_single_parser),
+    ))(input)
+}
+
+/// Wrapper for [`transition_parser`] that returns an optional list of [`Transition`]s
+pub fn transition_string_parser(input: &str) -> Option<Vec<Transition>> {
+    transition_parser(input).map(|(_, value)| value).ok()
+}
+
+/// Wrapper for [`transition_parser`] that returns a [`ParseError`] on failure
+///
+/// Unlike [`transition_string_parser`] the whole input must be consumed.
+pub fn transition_result_parser(input: &str) -> Result<Vec<Transition>, ParseError> {
+    parse_all(input, transition_parser)
+}
+
+/// Formats a list of [`Transition`]s as a string, the counterpart of [`transition_parser`]
+///
+/// A zero delay is omitted, like `background-color 200ms ease-out`.
+pub fn transition_formatter(transitions: &[Transition]) -> String {
+    if transitions.is_empty() {
+        return "none".to_string();
+    }
+    transitions
+        .iter()
+        .map(|transition| {
+            let mut parts = vec![
+                transition.property.clone(),
+                duration_formatter(transition.duration),
+                easing_formatter(&transition.easing),
+            ];
+            if transition.delay != 0.0 {
+                parts.push(delay_formatter(transition.delay));
+            }
+            parts.join(" ")
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Wrapper for [`transition_parser`] that implements a serde deserializer
+#[cfg(feature = "serde")]
+pub fn transition_serde_parser<'de, D>(deserializer: D) -> Result<Vec<Transition>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::Error;
+    let s: &str = serde::Deserialize::deserialize(deserializer)?;
+    transition_result_parser(s)
+        .map_err(|error| D::Error::custom(format!("invalid transition string: {}", error)))
+}
+
+/// Wrapper for [`transition_formatter`] that implements a serde serializer
+#[cfg(feature = "serde")]
+pub fn transition_serde_serializer<S>(
+    transitions: &[Transition],
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.serialize_str(&transition_formatter(transitions))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    fn transition(property: &str, duration: u64, easing: Easing, delay: i64) -> Transition {
+        Transition {
+            property: property.to_string(),
+            duration: Duration::from_millis(duration),
+            easing,
+            delay: delay as f32 / 1000.0,
+        }
+    }
+
+    #[test_case("background-color 200ms ease-out 50ms", transition("background-color", 200, Easing::EASE_OUT, 50) ; "full")]
+    #[test_case("width 1s", transition("width", 1000, Easing::EASE, 0) ; "property and duration")]
+    #[test_case("300ms steps(4)", transition("all", 300, Easing::Steps(4, Default::default()), 0) ; "without property")]
+    #[test_case("ease-in 1s opacity 2s", transition("opacity", 1000, Easing::EASE_IN, 2000) ; "any order")]
+    #[test_case("linear height", transition("height", 0, Easing::Linear, 0) ; "without duration")]
+    #[test_case("width 1s -500ms", transition("width", 1000, Easing::EASE, -500) ; "negative delay")]
+    #[test_case("linear-fade 1s", transition("linear-fade", 1000, Easing::EASE, 0) ; "property starting with linear")]
+    #[test_case("easement 1s ease-in", transition("easement", 1000, Easing::EASE_IN, 0) ; "property starting with ease")]
+    #[test_case("ease-in-out-glow steps(2) 1s", transition("ease-in-out-glow", 1000, Easing::Steps(2, Default::default()), 0) ; "property starting with easing keyword")]
+    fn test_transition_parser(string: &str, expected: Transition) {
+        assert_eq!(transition_result_parser(string), Ok(vec![expected]));
+    }
+
+    #[test]
+    fn test_transition_parser_list() {
+        let transitions =
+            transition_result_parser("background-color 200ms ease-out 50ms, width 1s linear")
+                .unwrap();
+        assert_eq!(transitions.len(), 2);
+        assert_eq!(transitions[1].easing, Easing::Linear);
+        assert_eq!(transition_string_parser(" none "), Some(vec![]));
+        assert_eq!(
+            transition_formatter(&transitions),
+            "background-color 200ms ease-out 50ms, width 1000ms linear"
+        );
+        let transitions = transition_result_parser("width 1s -250ms").unwrap();
+        assert_eq!(
+            transition_formatter(&transitions),
+            "width 1000ms ease -250ms"
+        );
+        assert_eq!(transition_formatter(&[]), "none");
+    }
+
+    #[test_case("width 1s 2s 3s" ; "too many times")]
+    #[test_case("width height 1s" ; "two properties")]
+    #[test_case("width 1s ease linear" ; "two easing functions")]
+    #[test_case("none 1s" ; "none with duration")]
+    #[test_case("width 1s," ; "trailing comma")]
+    #[test_case("width -1s" ; "negative duration")]
+    #[test_case("width -1s 2s" ; "negative duration before delay")]
+    fn test_transition_parser_invalid(string: &str) {
+        assert!(transition_result_parser(string).is_err());
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests_serde {
+    use super::Transition;
+    use serde::{Deserialize, Serialize};
+    use std::time::Duration;
+
+    #[derive(Deserialize, Serialize)]
+    pub struct Foo {
+        #[serde(
+            deserialize_with = "super::transition_serde_parser",
+            serialize_with = "super::transition_serde_serializer"
+        )]
+        pub transition: Vec<Transition>,
+    }
+
+    #[test]
+    fn test_transition_serde_parser() {
+        let foo: Foo =
+            serde_json::from_str(r#"{"transition": "opacity 0.5s ease-in, width 1s"}"#).unwrap();
+        assert_eq!(foo.transition.len(), 2);
+        assert_eq!(foo.transition[0].duration, Duration::from_millis(500));
+        assert!(serde_json::from_str::<Foo>(r#"{"transition": "opacity 1s 2s 3s"}"#).is_err());
+    }
+
+    #[test]
+    fn test_transition_serde_serializer() {
+        let foo = Foo {
+            transition: vec![Transition {
+                property: "opacity".to_string(),
+                duration: Duration::from_millis(500),
+                ..Default::default()
+            }],
+        };
+        let json = serde_json::to_string(&foo).unwrap();
+        assert_eq!(json, r#"{"transition":"opacity 500ms ease"}"#);
+    }
+}
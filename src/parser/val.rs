@@ -1,14 +1,14 @@
 use bevy::ui::Val;
 use nom::{
     branch::alt,
-    bytes::complete::tag,
     character::complete::multispace0 as multispace,
-    combinator::map,
-    number::streaming::float,
+    combinator::{map, verify},
     sequence::{delimited, tuple},
-    IResult,
 };
 
+use super::common::{number_parser, token};
+use super::error::{parse_all, IResult, InputError, ParseError};
+
 /// Parser for strings that represent a [`bevy::ui::Val`].
 ///
 /// The syntax is inspired by CSS:
@@ -24,24 +24,91 @@ use nom::{
 pub fn val_parser(input: &str) -> IResult<&str, Val> {
     delimited(
         multispace,
-        alt((
-            map(tag("auto"), |_| Val::Auto),
-            map(tuple((float, tag("px"))), |(val, _)| Val::Px(val)),
-            map(tuple((float, tag("%"))), |(val, _)| Val::Percent(val)),
-            map(tuple((float, tag("vw"))), |(val, _)| Val::Vw(val)),
-            map(tuple((float, tag("vh"))), |(val, _)| Val::Vh(val)),
-            map(tuple((float, tag("vmin"))), |(val, _)| Val::VMin(val)),
-            map(tuple((float, tag("vmax"))), |(val, _)| Val::VMax(val)),
-        )),
+        alt((map(token("auto"), |_| Val::Auto), val_length_parser)),
         multispace,
     )(input)
 }
 
+/// Parses a number with a length unit, like `12px` or `50%`, without surrounding whitespace
+pub(crate) fn val_length_parser(input: &str) -> IResult<&str, Val> {
+    alt((
+        map(tuple((number_parser, token("px"))), |(val, _)| Val::Px(val)),
+        map(tuple((number_parser, token("%"))), |(val, _)| {
+            Val::Percent(val)
+        }),
+        map(tuple((number_parser, token("vw"))), |(val, _)| Val::Vw(val)),
+        map(tuple((number_parser, token("vh"))), |(val, _)| Val::Vh(val)),
+        map(tuple((number_parser, token("vmin"))), |(val, _)| {
+            Val::VMin(val)
+        }),
+        map(tuple((number_parser, token("vmax"))), |(val, _)| {
+            Val::VMax(val)
+        }),
+    ))(input)
+}
+
+/// Parses a length like [`val_length_parser`], also accepting `0` without unit as `0px`
+pub(crate) fn val_length_or_zero_parser(input: &str) -> IResult<&str, Val> {
+    alt((
+        val_length_parser,
+        map(verify(number_parser, |value| *value == 0.0), |_| {
+            Val::Px(0.0)
+        }),
+    ))(input)
+}
+
+/// Returns whether the number of the [`bevy::ui::Val`] is negative, `auto` is not negative
+pub(crate) fn val_is_negative(val: Val) -> bool {
+    match val {
+        Val::Px(value)
+        | Val::Percent(value)
+        | Val::Vw(value)
+        | Val::Vh(value)
+        | Val::VMin(value)
+        | Val::VMax(value) => value < 0.0,
+        Val::Auto => false,
+    }
+}
+
+/// Parses a length like [`val_length_or_zero_parser`], negative lengths fail without backtracking
+pub(crate) fn non_negative_length_parser(input: &str) -> IResult<&str, Val> {
+    let (rest, val) = val_length_or_zero_parser(input)?;
+    if val_is_negative(val) {
+        return Err(nom::Err::Failure(InputError::expected(
+            input,
+            "<non-negative length>",
+        )));
+    }
+    Ok((rest, val))
+}
+
 /// Wrapper for [`val_parser`] that returns an optional [`bevy::ui::Val`]
 pub fn val_string_parser(input: &str) -> Option<Val> {
     val_parser(input).map(|(_, value)| value).ok()
 }
 
+/// Wrapper for [`val_parser`] that returns a [`ParseError`] on failure
+///
+/// Unlike [`val_string_parser`] the whole input must be consumed.
+pub fn val_result_parser(input: &str) -> Result<Val, ParseError> {
+    parse_all(input, val_parser)
+}
+
+/// Formats a [`bevy::ui::Val`] as a string, the counterpart of [`val_parser`]
+///
+/// For example `Val::Px(12.0)` -> `12px` or `Val::Auto` -> `auto`.
+pub fn val_formatter(val: Val) -> String {
+    match val {
+        Val::Auto => "auto".to_string(),
+        Val::Px(value) => format!("{}px", value),
+        Val::Percent(value) => format!("{}%", value),
+        Val::Vw(value) => format!("{}vw", value),
+        Val::Vh(value) => format!("{}vh", value),
+        Val::VMin(value) => format!("{}vmin", value),
+        Val::VMax(value) => format!("{}vmax", value),
+    }
+}
+
 /// Wrapper for [`val_parser`] that implements a serde deserializer
 #[cfg(feature = "serde")]
 pub fn val_serde_parser<'de, D>(deserializer: D) -> Result<Val, D::Error>
@@ -50,7 +117,16 @@ where
 {
     use serde::de::Error;
     let s: &str = serde::Deserialize::deserialize(deserializer)?;
-    val_string_parser(s).ok_or(D::Error::custom("invalid val string"))
+    val_result_parser(s).map_err(|error| D::Error::custom(format!("invalid val string: {}", error)))
+}
+
+/// Wrapper for [`val_formatter`] that implements a serde serializer
+#[cfg(feature = "serde")]
+pub fn val_serde_serializer<S>(val: &Val, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    serializer.serialize_str(&val_formatter(*val))
 }
 
 #[cfg(test)]
@@ -110,16 +186,43 @@ mod tests {
         assert_eq!(val_parser("1.32vmax"), Ok(("", Val::VMax(1.32))));
         assert!(val_parser("1.32").is_err());
     }
+
+    #[test_case(Val::Auto, "auto" ; "auto")]
+    #[test_case(Val::Px(12.0), "12px" ; "px")]
+    #[test_case(Val::Percent(33.3), "33.3%" ; "percent")]
+    #[test_case(Val::Vw(-0.5), "-0.5vw" ; "vw")]
+    #[test_case(Val::Vh(100.0), "100vh" ; "vh")]
+    #[test_case(Val::VMin(1.0 / 3.0), "0.33333334vmin" ; "vmin")]
+    #[test_case(Val::VMax(1e-7), "0.0000001vmax" ; "vmax")]
+    fn test_val_formatter(val: Val, expected: &str) {
+        assert_eq!(val_formatter(val), expected);
+        assert_eq!(val_result_parser(expected), Ok(val));
+    }
+
+    #[test]
+    fn test_val_result_parser() {
+        assert_eq!(val_result_parser(" 12px "), Ok(Val::Px(12.0)));
+        assert_eq!(val_result_parser("auto"), Ok(Val::Auto));
+        let error = val_result_parser("12").unwrap_err();
+        assert_eq!(error.offset, 2);
+        assert_eq!(error.fragment, "");
+        let error = val_result_parser("px").unwrap_err();
+        assert_eq!(error.offset, 0);
+        assert_eq!(error.expected, vec!["auto", "<number>"]);
+    }
 }
 
 #[cfg(all(test, feature = "serde"))]
 mod tests_serde {
     use bevy::ui::Val;
-    use serde::Deserialize;
+    use serde::{Deserialize, Serialize};
 
-    #[derive(Deserialize)]
+    #[derive(Deserialize, Serialize)]
     pub struct Foo {
-        #[serde(deserialize_with = "super::val_serde_parser")]
+        #[serde(
+            deserialize_with = "super::val_serde_parser",
+            serialize_with = "super::val_serde_serializer"
+        )]
         pub value: Val,
     }
 
@@ -128,4 +231,12 @@ mod tests_serde {
         let foo: Foo = serde_json::from_str(r#"{"value": "42px"}"#).unwrap();
         assert_eq!(foo.value, Val::Px(42.0));
     }
+
+    #[test]
+    fn test_val_serde_serializer() {
+        let foo = Foo {
+            value: Val::Percent(12.5),
+        };
+        assert_eq!(serde_json::to_string(&foo).unwrap(), r#"{"value":"12.5%"}"#);
+    }
 }
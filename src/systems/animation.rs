@@ -0,0 +1,589 @@
+use std::time::Duration;
+
+use bevy::{
+    app::{App, Plugin, PostUpdate},
+    ecs::{
+        change_detection::{DetectChangesMut, Mut},
+        component::Component,
+        entity::Entity,
+        query::With,
+        schedule::IntoSystemConfigs,
+        system::{ParamSet, Query, Res, Resource},
+    },
+    hierarchy::Parent,
+    math::{EulerRot, Quat, Vec2, Vec3},
+    render::color::Color,
+    text::Text,
+    time::Time,
+    transform::components::Transform,
+    ui::{BackgroundColor, BorderColor, Style, Val},
+    utils::HashMap,
+    window::{PrimaryWindow, Window},
+};
+
+use super::calc::{content_size, LayoutComponents};
+use super::{configure_style_systems, StyleSystem};
+use crate::{AnimatedProperty, Animation, CalcContext, KeyframeValue, Keyframes};
+
+/// Resource with the [`Keyframes`] that can be played by an [`AnimationStyle`], by name
+#[derive(Resource, Debug, Clone, Default)]
+pub struct KeyframesRegistry {
+    keyframes: HashMap<String, Keyframes>,
+}
+
+impl KeyframesRegistry {
+    /// Adds the keyframes, replacing keyframes with the same name
+    pub fn insert(&mut self, keyframes: Keyframes) {
+        self.keyframes.insert(keyframes.name.clone(), keyframes);
+    }
+
+    /// Returns the keyframes with the name, `None` if they are not registered
+    pub fn get(&self, name: &str) -> Option<&Keyframes> {
+        self.keyframes.get(name)
+    }
+}
+
+/// Component with the [`Animation`]s of a ui node, played by [`play_keyframe_animations`]
+///
+/// Later animations override the properties of earlier ones, animations of
+/// keyframes that are not registered in the [`KeyframesRegistry`] are ignored.
+#[derive(Component, Debug, Clone, Default, PartialEq)]
+pub struct AnimationStyle {
+    pub animations: Vec<Animation>,
+    /// The time since each animation started, paused animations don't advance
+    elapsed: Vec<Duration>,
+    /// The values of the animated properties from before the animations applied
+    base: Vec<(AnimatedProperty, NodeValue)>,
+}
+
+impl AnimationStyle {
+    /// Creates the component with the animations, which start playing in the next update
+    pub fn new(animations: Vec<Animation>) -> Self {
+        Self {
+            animations,
+            elapsed: Vec::new(),
+            base: Vec::new(),
+        }
+    }
+
+    /// Plays all animations from the start
+    pub fn restart(&mut self) {
+        self.elapsed.clear();
+    }
+
+    /// Returns true if all animations ended, infinite animations never end
+    pub fn is_finished(&self) -> bool {
+        self.animations
+            .iter()
+            .enumerate()
+            .all(|(index, animation)| {
+                animation.is_finished(self.elapsed.get(index).copied().unwrap_or_default())
+            })
+    }
+}
+
+/// A value of an [`AnimatedProperty`] as it is stored in the components of a node
+#[derive(Debug, Clone, PartialEq)]
+enum NodeValue {
+    Val(Val),
+    Color(Color),
+    /// The colors of all sections of the [`Text`]
+    TextColors(Vec<Color>),
+    Rotation(Quat),
+    Scale(Vec3),
+}
+
+impl NodeValue {
+    /// Converts the sampled value of a keyframe, `None` if it doesn't fit the property
+    /// or a [`KeyframeValue::Calc`] doesn't resolve to a finite length
+    ///
+    /// `parent_size` is the size percentages refer to, its width for horizontal
+    /// properties and its height for vertical ones, as for a [`crate::CalcStyle`].
+    fn from_keyframe(
+        property: AnimatedProperty,
+        value: KeyframeValue,
+        parent_size: Vec2,
+        viewport: Vec2,
+    ) -> Option<Self> {
+        match (property, value) {
+            (
+                AnimatedProperty::BackgroundColor
+                | AnimatedProperty::BorderColor
+                | AnimatedProperty::TextColor,
+                KeyframeValue::Color(color),
+            ) => Some(NodeValue::Color(color)),
+            (AnimatedProperty::Rotate, KeyframeValue::Angle(angle)) => {
+                Some(NodeValue::Rotation(Quat::from_rotation_z(angle)))
+            }
+            (AnimatedProperty::Scale, KeyframeValue::Number(scale)) => {
+                Some(NodeValue::Scale(Vec3::new(scale, scale, 1.0)))
+            }
+            (_, KeyframeValue::Val(val)) => Some(NodeValue::Val(val)),
+            (_, KeyframeValue::Calc(expr)) => {
+                let percent_base = match property {
+                    AnimatedProperty::Height
+                    | AnimatedProperty::MinHeight
+                    | AnimatedProperty::MaxHeight
+                    | AnimatedProperty::Top
+                    | AnimatedProperty::Bottom => parent_size.y,
+                    _ => parent_size.x,
+                };
+                let context = CalcContext {
+                    percent_base,
+                    viewport,
+                };
+                expr.resolve_val(&context).map(NodeValue::Val)
+            }
+            _ => None,
+        }
+    }
+
+    /// Converts the value into the value of a keyframe, the counterpart of
+    /// [`NodeValue::from_keyframe`], `None` for a [`Text`] without sections
+    fn to_keyframe(&self) -> Option<KeyframeValue> {
+        match self {
+            NodeValue::Val(val) => Some(KeyframeValue::Val(*val)),
+            NodeValue::Color(color) => Some(KeyframeValue::Color(*color)),
+            NodeValue::TextColors(colors) => colors.first().copied().map(KeyframeValue::Color),
+            NodeValue::Rotation(rotation) => {
+                Some(KeyframeValue::Angle(rotation.to_euler(EulerRot::ZYX).0))
+            }
+            NodeValue::Scale(scale) => Some(KeyframeValue::Number(scale.x)),
+        }
+    }
+}
+
+/// Returns the field of the [`Style`] of a length property
+fn style_field(style: &mut Style, property: AnimatedProperty) -> Option<&mut Val> {
+    match property {
+        AnimatedProperty::Width => Some(&mut style.width),
+        AnimatedProperty::Height => Some(&mut style.height),
+        AnimatedProperty::MinWidth => Some(&mut style.min_width),
+        AnimatedProperty::MinHeight => Some(&mut style.min_height),
+        AnimatedProperty::MaxWidth => Some(&mut style.max_width),
+        AnimatedProperty::MaxHeight => Some(&mut style.max_height),
+        AnimatedProperty::Left => Some(&mut style.left),
+        AnimatedProperty::Right => Some(&mut style.right),
+        AnimatedProperty::Top => Some(&mut style.top),
+        AnimatedProperty::Bottom => Some(&mut style.bottom),
+        _ => None,
+    }
+}
+
+/// Writes the value into the field of the component, which is only marked as changed
+/// if the value differs
+fn set_field<C, T: PartialEq>(
+    component: &mut Mut<C>,
+    field: impl FnOnce(&mut C) -> &mut T,
+    value: T,
+) {
+    let target = field(component.bypass_change_detection());
+    if *target != value {
+        *target = value;
+        component.set_changed();
+    }
+}
+
+/// The components of a ui node that are written by [`play_keyframe_animations`]
+type AnimatedComponents = (
+    Entity,
+    &'static mut AnimationStyle,
+    &'static mut Style,
+    Option<&'static mut BackgroundColor>,
+    Option<&'static mut BorderColor>,
+    Option<&'static mut Text>,
+    Option<&'static mut Transform>,
+);
+
+/// The animated components of a ui node, without the [`AnimationStyle`]
+struct AnimatedNode<'a> {
+    style: Mut<'a, Style>,
+    background_color: Option<Mut<'a, BackgroundColor>>,
+    border_color: Option<Mut<'a, BorderColor>>,
+    text: Option<Mut<'a, Text>>,
+    transform: Option<Mut<'a, Transform>>,
+}
+
+impl AnimatedNode<'_> {
+    /// Returns the current value of the property, `None` if the node lacks the component
+    fn read(&mut self, property: AnimatedProperty) -> Option<NodeValue> {
+        match property {
+            AnimatedProperty::BackgroundColor => self
+                .background_color
+                .as_ref()
+                .map(|color| NodeValue::Color(color.0)),
+            AnimatedProperty::BorderColor => self
+                .border_color
+                .as_ref()
+                .map(|color| NodeValue::Color(color.0)),
+            AnimatedProperty::TextColor => self.text.as_ref().map(|text| {
+                NodeValue::TextColors(
+                    text.sections
+                        .iter()
+                        .map(|section| section.style.color)
+                        .collect(),
+                )
+            }),
+            AnimatedProperty::Rotate => self
+                .transform
+                .as_ref()
+                .map(|transform| NodeValue::Rotation(transform.rotation)),
+            AnimatedProperty::Scale => self
+                .transform
+                .as_ref()
+                .map(|transform| NodeValue::Scale(transform.scale)),
+            property => style_field(self.style.bypass_change_detection(), property)
+                .map(|val| NodeValue::Val(*val)),
+        }
+    }
+
+    /// Writes the value of the property into the components of the node
+    fn write(&mut self, property: AnimatedProperty, value: NodeValue) {
+        match (property, value) {
+            (AnimatedProperty::BackgroundColor, NodeValue::Color(color)) => {
+                if let Some(background_color) = self.background_color.as_mut() {
+                    set_field(
+                        background_color,
+                        |background_color| &mut background_color.0,
+                        color,
+                    );
+                }
+            }
+            (AnimatedProperty::BorderColor, NodeValue::Color(color)) => {
+                if let Some(border_color) = self.border_color.as_mut() {
+                    set_field(border_color, |border_color| &mut border_color.0, color);
+                }
+            }
+            (AnimatedProperty::TextColor, NodeValue::Color(color)) => {
+                if let Some(text) = self.text.as_mut() {
+                    for index in 0..text.sections.len() {
+                        set_field(text, |text| &mut text.sections[index].style.color, color);
+                    }
+                }
+            }
+            (AnimatedProperty::TextColor, NodeValue::TextColors(colors)) => {
+                if let Some(text) = self.text.as_mut() {
+                    let count = text.sections.len().min(colors.len());
+                    for (index, color) in colors.into_iter().enumerate().take(count) {
+                        set_field(text, |text| &mut text.sections[index].style.color, color);
+                    }
+                }
+            }
+            (AnimatedProperty::Rotate, NodeValue::Rotation(rotation)) => {
+                if let Some(transform) = self.transform.as_mut() {
+                    set_field(transform, |transform| &mut transform.rotation, rotation);
+                }
+            }
+            (AnimatedProperty::Scale, NodeValue::Scale(scale)) => {
+                if let Some(transform) = self.transform.as_mut() {
+                    set_field(transform, |transform| &mut transform.scale, scale);
+                }
+            }
+            (property, NodeValue::Val(val)) => {
+                let style = self.style.bypass_change_detection();
+                if let Some(field) = style_field(style, property).filter(|field| **field != val) {
+                    *field = val;
+                    self.style.set_changed();
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// System that advances the [`AnimationStyle`]s of ui nodes and applies their keyframes
+///
+/// Colors are written into the [`BackgroundColor`], [`BorderColor`] and the sections
+/// of the [`Text`], lengths into the [`Style`] and `rotate` and `scale` into the
+/// [`Transform`] of the node, if it has these components.
+///
+/// The value of a property from before the animations is the implicit `from` and `to`
+/// keyframe of keyframes without them. When no animation applies to a property anymore,
+/// like after an animation without fill mode ended, the property is restored to it.
+///
+/// Lengths of different units are interpolated in a [`KeyframeValue::Calc`], resolved
+/// against the content box of the parent node from the previous layout like a
+/// [`crate::CalcStyle`].
+pub fn play_keyframe_animations(
+    time: Res<Time>,
+    registry: Res<KeyframesRegistry>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    animated_nodes: Query<(Entity, Option<&Parent>), With<AnimationStyle>>,
+    mut nodes: ParamSet<(Query<LayoutComponents>, Query<AnimatedComponents>)>,
+) {
+    let viewport = windows
+        .get_single()
+        .map(|window| Vec2::new(window.resolution.width(), window.resolution.height()))
+        .unwrap_or(Vec2::ZERO);
+    // the sizes are read before the styles are written, the layout reads the styles too
+    let parent_sizes: HashMap<Entity, Vec2> = animated_nodes
+        .iter()
+        .filter_map(|(entity, parent)| {
+            let parent_size = content_size(parent?.get(), &nodes.p0(), viewport)?;
+            Some((entity, parent_size))
+        })
+        .collect();
+    for (entity, mut animation_style, style, background_color, border_color, text, transform) in
+        nodes.p1().iter_mut()
+    {
+        let parent_size = parent_sizes.get(&entity).copied().unwrap_or(viewport);
+        let mut node = AnimatedNode {
+            style,
+            background_color,
+            border_color,
+            text,
+            transform,
+        };
+        let animation_style = animation_style.as_mut();
+        let count = animation_style.animations.len();
+        animation_style.elapsed.resize(count, Duration::ZERO);
+        let mut values: Vec<(AnimatedProperty, NodeValue)> = Vec::new();
+        let base = &animation_style.base;
+        let mut base_value = |property| {
+            // properties that were not animated in the previous update still have their base value
+            base.iter()
+                .find(|(other, _)| *other == property)
+                .map(|(_, value)| value.clone())
+                .or_else(|| node.read(property))
+                .and_then(|value| value.to_keyframe())
+        };
+        for (animation, elapsed) in animation_style
+            .animations
+            .iter()
+            .zip(animation_style.elapsed.iter_mut())
+        {
+            if !animation.paused {
+                *elapsed += time.delta();
+            }
+            let Some(keyframes) = registry.get(&animation.name) else {
+                continue;
+            };
+            let Some(progress) = animation.progress(*elapsed) else {
+                continue;
+            };
+            for (property, value) in keyframes.sample(progress, &animation.easing, &mut base_value)
+            {
+                let Some(value) = NodeValue::from_keyframe(property, value, parent_size, viewport)
+                else {
+                    continue;
+                };
+                // later animations override the properties of earlier ones
+                values.retain(|(other, _)| *other != property);
+                values.push((property, value));
+            }
+        }
+        for (property, _) in &values {
+            let known = animation_style
+                .base
+                .iter()
+                .any(|(other, _)| other == property);
+            if let Some(base) = node.read(*property).filter(|_| !known) {
+                animation_style.base.push((*property, base));
+            }
+        }
+        animation_style.base.retain(|(property, base)| {
+            let animated = values.iter().any(|(other, _)| other == property);
+            if !animated {
+                node.write(*property, base.clone());
+            }
+            animated
+        });
+        for (property, value) in values {
+            node.write(property, value);
+        }
+    }
+}
+
+/// Plugin that adds the [`KeyframesRegistry`] and the [`play_keyframe_animations`] system
+/// in [`StyleSystem::Animations`], running before the ui layout
+pub struct KeyframesPlugin;
+
+impl Plugin for KeyframesPlugin {
+    fn build(&self, app: &mut App) {
+        configure_style_systems(app);
+        app.init_resource::<KeyframesRegistry>().add_systems(
+            PostUpdate,
+            play_keyframe_animations.in_set(StyleSystem::Animations),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{animation_result_parser, keyframes_result_parser};
+    use bevy::ecs::{change_detection::DetectChanges, schedule::Schedule, world::World};
+
+    #[test]
+    fn test_play_keyframe_animations() {
+        let mut world = World::new();
+        let mut registry = KeyframesRegistry::default();
+        registry.insert(
+            keyframes_result_parser(
+                "@keyframes pulse { from { background-color: #000; width: 100px } to { background-color: #fff; width: 200px; rotate: 90deg } }",
+            )
+            .unwrap(),
+        );
+        world.insert_resource(registry);
+        world.insert_resource(Time::<()>::default());
+        let animations = animation_result_parser("pulse 1s linear forwards").unwrap();
+        let node = world
+            .spawn((
+                AnimationStyle::new(animations),
+                Style::default(),
+                BackgroundColor::default(),
+                Transform::default(),
+            ))
+            .id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(play_keyframe_animations);
+        let mut advance = |world: &mut World, millis| {
+            world
+                .resource_mut::<Time>()
+                .advance_by(Duration::from_millis(millis));
+            schedule.run(world);
+        };
+
+        advance(&mut world, 250);
+        assert_eq!(world.get::<Style>(node).unwrap().width, Val::Px(125.0));
+        assert_eq!(
+            world.get::<BackgroundColor>(node).unwrap().0,
+            Color::rgb(0.25, 0.25, 0.25)
+        );
+        // rotate has no `from` keyframe and starts at the rotation of the node
+        assert!(world
+            .get::<Transform>(node)
+            .unwrap()
+            .rotation
+            .abs_diff_eq(Quat::from_rotation_z(22.5f32.to_radians()), 1e-6));
+
+        advance(&mut world, 1000);
+        assert_eq!(world.get::<Style>(node).unwrap().width, Val::Px(200.0));
+        assert!(world.get::<AnimationStyle>(node).unwrap().is_finished());
+
+        world.get_mut::<AnimationStyle>(node).unwrap().restart();
+        advance(&mut world, 500);
+        assert_eq!(world.get::<Style>(node).unwrap().width, Val::Px(150.0));
+    }
+
+    #[test]
+    fn test_play_keyframe_animations_units() {
+        let mut world = World::new();
+        let mut window = Window::default();
+        window.resolution.set(800.0, 600.0);
+        world.spawn((window, PrimaryWindow));
+        let mut registry = KeyframesRegistry::default();
+        registry.insert(
+            keyframes_result_parser(
+                "@keyframes grow { from { width: 100px; top: 0px } to { width: 50%; top: 10% } }",
+            )
+            .unwrap(),
+        );
+        world.insert_resource(registry);
+        world.insert_resource(Time::<()>::default());
+        let animations = animation_result_parser("grow 1s linear").unwrap();
+        let node = world
+            .spawn((AnimationStyle::new(animations), Style::default()))
+            .id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(play_keyframe_animations);
+        world
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_millis(250));
+        schedule.run(&mut world);
+
+        // percentages of nodes without a parent refer to the size of the window
+        let style = world.get::<Style>(node).unwrap();
+        assert_eq!(style.width, Val::Px(175.0));
+        assert_eq!(style.top, Val::Px(15.0));
+    }
+
+    #[test]
+    fn test_play_keyframe_animations_restores_values() {
+        let mut world = World::new();
+        let mut registry = KeyframesRegistry::default();
+        registry.insert(
+            keyframes_result_parser(
+                "@keyframes pulse { from { background-color: #000; width: 100px } to { background-color: #fff; width: 200px } }",
+            )
+            .unwrap(),
+        );
+        world.insert_resource(registry);
+        world.insert_resource(Time::<()>::default());
+        let animations = animation_result_parser("pulse 1s linear").unwrap();
+        let style = Style {
+            width: Val::Percent(50.0),
+            height: Val::Px(10.0),
+            ..Default::default()
+        };
+        let node = world
+            .spawn((
+                AnimationStyle::new(animations),
+                style.clone(),
+                BackgroundColor(Color::RED),
+            ))
+            .id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(play_keyframe_animations);
+        let mut advance = |world: &mut World, millis| {
+            world
+                .resource_mut::<Time>()
+                .advance_by(Duration::from_millis(millis));
+            schedule.run(world);
+        };
+
+        advance(&mut world, 500);
+        assert_eq!(world.get::<Style>(node).unwrap().width, Val::Px(150.0));
+        assert_eq!(world.get::<Style>(node).unwrap().height, Val::Px(10.0));
+
+        advance(&mut world, 1000);
+        assert_eq!(world.get::<Style>(node).unwrap(), &style);
+        assert_eq!(world.get::<BackgroundColor>(node).unwrap().0, Color::RED);
+
+        // the restored values are not written again
+        world.clear_trackers();
+        advance(&mut world, 100);
+        assert!(!world.entity(node).get_ref::<Style>().unwrap().is_changed());
+        assert!(!world
+            .entity(node)
+            .get_ref::<BackgroundColor>()
+            .unwrap()
+            .is_changed());
+    }
+
+    #[test]
+    fn test_play_keyframe_animations_implicit_from() {
+        let mut world = World::new();
+        let mut registry = KeyframesRegistry::default();
+        registry
+            .insert(keyframes_result_parser("@keyframes grow { to { width: 100px } }").unwrap());
+        world.insert_resource(registry);
+        world.insert_resource(Time::<()>::default());
+        let animations = animation_result_parser("grow 1s linear").unwrap();
+        let style = Style {
+            width: Val::Px(20.0),
+            ..Default::default()
+        };
+        let node = world.spawn((AnimationStyle::new(animations), style)).id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(play_keyframe_animations);
+        let mut advance = |world: &mut World, millis| {
+            world
+                .resource_mut::<Time>()
+                .advance_by(Duration::from_millis(millis));
+            schedule.run(world);
+        };
+
+        advance(&mut world, 0);
+        assert_eq!(world.get::<Style>(node).unwrap().width, Val::Px(20.0));
+        advance(&mut world, 500);
+        assert_eq!(world.get::<Style>(node).unwrap().width, Val::Px(60.0));
+        // the base value is kept while the width is animated
+        advance(&mut world, 250);
+        assert_eq!(world.get::<Style>(node).unwrap().width, Val::Px(80.0));
+    }
+}
@@ -0,0 +1,267 @@
+use bevy::{
+    app::{App, Plugin, PostUpdate},
+    ecs::{
+        change_detection::DetectChangesMut,
+        component::Component,
+        entity::Entity,
+        query::With,
+        schedule::IntoSystemConfigs,
+        system::{ParamSet, Query},
+    },
+    hierarchy::Parent,
+    math::Vec2,
+    ui::{Node, Style, Val},
+    window::{PrimaryWindow, Window},
+};
+
+use super::{configure_style_systems, StyleSystem};
+use crate::{CalcContext, CalcExpr};
+
+/// Component with [`CalcExpr`] lengths for a ui node, resolved into its [`Style`] every frame
+///
+/// Percentages of horizontal properties (`width`, `left`, ...) refer to the width of the
+/// content box of the parent node, its size without padding and border, those of vertical
+/// properties to its height, like the percentages of the ui layout. Nodes without a parent
+/// refer to the size of the primary window instead. The `flex_basis` refers to the width
+/// of the parent, as is the case for rows. Like in css, the percentages of `column_gap`
+/// and `row_gap` refer to the width and height of the content box of the node itself.
+///
+/// Properties that are `None` are left untouched, as are properties whose expression
+/// doesn't resolve to a finite length, like a division by zero.
+#[derive(Component, Debug, Clone, Default, PartialEq)]
+pub struct CalcStyle {
+    pub width: Option<CalcExpr>,
+    pub height: Option<CalcExpr>,
+    pub min_width: Option<CalcExpr>,
+    pub min_height: Option<CalcExpr>,
+    pub max_width: Option<CalcExpr>,
+    pub max_height: Option<CalcExpr>,
+    pub left: Option<CalcExpr>,
+    pub right: Option<CalcExpr>,
+    pub top: Option<CalcExpr>,
+    pub bottom: Option<CalcExpr>,
+    pub flex_basis: Option<CalcExpr>,
+    pub row_gap: Option<CalcExpr>,
+    pub column_gap: Option<CalcExpr>,
+}
+
+impl CalcStyle {
+    /// Writes the resolved lengths into the style, returns whether a property changed
+    ///
+    /// `parent_size` is the content box of the parent and `size` that of the node itself,
+    /// which the percentages of the gaps refer to.
+    pub fn apply(&self, style: &mut Style, parent_size: Vec2, size: Vec2, viewport: Vec2) -> bool {
+        let mut changed = false;
+        let context = |percent_base| CalcContext {
+            percent_base,
+            viewport,
+        };
+        let (horizontal, vertical) = (context(parent_size.x), context(parent_size.y));
+        let (row_gap, column_gap) = (context(size.y), context(size.x));
+        let properties = [
+            (&self.width, &mut style.width, &horizontal),
+            (&self.height, &mut style.height, &vertical),
+            (&self.min_width, &mut style.min_width, &horizontal),
+            (&self.min_height, &mut style.min_height, &vertical),
+            (&self.max_width, &mut style.max_width, &horizontal),
+            (&self.max_height, &mut style.max_height, &vertical),
+            (&self.left, &mut style.left, &horizontal),
+            (&self.right, &mut style.right, &horizontal),
+            (&self.top, &mut style.top, &vertical),
+            (&self.bottom, &mut style.bottom, &vertical),
+            (&self.flex_basis, &mut style.flex_basis, &horizontal),
+            (&self.row_gap, &mut style.row_gap, &row_gap),
+            (&self.column_gap, &mut style.column_gap, &column_gap),
+        ];
+        for (expr, val, context) in properties {
+            if let Some(resolved) = expr.as_ref().and_then(|expr| expr.resolve_val(context)) {
+                changed |= *val != resolved;
+                *val = resolved;
+            }
+        }
+        changed
+    }
+}
+
+/// The components of a ui node that its content box is computed from, see [`content_size`]
+pub(crate) type LayoutComponents = (&'static Node, &'static Style, Option<&'static Parent>);
+
+/// Returns the content box of a ui node from the previous layout, its size without
+/// padding and border, `None` if the entity has no [`Node`]
+///
+/// The percentages of the padding and border refer to the width of the content box of
+/// the parent in both axes, like in css, or to the width of the viewport for the root.
+pub(crate) fn content_size(
+    entity: Entity,
+    layout: &Query<LayoutComponents>,
+    viewport: Vec2,
+) -> Option<Vec2> {
+    let (node, style, parent) = layout.get(entity).ok()?;
+    let containing_width = parent
+        .and_then(|parent| content_size(parent.get(), layout, viewport))
+        .unwrap_or(viewport)
+        .x;
+    let context = CalcContext {
+        percent_base: containing_width,
+        viewport,
+    };
+    let resolve = |vals: [Val; 4]| -> f32 {
+        vals.into_iter()
+            .filter_map(|val| CalcExpr::Length(val).resolve(&context))
+            .sum()
+    };
+    let (padding, border) = (style.padding, style.border);
+    let inset = Vec2::new(
+        resolve([padding.left, padding.right, border.left, border.right]),
+        resolve([padding.top, padding.bottom, border.top, border.bottom]),
+    );
+    Some((node.size() - inset).max(Vec2::ZERO))
+}
+
+/// The components of a ui node that are written by [`resolve_calc_styles`]
+type CalcComponents = (&'static CalcStyle, &'static mut Style);
+
+/// System that resolves the [`CalcStyle`] of ui nodes into their [`Style`]
+///
+/// The sizes of the nodes are those of the previous layout, so changes
+/// of their size take effect one frame later.
+pub fn resolve_calc_styles(
+    windows: Query<&Window, With<PrimaryWindow>>,
+    calc_nodes: Query<(Entity, Option<&Parent>), With<CalcStyle>>,
+    mut styles: ParamSet<(Query<LayoutComponents>, Query<CalcComponents>)>,
+) {
+    let viewport = windows
+        .get_single()
+        .map(|window| Vec2::new(window.resolution.width(), window.resolution.height()))
+        .unwrap_or(Vec2::ZERO);
+    // the sizes are read before the styles are written, the layout reads the styles too
+    let sizes: Vec<_> = calc_nodes
+        .iter()
+        .map(|(entity, parent)| {
+            let layout = styles.p0();
+            let parent_size = parent
+                .and_then(|parent| content_size(parent.get(), &layout, viewport))
+                .unwrap_or(viewport);
+            let size = content_size(entity, &layout, viewport).unwrap_or(Vec2::ZERO);
+            (entity, parent_size, size)
+        })
+        .collect();
+    let mut nodes = styles.p1();
+    for (entity, parent_size, size) in sizes {
+        let Ok((calc_style, mut style)) = nodes.get_mut(entity) else {
+            continue;
+        };
+        // the style is only marked as changed if a resolved length differs
+        if calc_style.apply(style.bypass_change_detection(), parent_size, size, viewport) {
+            style.set_changed();
+        }
+    }
+}
+
+/// Plugin that adds the [`resolve_calc_styles`] system in [`StyleSystem::Calc`],
+/// running before the ui layout
+pub struct CalcPlugin;
+
+impl Plugin for CalcPlugin {
+    fn build(&self, app: &mut App) {
+        configure_style_systems(app);
+        app.add_systems(PostUpdate, resolve_calc_styles.in_set(StyleSystem::Calc));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calc_string_parser;
+    use bevy::{
+        ecs::{schedule::Schedule, world::World},
+        hierarchy::BuildWorldChildren,
+        reflect::Struct,
+        ui::UiRect,
+    };
+
+    /// Creates a node with the size of a previous layout
+    fn laid_out_node(size: Vec2) -> Node {
+        let mut node = Node::default();
+        node.field_mut("calculated_size").unwrap().apply(&size);
+        node
+    }
+
+    #[test]
+    fn test_resolve_calc_styles() {
+        let mut world = World::new();
+        let mut window = Window::default();
+        window.resolution.set(800.0, 600.0);
+        world.spawn((window, PrimaryWindow));
+        let calc_style = CalcStyle {
+            width: calc_string_parser("calc(100% - 20px)"),
+            height: calc_string_parser("clamp(100px, 50%, 200px)"),
+            left: calc_string_parser("calc(10vw / 2)"),
+            right: calc_string_parser("calc(100% / (2 - 2))"),
+            ..Default::default()
+        };
+        let node = world.spawn((calc_style, Style::default())).id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(resolve_calc_styles);
+        schedule.run(&mut world);
+
+        let style = world.get::<Style>(node).unwrap();
+        assert_eq!(style.width, Val::Px(780.0));
+        assert_eq!(style.height, Val::Px(200.0));
+        assert_eq!(style.left, Val::Px(40.0));
+        assert_eq!(style.top, Val::Auto);
+        assert_eq!(style.right, Val::Auto);
+    }
+
+    #[test]
+    fn test_resolve_calc_styles_padded_parent() {
+        let mut world = World::new();
+        let mut window = Window::default();
+        window.resolution.set(800.0, 600.0);
+        world.spawn((window, PrimaryWindow));
+        let parent_style = Style {
+            padding: UiRect::new(
+                Val::Percent(5.0),
+                Val::Px(10.0),
+                Val::Px(10.0),
+                Val::Px(10.0),
+            ),
+            border: UiRect::all(Val::Px(5.0)),
+            ..Default::default()
+        };
+        let calc_style = CalcStyle {
+            width: calc_string_parser("calc(100%)"),
+            height: calc_string_parser("calc(50%)"),
+            column_gap: calc_string_parser("calc(50%)"),
+            row_gap: calc_string_parser("calc(10%)"),
+            ..Default::default()
+        };
+        let child_style = Style {
+            padding: UiRect::all(Val::Px(10.0)),
+            ..Default::default()
+        };
+        let parent = world
+            .spawn((laid_out_node(Vec2::new(400.0, 300.0)), parent_style))
+            .id();
+        let node = world
+            .spawn((
+                laid_out_node(Vec2::new(200.0, 100.0)),
+                calc_style,
+                child_style,
+            ))
+            .set_parent(parent)
+            .id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(resolve_calc_styles);
+        schedule.run(&mut world);
+
+        // the padding percentage of the root refers to the width of the window
+        let style = world.get::<Style>(node).unwrap();
+        assert_eq!(style.width, Val::Px(400.0 - 40.0 - 10.0 - 10.0));
+        assert_eq!(style.height, Val::Px((300.0 - 20.0 - 10.0) / 2.0));
+        assert_eq!(style.column_gap, Val::Px(90.0));
+        assert_eq!(style.row_gap, Val::Px(8.0));
+    }
+}
@@ -0,0 +1,87 @@
+use bevy::{
+    app::{App, Plugin, PostUpdate},
+    ecs::{component::Component, schedule::IntoSystemConfigs, system::Query},
+    hierarchy::Parent,
+    ui::Style,
+};
+
+use super::{configure_style_systems, StyleSystem};
+use crate::GridTemplateAreas;
+
+/// Component that places a ui node in a named area of its parent's [`GridTemplateAreas`]
+///
+/// The placement is written into the `grid_row` and `grid_column` of its [`Style`],
+/// nodes whose parent has no area with the name are left untouched.
+#[derive(Component, Debug, Clone, Default, PartialEq, Eq)]
+pub struct GridAreaName(pub String);
+
+/// System that places ui nodes with a [`GridAreaName`] in the areas of their parent
+pub fn resolve_grid_areas(
+    containers: Query<&GridTemplateAreas>,
+    mut items: Query<(&GridAreaName, &Parent, &mut Style)>,
+) {
+    for (name, parent, mut style) in items.iter_mut() {
+        let Some((row, column)) = containers
+            .get(parent.get())
+            .ok()
+            .and_then(|areas| areas.get(&name.0))
+        else {
+            continue;
+        };
+        // only touch the style if the placement changed, to keep change detection quiet
+        if style.grid_row != row || style.grid_column != column {
+            style.grid_row = row;
+            style.grid_column = column;
+        }
+    }
+}
+
+/// Plugin that adds the [`resolve_grid_areas`] system in [`StyleSystem::GridAreas`],
+/// running before the ui layout
+pub struct GridAreasPlugin;
+
+impl Plugin for GridAreasPlugin {
+    fn build(&self, app: &mut App) {
+        configure_style_systems(app);
+        app.add_systems(
+            PostUpdate,
+            resolve_grid_areas.in_set(StyleSystem::GridAreas),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid_template_areas_string_parser;
+    use bevy::{
+        ecs::{schedule::Schedule, world::World},
+        hierarchy::BuildWorldChildren,
+        ui::GridPlacement,
+    };
+
+    #[test]
+    fn test_resolve_grid_areas() {
+        let mut world = World::new();
+        let areas = grid_template_areas_string_parser(r#""header header" "sidebar main""#);
+        let container = world.spawn((areas.unwrap(), Style::default())).id();
+        let main = world
+            .spawn((GridAreaName("main".to_string()), Style::default()))
+            .set_parent(container)
+            .id();
+        let unknown = world
+            .spawn((GridAreaName("footer".to_string()), Style::default()))
+            .set_parent(container)
+            .id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(resolve_grid_areas);
+        schedule.run(&mut world);
+
+        let style = world.get::<Style>(main).unwrap();
+        assert_eq!(style.grid_row, GridPlacement::start_end(2, 3));
+        assert_eq!(style.grid_column, GridPlacement::start_end(2, 3));
+        let style = world.get::<Style>(unknown).unwrap();
+        assert_eq!(style.grid_row, GridPlacement::default());
+    }
+}
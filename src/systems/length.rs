@@ -0,0 +1,208 @@
+use bevy::{
+    app::{App, Plugin, PostUpdate},
+    ecs::{
+        change_detection::DetectChangesMut,
+        component::Component,
+        entity::Entity,
+        schedule::IntoSystemConfigs,
+        system::{Query, Res},
+    },
+    hierarchy::Parent,
+    text::Text,
+    ui::Style,
+};
+
+use super::{configure_style_systems, StyleSystem};
+use crate::{Length, LengthContext, LengthRect};
+
+/// Component with [`Length`]s for a ui node, resolved into its [`Style`] every frame
+///
+/// The font size of `em` and `ch` is that of the first section of the [`Text`]
+/// of the node, nodes without text inherit it from the nearest ancestor with text,
+/// like in css. Otherwise the font size of the [`LengthContext`] resource is used,
+/// or its default if the resource doesn't exist.
+///
+/// Properties that are `None` are left untouched.
+#[derive(Component, Debug, Clone, Default, PartialEq)]
+pub struct LengthStyle {
+    pub width: Option<Length>,
+    pub height: Option<Length>,
+    pub min_width: Option<Length>,
+    pub min_height: Option<Length>,
+    pub max_width: Option<Length>,
+    pub max_height: Option<Length>,
+    pub left: Option<Length>,
+    pub right: Option<Length>,
+    pub top: Option<Length>,
+    pub bottom: Option<Length>,
+    pub flex_basis: Option<Length>,
+    pub row_gap: Option<Length>,
+    pub column_gap: Option<Length>,
+    pub margin: Option<LengthRect>,
+    pub padding: Option<LengthRect>,
+    pub border: Option<LengthRect>,
+}
+
+impl LengthStyle {
+    /// Writes the resolved lengths into the style, returns whether a property changed
+    pub fn apply(&self, style: &mut Style, context: &LengthContext) -> bool {
+        let mut changed = false;
+        let properties = [
+            (&self.width, &mut style.width),
+            (&self.height, &mut style.height),
+            (&self.min_width, &mut style.min_width),
+            (&self.min_height, &mut style.min_height),
+            (&self.max_width, &mut style.max_width),
+            (&self.max_height, &mut style.max_height),
+            (&self.left, &mut style.left),
+            (&self.right, &mut style.right),
+            (&self.top, &mut style.top),
+            (&self.bottom, &mut style.bottom),
+            (&self.flex_basis, &mut style.flex_basis),
+            (&self.row_gap, &mut style.row_gap),
+            (&self.column_gap, &mut style.column_gap),
+        ];
+        for (length, val) in properties {
+            if let Some(length) = length {
+                let resolved = length.resolve(context);
+                changed |= *val != resolved;
+                *val = resolved;
+            }
+        }
+        let rects = [
+            (&self.margin, &mut style.margin),
+            (&self.padding, &mut style.padding),
+            (&self.border, &mut style.border),
+        ];
+        for (lengths, rect) in rects {
+            if let Some(lengths) = lengths {
+                let resolved = lengths.resolve(context);
+                changed |= *rect != resolved;
+                *rect = resolved;
+            }
+        }
+        changed
+    }
+}
+
+/// System that resolves the [`LengthStyle`] of ui nodes into their [`Style`]
+pub fn resolve_length_styles(
+    context: Option<Res<LengthContext>>,
+    texts: Query<&Text>,
+    parents: Query<&Parent>,
+    mut nodes: Query<(Entity, &LengthStyle, &mut Style)>,
+) {
+    let context = context.map(|context| *context).unwrap_or_default();
+    for (entity, length_style, mut style) in nodes.iter_mut() {
+        let mut ancestors = std::iter::successors(Some(entity), |entity| {
+            parents.get(*entity).ok().map(Parent::get)
+        });
+        let font_size = ancestors
+            .find_map(|entity| texts.get(entity).ok()?.sections.first())
+            .map(|section| section.style.font_size)
+            .unwrap_or(context.font_size);
+        let context = LengthContext {
+            font_size,
+            ..context
+        };
+        // the style is only marked as changed if a resolved length differs
+        if length_style.apply(style.bypass_change_detection(), &context) {
+            style.set_changed();
+        }
+    }
+}
+
+/// Plugin that adds the [`resolve_length_styles`] system in [`StyleSystem::Lengths`],
+/// running before the ui layout
+pub struct LengthPlugin;
+
+impl Plugin for LengthPlugin {
+    fn build(&self, app: &mut App) {
+        configure_style_systems(app);
+        app.add_systems(
+            PostUpdate,
+            resolve_length_styles.in_set(StyleSystem::Lengths),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{length_rect_string_parser, length_string_parser};
+    use bevy::{
+        ecs::{query::Changed, schedule::Schedule, world::World},
+        hierarchy::BuildWorldChildren,
+        text::TextStyle,
+        ui::{UiRect, Val},
+    };
+
+    #[test]
+    fn test_resolve_length_styles() {
+        let mut world = World::new();
+        world.insert_resource(LengthContext {
+            root_font_size: 20.0,
+            ..Default::default()
+        });
+        let length_style = LengthStyle {
+            width: length_string_parser("2em"),
+            height: length_string_parser("1rem"),
+            padding: length_rect_string_parser("0.5em 1in"),
+            ..Default::default()
+        };
+        let text = Text::from_section(
+            "",
+            TextStyle {
+                font_size: 30.0,
+                ..Default::default()
+            },
+        );
+        let node = world.spawn((length_style.clone(), Style::default())).id();
+        let text_node = world
+            .spawn((length_style.clone(), Style::default(), text))
+            .id();
+        let child = world
+            .spawn((length_style, Style::default()))
+            .set_parent(text_node)
+            .id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(resolve_length_styles);
+        schedule.run(&mut world);
+
+        let style = world.get::<Style>(node).unwrap();
+        assert_eq!(style.width, Val::Px(32.0));
+        assert_eq!(style.height, Val::Px(20.0));
+        assert_eq!(
+            style.padding,
+            UiRect::new(Val::Px(96.0), Val::Px(96.0), Val::Px(8.0), Val::Px(8.0))
+        );
+        let style = world.get::<Style>(text_node).unwrap();
+        assert_eq!(style.width, Val::Px(60.0));
+        assert_eq!(style.height, Val::Px(20.0));
+        assert_eq!(style.left, Val::Auto);
+        let style = world.get::<Style>(child).unwrap();
+        assert_eq!(style.width, Val::Px(60.0));
+    }
+
+    #[test]
+    fn test_resolve_length_styles_change_detection() {
+        let mut world = World::new();
+        let length_style = LengthStyle {
+            width: length_string_parser("1em"),
+            ..Default::default()
+        };
+        let node = world.spawn((length_style, Style::default())).id();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(resolve_length_styles);
+        schedule.run(&mut world);
+        world.clear_trackers();
+
+        schedule.run(&mut world);
+        let mut query = world.query_filtered::<Entity, Changed<Style>>();
+        assert_eq!(query.iter(&world).count(), 0);
+        world.get_mut::<LengthStyle>(node).unwrap().width = length_string_parser("2em");
+        schedule.run(&mut world);
+        assert_eq!(query.iter(&world).collect::<Vec<_>>(), vec![node]);
+    }
+}
@@ -0,0 +1,80 @@
+mod animation;
+mod calc;
+mod grid_areas;
+mod length;
+
+use bevy::{
+    app::{App, PostUpdate},
+    ecs::schedule::{IntoSystemSetConfigs, SystemSet},
+    ui::UiSystem,
+};
+
+pub use animation::{play_keyframe_animations, KeyframesPlugin};
+pub use animation::{AnimationStyle, KeyframesRegistry};
+pub use calc::{resolve_calc_styles, CalcPlugin, CalcStyle};
+pub use grid_areas::{resolve_grid_areas, GridAreaName, GridAreasPlugin};
+pub use length::{resolve_length_styles, LengthPlugin, LengthStyle};
+
+/// System sets of the plugins of this crate, running in this order in `PostUpdate`
+/// before [`UiSystem::Layout`]
+///
+/// If a node has both a [`LengthStyle`] and a [`CalcStyle`] for the same property,
+/// the [`CalcStyle`] wins. Animations override both, like in css.
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StyleSystem {
+    /// Runs [`resolve_length_styles`]
+    Lengths,
+    /// Runs [`resolve_calc_styles`]
+    Calc,
+    /// Runs [`resolve_grid_areas`]
+    GridAreas,
+    /// Runs [`play_keyframe_animations`]
+    Animations,
+}
+
+/// Orders the [`StyleSystem`] sets, called by every plugin so that any of them can be
+/// added on its own
+fn configure_style_systems(app: &mut App) {
+    app.configure_sets(
+        PostUpdate,
+        (
+            StyleSystem::Lengths,
+            StyleSystem::Calc,
+            StyleSystem::GridAreas,
+            StyleSystem::Animations,
+        )
+            .chain()
+            .before(UiSystem::Layout),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{calc_string_parser, length_string_parser};
+    use bevy::ui::{Style, Val};
+
+    #[test]
+    fn test_style_systems_order() {
+        let mut app = App::new();
+        app.add_plugins((LengthPlugin, CalcPlugin, GridAreasPlugin));
+        let length_style = LengthStyle {
+            width: length_string_parser("2em"),
+            height: length_string_parser("2em"),
+            ..Default::default()
+        };
+        let calc_style = CalcStyle {
+            width: calc_string_parser("calc(10px + 5px)"),
+            ..Default::default()
+        };
+        let node = app
+            .world
+            .spawn((length_style, calc_style, Style::default()))
+            .id();
+        app.update();
+
+        let style = app.world.get::<Style>(node).unwrap();
+        assert_eq!(style.width, Val::Px(15.0));
+        assert_eq!(style.height, Val::Px(32.0));
+    }
+}